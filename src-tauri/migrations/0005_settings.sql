@@ -0,0 +1,8 @@
+-- =========================
+-- 设置表：键值对形式保存应用配置（取整规则等），值为 JSON 文本
+-- =========================
+CREATE TABLE IF NOT EXISTS settings (
+  key TEXT PRIMARY KEY,                               -- 配置键，例如 rounding_policy
+  value TEXT NOT NULL,                                -- 配置值（JSON 序列化后的文本）
+  updated_at TEXT NOT NULL DEFAULT (datetime('now'))  -- 更新时间（UTC）
+);
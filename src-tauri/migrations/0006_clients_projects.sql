@@ -0,0 +1,31 @@
+-- =========================
+-- 客户表：项目归属的客户（用于计费、按客户汇总统计）
+-- =========================
+CREATE TABLE IF NOT EXISTS clients (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,               -- 客户ID，自增主键
+  name TEXT NOT NULL UNIQUE,                          -- 客户名称（唯一）
+  created_at TEXT NOT NULL DEFAULT (datetime('now'))  -- 创建时间（UTC）
+);
+
+
+-- =========================
+-- 项目表：客户 → 项目 → 任务 的中间层级
+-- =========================
+CREATE TABLE IF NOT EXISTS projects (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,               -- 项目ID，自增主键
+  client_id INTEGER,                                  -- 所属客户ID（可选，内部项目可不关联客户）
+  name TEXT NOT NULL,                                 -- 项目名称（同一客户下唯一）
+  created_at TEXT NOT NULL DEFAULT (datetime('now')), -- 创建时间（UTC）
+
+  FOREIGN KEY (client_id) REFERENCES clients(id) ON DELETE SET NULL,
+  UNIQUE (client_id, name)
+);
+
+-- 索引：按客户查询项目
+CREATE INDEX IF NOT EXISTS idx_projects_client_id ON projects(client_id);
+
+-- 任务可选关联项目（与分类并存）
+ALTER TABLE tasks ADD COLUMN project_id INTEGER REFERENCES projects(id) ON DELETE SET NULL;
+
+-- 索引：按项目筛选任务、统计聚合
+CREATE INDEX IF NOT EXISTS idx_tasks_project_id ON tasks(project_id);
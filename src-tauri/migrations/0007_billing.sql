@@ -0,0 +1,20 @@
+-- =========================
+-- 计费：可计费标记与小时费率
+-- 费率统一以最小货币单位（如“分”）的整数保存，避免浮点误差；币种见 settings.currency
+-- 生效费率取最具体的一级：任务 > 项目 > 分类
+-- =========================
+
+-- 任务是否可计费：0=否，1=是
+ALTER TABLE tasks ADD COLUMN billable INTEGER NOT NULL DEFAULT 0;
+
+-- 任务级小时费率（最小货币单位/小时，可选）
+ALTER TABLE tasks ADD COLUMN hourly_rate INTEGER;
+
+-- 项目级小时费率（最小货币单位/小时，可选）
+ALTER TABLE projects ADD COLUMN hourly_rate INTEGER;
+
+-- 分类级小时费率（最小货币单位/小时，可选）
+ALTER TABLE categories ADD COLUMN hourly_rate INTEGER;
+
+-- 计时记录是否可计费（可选）：NULL=沿用任务设置，0/1=单独覆盖
+ALTER TABLE time_entries ADD COLUMN billable INTEGER;
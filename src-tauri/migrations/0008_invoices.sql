@@ -0,0 +1,54 @@
+-- =========================
+-- 发票表：按客户与日期范围从可计费时间生成，编号顺序递增
+-- =========================
+CREATE TABLE IF NOT EXISTS invoices (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,               -- 发票ID，自增主键
+  seq INTEGER NOT NULL UNIQUE,                        -- 顺序号（从 1 开始递增）
+  number TEXT NOT NULL UNIQUE,                        -- 发票编号，例如 INV-00001
+  client_id INTEGER NOT NULL,                         -- 开票客户ID，对应 clients.id
+  period_start TEXT NOT NULL,                         -- 计费起始日期 YYYY-MM-DD（含）
+  period_end TEXT NOT NULL,                           -- 计费结束日期 YYYY-MM-DD（含）
+  currency TEXT NOT NULL,                             -- 币种（ISO 4217）
+  total_seconds INTEGER NOT NULL,                     -- 计费总时长（秒，已取整）
+  total_amount INTEGER NOT NULL,                      -- 总金额（最小货币单位）
+  created_at TEXT NOT NULL DEFAULT (datetime('now')), -- 创建时间（UTC）
+
+  FOREIGN KEY (client_id) REFERENCES clients(id) ON DELETE RESTRICT
+);
+
+-- 索引：按客户查询发票
+CREATE INDEX IF NOT EXISTS idx_invoices_client_id ON invoices(client_id);
+
+
+-- =========================
+-- 发票明细表：每个任务一行
+-- =========================
+CREATE TABLE IF NOT EXISTS invoice_lines (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,               -- 明细ID，自增主键
+  invoice_id INTEGER NOT NULL,                        -- 所属发票ID
+  task_id INTEGER,                                    -- 对应任务ID（任务删除后置空，明细保留）
+  description TEXT NOT NULL,                          -- 明细描述（项目 / 任务标题）
+  seconds INTEGER NOT NULL,                           -- 计费时长（秒，已取整）
+  hourly_rate INTEGER NOT NULL,                       -- 小时费率（最小货币单位）
+  amount INTEGER NOT NULL,                            -- 金额（最小货币单位）
+  position INTEGER NOT NULL DEFAULT 0,                -- 排序
+
+  FOREIGN KEY (invoice_id) REFERENCES invoices(id) ON DELETE CASCADE,
+  FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE SET NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_invoice_lines_invoice_id ON invoice_lines(invoice_id);
+
+
+-- =========================
+-- 已开票计时记录：主键保证同一条计时记录只能开票一次
+-- =========================
+CREATE TABLE IF NOT EXISTS invoice_entries (
+  time_entry_id INTEGER PRIMARY KEY,                  -- 计时记录ID，对应 time_entries.id
+  invoice_id INTEGER NOT NULL,                        -- 所属发票ID
+
+  FOREIGN KEY (time_entry_id) REFERENCES time_entries(id) ON DELETE CASCADE,
+  FOREIGN KEY (invoice_id) REFERENCES invoices(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_invoice_entries_invoice_id ON invoice_entries(invoice_id);
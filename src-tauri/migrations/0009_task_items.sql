@@ -0,0 +1,17 @@
+-- =========================
+-- 子任务/检查项表：任务下的有序清单
+-- =========================
+CREATE TABLE IF NOT EXISTS task_items (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,               -- 检查项ID，自增主键
+  task_id INTEGER NOT NULL,                           -- 所属任务ID，对应 tasks.id
+  title TEXT NOT NULL,                                -- 检查项标题
+  is_done INTEGER NOT NULL DEFAULT 0,                 -- 是否完成：0/1
+  position INTEGER NOT NULL DEFAULT 0,                -- 排序（拖拽用，越小越靠上）
+  created_at TEXT NOT NULL DEFAULT (datetime('now')), -- 创建时间（UTC）
+  updated_at TEXT NOT NULL DEFAULT (datetime('now')), -- 更新时间（UTC）
+
+  FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+-- 索引：按任务取出有序清单、统计完成进度
+CREATE INDEX IF NOT EXISTS idx_task_items_task_id ON task_items(task_id, position);
@@ -0,0 +1,25 @@
+-- =========================
+-- 标签表：跨分类的自由标签，例如 bug / review / oncall
+-- =========================
+CREATE TABLE IF NOT EXISTS tags (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,               -- 标签ID，自增主键
+  name TEXT NOT NULL UNIQUE COLLATE NOCASE,           -- 标签名称（唯一，不区分大小写）
+  color TEXT NOT NULL DEFAULT '#9CA3AF',              -- 标签颜色（Hex）
+  created_at TEXT NOT NULL DEFAULT (datetime('now'))  -- 创建时间（UTC）
+);
+
+
+-- =========================
+-- 任务-标签关联表：一个任务可有多个标签
+-- =========================
+CREATE TABLE IF NOT EXISTS task_tags (
+  task_id INTEGER NOT NULL,                           -- 任务ID，对应 tasks.id
+  tag_id INTEGER NOT NULL,                            -- 标签ID，对应 tags.id
+
+  PRIMARY KEY (task_id, tag_id),
+  FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+  FOREIGN KEY (tag_id) REFERENCES tags(id) ON DELETE CASCADE
+);
+
+-- 索引：按标签筛选任务、按标签统计
+CREATE INDEX IF NOT EXISTS idx_task_tags_tag_id ON task_tags(tag_id);
@@ -0,0 +1,14 @@
+-- =========================
+-- 任务日期：截止日期与计划日期
+-- 均为本地日历日期（YYYY-MM-DD），与 UTC 时间戳不同，不做时区换算
+-- =========================
+
+-- 截止日期（可选）
+ALTER TABLE tasks ADD COLUMN due_date TEXT;
+
+-- 计划处理日期（可选）
+ALTER TABLE tasks ADD COLUMN scheduled_date TEXT;
+
+-- 索引：逾期/到期视图与按截止日期排序
+CREATE INDEX IF NOT EXISTS idx_tasks_due_date ON tasks(due_date);
+CREATE INDEX IF NOT EXISTS idx_tasks_scheduled_date ON tasks(scheduled_date);
@@ -0,0 +1,30 @@
+-- =========================
+-- 周期任务：任务模板 + 重复规则（RFC 5545 RRULE 子集）
+-- 后台任务按规则生成任务实例，实例通过 tasks.recurring_task_id 关联回模板
+-- =========================
+CREATE TABLE IF NOT EXISTS recurring_tasks (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,               -- 周期任务ID，自增主键
+  title TEXT NOT NULL,                                -- 实例标题
+  category_id INTEGER,                                -- 实例分类ID，对应 categories.id（可选）
+  project_id INTEGER,                                 -- 实例项目ID，对应 projects.id（可选）
+  estimate_seconds INTEGER,                           -- 实例预估用时（秒，可选）
+  notes TEXT,                                         -- 实例备注（可选）
+  billable INTEGER NOT NULL DEFAULT 0,                -- 实例是否可计费：0/1
+  hourly_rate INTEGER,                                -- 实例小时费率（最小货币单位，可选）
+  rrule TEXT NOT NULL,                                -- 重复规则，如 FREQ=WEEKLY;BYDAY=MO,WE
+  starts_on TEXT NOT NULL,                            -- 规则起始日期（本地日期 YYYY-MM-DD）
+  next_date TEXT,                                     -- 下一个待生成实例的日期；NULL 表示规则已结束
+  is_active INTEGER NOT NULL DEFAULT 1,               -- 是否启用：0=暂停，1=启用
+  created_at TEXT NOT NULL DEFAULT (datetime('now')), -- 创建时间（UTC）
+  updated_at TEXT NOT NULL DEFAULT (datetime('now')), -- 更新时间（UTC）
+
+  FOREIGN KEY (category_id) REFERENCES categories(id) ON DELETE SET NULL,
+  FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE SET NULL
+);
+
+-- 任务实例所属的周期任务（可选）：删除周期任务后实例保留
+ALTER TABLE tasks ADD COLUMN recurring_task_id INTEGER REFERENCES recurring_tasks(id) ON DELETE SET NULL;
+
+-- 索引：后台任务查找到期规则、按周期任务汇总实例
+CREATE INDEX IF NOT EXISTS idx_recurring_tasks_next_date ON recurring_tasks(is_active, next_date);
+CREATE INDEX IF NOT EXISTS idx_tasks_recurring_task_id ON tasks(recurring_task_id);
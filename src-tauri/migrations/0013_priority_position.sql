@@ -0,0 +1,16 @@
+-- =========================
+-- 任务优先级与手动排序
+-- =========================
+
+-- 优先级：0=P0（最高）... 3=P3（最低），默认 P2
+ALTER TABLE tasks ADD COLUMN priority INTEGER NOT NULL DEFAULT 2;
+
+-- 手动排序位置（拖拽用，越小越靠上）
+ALTER TABLE tasks ADD COLUMN position INTEGER NOT NULL DEFAULT 0;
+
+-- 已有任务按创建顺序初始化手动排序
+UPDATE tasks SET position = id;
+
+-- 索引：按优先级、手动顺序排序
+CREATE INDEX IF NOT EXISTS idx_tasks_priority ON tasks(priority);
+CREATE INDEX IF NOT EXISTS idx_tasks_position ON tasks(position);
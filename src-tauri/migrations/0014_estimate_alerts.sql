@@ -0,0 +1,6 @@
+-- =========================
+-- 预估超时提醒
+-- =========================
+
+-- 运行中的任务实际用时首次超过预估时记录提醒时间，避免重复提醒；修改预估后清空
+ALTER TABLE tasks ADD COLUMN overrun_notified_at TEXT;
@@ -0,0 +1,50 @@
+-- =========================
+-- 全文搜索：任务标题/备注、计时记录备注的 FTS5 索引
+-- 使用外部内容表（content=），由触发器与原表保持同步
+-- =========================
+CREATE VIRTUAL TABLE IF NOT EXISTS tasks_fts USING fts5(
+  title,
+  notes,
+  content = 'tasks',
+  content_rowid = 'id',
+  tokenize = 'unicode61 remove_diacritics 2'
+);
+
+CREATE VIRTUAL TABLE IF NOT EXISTS time_entries_fts USING fts5(
+  note,
+  content = 'time_entries',
+  content_rowid = 'id',
+  tokenize = 'unicode61 remove_diacritics 2'
+);
+
+-- 任务：新增、删除、修改标题/备注时同步索引
+CREATE TRIGGER IF NOT EXISTS tasks_fts_ai AFTER INSERT ON tasks BEGIN
+  INSERT INTO tasks_fts (rowid, title, notes) VALUES (new.id, new.title, new.notes);
+END;
+
+CREATE TRIGGER IF NOT EXISTS tasks_fts_ad AFTER DELETE ON tasks BEGIN
+  INSERT INTO tasks_fts (tasks_fts, rowid, title, notes) VALUES ('delete', old.id, old.title, old.notes);
+END;
+
+CREATE TRIGGER IF NOT EXISTS tasks_fts_au AFTER UPDATE OF title, notes ON tasks BEGIN
+  INSERT INTO tasks_fts (tasks_fts, rowid, title, notes) VALUES ('delete', old.id, old.title, old.notes);
+  INSERT INTO tasks_fts (rowid, title, notes) VALUES (new.id, new.title, new.notes);
+END;
+
+-- 计时记录：新增、删除、修改备注时同步索引
+CREATE TRIGGER IF NOT EXISTS time_entries_fts_ai AFTER INSERT ON time_entries BEGIN
+  INSERT INTO time_entries_fts (rowid, note) VALUES (new.id, new.note);
+END;
+
+CREATE TRIGGER IF NOT EXISTS time_entries_fts_ad AFTER DELETE ON time_entries BEGIN
+  INSERT INTO time_entries_fts (time_entries_fts, rowid, note) VALUES ('delete', old.id, old.note);
+END;
+
+CREATE TRIGGER IF NOT EXISTS time_entries_fts_au AFTER UPDATE OF note ON time_entries BEGIN
+  INSERT INTO time_entries_fts (time_entries_fts, rowid, note) VALUES ('delete', old.id, old.note);
+  INSERT INTO time_entries_fts (rowid, note) VALUES (new.id, new.note);
+END;
+
+-- 为已有数据建立索引
+INSERT INTO tasks_fts (tasks_fts) VALUES ('rebuild');
+INSERT INTO time_entries_fts (time_entries_fts) VALUES ('rebuild');
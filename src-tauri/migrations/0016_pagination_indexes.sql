@@ -0,0 +1,10 @@
+-- =========================
+-- 分页与列表性能索引
+-- =========================
+
+-- 覆盖索引：任务列表中按任务汇总时长的子查询无需回表
+CREATE INDEX IF NOT EXISTS idx_time_entries_task_started ON time_entries(task_id, started_at, duration_seconds);
+
+-- 游标分页：按创建/更新时间排序的任务列表
+CREATE INDEX IF NOT EXISTS idx_tasks_created_at ON tasks(created_at, id);
+CREATE INDEX IF NOT EXISTS idx_tasks_updated_at ON tasks(updated_at, id);
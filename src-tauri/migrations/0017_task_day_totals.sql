@@ -0,0 +1,64 @@
+-- =========================
+-- 按任务、按天预汇总的计时时长
+-- 只汇总已结束的记录；日期为 date(started_at)，与统计口径一致
+-- 按记录的可计费覆盖值（NULL/0/1）分行，统计时仍可按记录判断是否计费
+-- 由触发器在记录新增、修改、删除时于同一事务内重算受影响的分组
+-- =========================
+CREATE TABLE IF NOT EXISTS task_day_totals (
+  task_id INTEGER NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+  day TEXT NOT NULL,
+  billable INTEGER,
+  seconds INTEGER NOT NULL DEFAULT 0,
+  entry_count INTEGER NOT NULL DEFAULT 0,
+  last_ended_at TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_task_day_totals_task_day ON task_day_totals(task_id, day);
+CREATE INDEX IF NOT EXISTS idx_task_day_totals_day ON task_day_totals(day);
+
+-- 新增：只有直接写入已结束的记录才需要汇总（开始计时的记录尚未结束）
+CREATE TRIGGER IF NOT EXISTS task_day_totals_ai AFTER INSERT ON time_entries
+WHEN new.ended_at IS NOT NULL BEGIN
+  DELETE FROM task_day_totals WHERE task_id = new.task_id AND day = date(new.started_at);
+  INSERT INTO task_day_totals (task_id, day, billable, seconds, entry_count, last_ended_at)
+  SELECT task_id, date(started_at), billable, SUM(duration_seconds), COUNT(*), MAX(ended_at)
+  FROM time_entries
+  WHERE task_id = new.task_id AND date(started_at) = date(new.started_at) AND ended_at IS NOT NULL
+  GROUP BY billable;
+END;
+
+-- 删除：重算原分组
+CREATE TRIGGER IF NOT EXISTS task_day_totals_ad AFTER DELETE ON time_entries
+WHEN old.ended_at IS NOT NULL BEGIN
+  DELETE FROM task_day_totals WHERE task_id = old.task_id AND day = date(old.started_at);
+  INSERT INTO task_day_totals (task_id, day, billable, seconds, entry_count, last_ended_at)
+  SELECT task_id, date(started_at), billable, SUM(duration_seconds), COUNT(*), MAX(ended_at)
+  FROM time_entries
+  WHERE task_id = old.task_id AND date(started_at) = date(old.started_at) AND ended_at IS NOT NULL
+  GROUP BY billable;
+END;
+
+-- 修改（结束计时、编辑时长或时间、调整可计费）：先重算原分组，再重算新分组
+CREATE TRIGGER IF NOT EXISTS task_day_totals_au
+AFTER UPDATE OF task_id, started_at, ended_at, duration_seconds, billable ON time_entries BEGIN
+  DELETE FROM task_day_totals WHERE task_id = old.task_id AND day = date(old.started_at);
+  INSERT INTO task_day_totals (task_id, day, billable, seconds, entry_count, last_ended_at)
+  SELECT task_id, date(started_at), billable, SUM(duration_seconds), COUNT(*), MAX(ended_at)
+  FROM time_entries
+  WHERE task_id = old.task_id AND date(started_at) = date(old.started_at) AND ended_at IS NOT NULL
+  GROUP BY billable;
+
+  DELETE FROM task_day_totals WHERE task_id = new.task_id AND day = date(new.started_at);
+  INSERT INTO task_day_totals (task_id, day, billable, seconds, entry_count, last_ended_at)
+  SELECT task_id, date(started_at), billable, SUM(duration_seconds), COUNT(*), MAX(ended_at)
+  FROM time_entries
+  WHERE task_id = new.task_id AND date(started_at) = date(new.started_at) AND ended_at IS NOT NULL
+  GROUP BY billable;
+END;
+
+-- 回填已有记录
+INSERT INTO task_day_totals (task_id, day, billable, seconds, entry_count, last_ended_at)
+SELECT task_id, date(started_at), billable, SUM(duration_seconds), COUNT(*), MAX(ended_at)
+FROM time_entries
+WHERE ended_at IS NOT NULL
+GROUP BY task_id, date(started_at), billable;
@@ -0,0 +1,6 @@
+-- =========================
+-- 计时记录的会话类型：normal（普通）、focus（专注）、pomodoro（番茄钟）
+-- 专注时长统计：标记为专注/番茄钟的会话，或不间断时长达到阈值的会话
+-- =========================
+ALTER TABLE time_entries ADD COLUMN session_kind TEXT NOT NULL DEFAULT 'normal'
+  CHECK (session_kind IN ('normal', 'focus', 'pomodoro'));
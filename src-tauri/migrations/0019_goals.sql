@@ -0,0 +1,26 @@
+-- =========================
+-- 时间目标与预算：按日/周/月为分类、标签或任务设定目标时长（target）或上限（limit）
+-- =========================
+CREATE TABLE IF NOT EXISTS goals (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,               -- 目标ID，自增主键
+  kind TEXT NOT NULL,                                 -- 类型：target=至少达到，limit=不超过
+  period TEXT NOT NULL,                               -- 周期：day、week（周一开始）、month
+  target_seconds INTEGER NOT NULL,                    -- 目标/上限时长（秒）
+  category_id INTEGER,                                -- 作用范围：分类、标签、任务三选一
+  tag_id INTEGER,
+  task_id INTEGER,
+  is_active INTEGER NOT NULL DEFAULT 1,               -- 是否启用：0=停用，1=启用
+  notified_period TEXT,                               -- 已提醒的周期起始日期，同一周期只提醒一次
+  created_at TEXT NOT NULL DEFAULT (datetime('now')), -- 创建时间（UTC）
+  updated_at TEXT NOT NULL DEFAULT (datetime('now')), -- 更新时间（UTC）
+
+  CHECK (kind IN ('target', 'limit')),
+  CHECK (period IN ('day', 'week', 'month')),
+  CHECK (target_seconds > 0),
+  CHECK ((category_id IS NOT NULL) + (tag_id IS NOT NULL) + (task_id IS NOT NULL) = 1),
+  FOREIGN KEY (category_id) REFERENCES categories(id) ON DELETE CASCADE,
+  FOREIGN KEY (tag_id) REFERENCES tags(id) ON DELETE CASCADE,
+  FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_goals_active ON goals(is_active);
@@ -0,0 +1,6 @@
+-- =========================
+-- 每日重点关联任务：可选关联一个任务，从重点面板开始计时、任务完成时自动勾掉
+-- =========================
+ALTER TABLE daily_focus ADD COLUMN task_id INTEGER REFERENCES tasks(id) ON DELETE SET NULL; -- 关联任务ID（可选）
+
+CREATE INDEX IF NOT EXISTS idx_daily_focus_task ON daily_focus(task_id);
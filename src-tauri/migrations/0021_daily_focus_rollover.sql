@@ -0,0 +1,5 @@
+-- =========================
+-- 每日重点顺延：未完成的重点可复制或移动到新的一天，记录最初的日期和顺延次数
+-- =========================
+ALTER TABLE daily_focus ADD COLUMN carried_from TEXT;                     -- 最初计划的日期 YYYY-MM-DD，未顺延时为空
+ALTER TABLE daily_focus ADD COLUMN carry_count INTEGER NOT NULL DEFAULT 0; -- 已顺延的次数
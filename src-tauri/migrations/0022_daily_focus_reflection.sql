@@ -0,0 +1,4 @@
+-- =========================
+-- 每日重点回顾：每条重点可记录一段简短的当天复盘
+-- =========================
+ALTER TABLE daily_focus ADD COLUMN reflection TEXT; -- 当天结束时的复盘笔记（可选）
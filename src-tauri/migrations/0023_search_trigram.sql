@@ -0,0 +1,59 @@
+-- =========================
+-- 全文搜索改用 trigram 分词：unicode61 不切分中文，“测试”无法命中“测试任务”。
+-- trigram 按子串匹配，不少于 3 个字符的词走索引，更短的词由查询端用 LIKE 匹配
+-- =========================
+DROP TRIGGER IF EXISTS tasks_fts_ai;
+DROP TRIGGER IF EXISTS tasks_fts_ad;
+DROP TRIGGER IF EXISTS tasks_fts_au;
+DROP TRIGGER IF EXISTS time_entries_fts_ai;
+DROP TRIGGER IF EXISTS time_entries_fts_ad;
+DROP TRIGGER IF EXISTS time_entries_fts_au;
+DROP TABLE IF EXISTS tasks_fts;
+DROP TABLE IF EXISTS time_entries_fts;
+
+CREATE VIRTUAL TABLE tasks_fts USING fts5(
+  title,
+  notes,
+  content = 'tasks',
+  content_rowid = 'id',
+  tokenize = 'trigram'
+);
+
+CREATE VIRTUAL TABLE time_entries_fts USING fts5(
+  note,
+  content = 'time_entries',
+  content_rowid = 'id',
+  tokenize = 'trigram'
+);
+
+-- 任务：新增、删除、修改标题/备注时同步索引
+CREATE TRIGGER tasks_fts_ai AFTER INSERT ON tasks BEGIN
+  INSERT INTO tasks_fts (rowid, title, notes) VALUES (new.id, new.title, new.notes);
+END;
+
+CREATE TRIGGER tasks_fts_ad AFTER DELETE ON tasks BEGIN
+  INSERT INTO tasks_fts (tasks_fts, rowid, title, notes) VALUES ('delete', old.id, old.title, old.notes);
+END;
+
+CREATE TRIGGER tasks_fts_au AFTER UPDATE OF title, notes ON tasks BEGIN
+  INSERT INTO tasks_fts (tasks_fts, rowid, title, notes) VALUES ('delete', old.id, old.title, old.notes);
+  INSERT INTO tasks_fts (rowid, title, notes) VALUES (new.id, new.title, new.notes);
+END;
+
+-- 计时记录：新增、删除、修改备注时同步索引
+CREATE TRIGGER time_entries_fts_ai AFTER INSERT ON time_entries BEGIN
+  INSERT INTO time_entries_fts (rowid, note) VALUES (new.id, new.note);
+END;
+
+CREATE TRIGGER time_entries_fts_ad AFTER DELETE ON time_entries BEGIN
+  INSERT INTO time_entries_fts (time_entries_fts, rowid, note) VALUES ('delete', old.id, old.note);
+END;
+
+CREATE TRIGGER time_entries_fts_au AFTER UPDATE OF note ON time_entries BEGIN
+  INSERT INTO time_entries_fts (time_entries_fts, rowid, note) VALUES ('delete', old.id, old.note);
+  INSERT INTO time_entries_fts (rowid, note) VALUES (new.id, new.note);
+END;
+
+-- 为已有数据重建索引
+INSERT INTO tasks_fts (tasks_fts) VALUES ('rebuild');
+INSERT INTO time_entries_fts (time_entries_fts) VALUES ('rebuild');
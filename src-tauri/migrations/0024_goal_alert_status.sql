@@ -0,0 +1,4 @@
+-- =========================
+-- 目标提醒状态：记录本周期已提醒的状态，上限从即将超出升级为已超出时可再次提醒
+-- =========================
+ALTER TABLE goals ADD COLUMN notified_status TEXT; -- 已提醒的状态：met、approaching、exceeded
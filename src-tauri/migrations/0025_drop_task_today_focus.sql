@@ -0,0 +1,5 @@
+-- =========================
+-- 移除 tasks.is_today_focus：改为读取时按今天（本地日期）是否有关联的每日重点推导
+-- =========================
+DROP INDEX IF EXISTS idx_tasks_today_focus;
+ALTER TABLE tasks DROP COLUMN is_today_focus;
@@ -0,0 +1,4 @@
+-- =========================
+-- 发票作废：发票不再删除，作废后保留编号，其包含的计时记录可重新开票
+-- =========================
+ALTER TABLE invoices ADD COLUMN voided_at TEXT; -- 作废时间（UTC），未作废为 NULL
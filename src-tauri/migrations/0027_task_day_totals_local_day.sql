@@ -0,0 +1,57 @@
+-- =========================
+-- task_day_totals 改为按本地日期汇总：day 为 date(started_at, 'localtime')，
+-- 与任务列表的“今日时长”、统计、目标、连续记录的日期口径一致
+-- 统计需要逐条裁剪到范围并在本地零点拆分跨天记录，仍直接读取 time_entries，不读取本表
+-- 系统时区变化后可执行重建命令按新时区重新汇总
+-- =========================
+DROP TRIGGER IF EXISTS task_day_totals_ai;
+DROP TRIGGER IF EXISTS task_day_totals_ad;
+DROP TRIGGER IF EXISTS task_day_totals_au;
+
+-- 新增：只有直接写入已结束的记录才需要汇总（开始计时的记录尚未结束）
+CREATE TRIGGER task_day_totals_ai AFTER INSERT ON time_entries
+WHEN new.ended_at IS NOT NULL BEGIN
+  DELETE FROM task_day_totals WHERE task_id = new.task_id AND day = date(new.started_at, 'localtime');
+  INSERT INTO task_day_totals (task_id, day, billable, seconds, entry_count, last_ended_at)
+  SELECT task_id, date(started_at, 'localtime'), billable, SUM(duration_seconds), COUNT(*), MAX(ended_at)
+  FROM time_entries
+  WHERE task_id = new.task_id AND date(started_at, 'localtime') = date(new.started_at, 'localtime') AND ended_at IS NOT NULL
+  GROUP BY billable;
+END;
+
+-- 删除：重算原分组
+CREATE TRIGGER task_day_totals_ad AFTER DELETE ON time_entries
+WHEN old.ended_at IS NOT NULL BEGIN
+  DELETE FROM task_day_totals WHERE task_id = old.task_id AND day = date(old.started_at, 'localtime');
+  INSERT INTO task_day_totals (task_id, day, billable, seconds, entry_count, last_ended_at)
+  SELECT task_id, date(started_at, 'localtime'), billable, SUM(duration_seconds), COUNT(*), MAX(ended_at)
+  FROM time_entries
+  WHERE task_id = old.task_id AND date(started_at, 'localtime') = date(old.started_at, 'localtime') AND ended_at IS NOT NULL
+  GROUP BY billable;
+END;
+
+-- 修改（结束计时、编辑时长或时间、调整可计费）：先重算原分组，再重算新分组
+CREATE TRIGGER task_day_totals_au
+AFTER UPDATE OF task_id, started_at, ended_at, duration_seconds, billable ON time_entries BEGIN
+  DELETE FROM task_day_totals WHERE task_id = old.task_id AND day = date(old.started_at, 'localtime');
+  INSERT INTO task_day_totals (task_id, day, billable, seconds, entry_count, last_ended_at)
+  SELECT task_id, date(started_at, 'localtime'), billable, SUM(duration_seconds), COUNT(*), MAX(ended_at)
+  FROM time_entries
+  WHERE task_id = old.task_id AND date(started_at, 'localtime') = date(old.started_at, 'localtime') AND ended_at IS NOT NULL
+  GROUP BY billable;
+
+  DELETE FROM task_day_totals WHERE task_id = new.task_id AND day = date(new.started_at, 'localtime');
+  INSERT INTO task_day_totals (task_id, day, billable, seconds, entry_count, last_ended_at)
+  SELECT task_id, date(started_at, 'localtime'), billable, SUM(duration_seconds), COUNT(*), MAX(ended_at)
+  FROM time_entries
+  WHERE task_id = new.task_id AND date(started_at, 'localtime') = date(new.started_at, 'localtime') AND ended_at IS NOT NULL
+  GROUP BY billable;
+END;
+
+-- 按本地日期重新回填
+DELETE FROM task_day_totals;
+INSERT INTO task_day_totals (task_id, day, billable, seconds, entry_count, last_ended_at)
+SELECT task_id, date(started_at, 'localtime'), billable, SUM(duration_seconds), COUNT(*), MAX(ended_at)
+FROM time_entries
+WHERE ended_at IS NOT NULL
+GROUP BY task_id, date(started_at, 'localtime'), billable;
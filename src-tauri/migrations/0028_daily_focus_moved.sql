@@ -0,0 +1,5 @@
+-- =========================
+-- 移动顺延保留原条目：原日期的条目不再改期，只记录移动到的日期，
+-- 原日期的完成情况（连续记录、历史、周回顾）按原样统计
+-- =========================
+ALTER TABLE daily_focus ADD COLUMN moved_to TEXT; -- 已移动到的日期 YYYY-MM-DD，未移动时为空
@@ -0,0 +1,11 @@
+-- =========================
+-- 未关联客户的项目名称唯一：UNIQUE (client_id, name) 中 NULL 互不相等，需单独约束
+-- =========================
+-- 已存在的重名项目（保留最早的一个）在名称后追加项目ID，避免创建索引失败
+UPDATE projects
+SET name = name || ' (' || id || ')'
+WHERE client_id IS NULL
+  AND id NOT IN (SELECT MIN(id) FROM projects WHERE client_id IS NULL GROUP BY name);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_projects_name_without_client
+ON projects(name) WHERE client_id IS NULL;
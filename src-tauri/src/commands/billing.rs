@@ -0,0 +1,99 @@
+use sqlx::SqlitePool;
+
+use crate::{
+    commands::settings::{get_setting, set_setting},
+    error::ZapError,
+};
+
+/// 币种在 settings 表中的键
+const CURRENCY_KEY: &str = "currency";
+
+/// 未配置时的默认币种
+pub const DEFAULT_CURRENCY: &str = "USD";
+
+/// 生效的可计费标记：计时记录单独设置优先，否则沿用任务设置
+pub(crate) const BILLABLE_SQL: &str = "COALESCE(te.billable, t.billable)";
+
+/// 生效的小时费率：任务 > 项目 > 分类，均未设置时为 0
+pub(crate) const HOURLY_RATE_SQL: &str = "COALESCE(t.hourly_rate, p.hourly_rate, c.hourly_rate, 0)";
+
+/// 按时长（秒）与小时费率（最小货币单位）计算金额，四舍五入到最小货币单位
+pub fn earned_amount(seconds: i64, hourly_rate: i64) -> i64 {
+    let cents = seconds as i128 * hourly_rate as i128;
+    ((cents + 1800).div_euclid(3600)) as i64
+}
+
+/// 将最小货币单位的金额格式化为带币种的文本，例如 `1234.50 USD`
+pub fn format_amount(amount: i64, currency: &str) -> String {
+    // 无辅币单位的币种
+    const ZERO_DECIMAL: [&str; 4] = ["JPY", "KRW", "VND", "CLP"];
+    if ZERO_DECIMAL.contains(&currency) {
+        return format!("{} {}", amount, currency);
+    }
+
+    let sign = if amount < 0 { "-" } else { "" };
+    let abs = amount.unsigned_abs();
+    format!("{}{}.{:02} {}", sign, abs / 100, abs % 100, currency)
+}
+
+/// 获取币种（ISO 4217 代码），未配置时为 USD
+pub async fn get_currency_impl(pool: &SqlitePool) -> Result<String, ZapError> {
+    Ok(get_setting(pool, CURRENCY_KEY)
+        .await?
+        .unwrap_or_else(|| DEFAULT_CURRENCY.to_string()))
+}
+
+/// 设置币种（ISO 4217 代码，如 USD、CNY）
+pub async fn set_currency_impl(pool: &SqlitePool, currency: String) -> Result<String, ZapError> {
+    let currency = currency.trim().to_uppercase();
+    if currency.len() != 3 || !currency.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Err(ZapError::InvalidSetting(
+            "currency must be a 3-letter ISO 4217 code",
+        ));
+    }
+
+    set_setting(pool, CURRENCY_KEY, &currency).await?;
+    Ok(currency)
+}
+
+/// 设置分类级小时费率（最小货币单位），传 None 清除
+pub async fn set_category_rate_impl(
+    pool: &SqlitePool,
+    category_id: u32,
+    hourly_rate: Option<i64>,
+) -> Result<(), ZapError> {
+    if hourly_rate.is_some_and(|r| r < 0) {
+        return Err(ZapError::InvalidCategoryData(
+            "hourly rate cannot be negative",
+        ));
+    }
+
+    let res = sqlx::query("UPDATE categories SET hourly_rate = ? WHERE id = ?")
+        .bind(hourly_rate)
+        .bind(category_id)
+        .execute(pool)
+        .await?;
+
+    if res.rows_affected() == 0 {
+        return Err(ZapError::CategoryNotFound(category_id));
+    }
+    Ok(())
+}
+
+/// 设置单条计时记录是否可计费，传 None 恢复为沿用任务设置
+pub async fn set_time_entry_billable_impl(
+    pool: &SqlitePool,
+    entry_id: u32,
+    billable: Option<bool>,
+) -> Result<(), ZapError> {
+    let res = sqlx::query("UPDATE time_entries SET billable = ? WHERE id = ?")
+        .bind(billable)
+        .bind(entry_id)
+        .execute(pool)
+        .await?;
+
+    if res.rows_affected() == 0 {
+        return Err(ZapError::TimeEntryNotFound(entry_id));
+    }
+    Ok(())
+}
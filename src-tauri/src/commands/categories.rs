@@ -11,13 +11,16 @@ pub struct CategoryResponse {
     name: String,
     /// 分类颜色
     color: String,
+    /// 分类级小时费率（最小货币单位）
+    hourly_rate: Option<i64>,
 }
 
 pub async fn list_categories_impl(pool: &SqlitePool) -> Result<Vec<CategoryResponse>, ZapError> {
-    let categories_table =
-        sqlx::query_as::<_, CategoryResponse>("SELECT id, name, color FROM categories")
-            .fetch_all(pool)
-            .await?;
+    let categories_table = sqlx::query_as::<_, CategoryResponse>(
+        "SELECT id, name, color, hourly_rate FROM categories",
+    )
+    .fetch_all(pool)
+    .await?;
 
     Ok(categories_table)
 }
@@ -0,0 +1,105 @@
+use serde::{Deserialize, Serialize};
+use sqlx::{SqlitePool, prelude::FromRow};
+
+use crate::error::ZapError;
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct ClientResponse {
+    /// 主键
+    pub id: u32,
+    /// 客户名称
+    pub name: String,
+    /// 创建时间
+    pub created_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SaveClientRequest {
+    /// 客户名称
+    pub name: String,
+}
+
+pub async fn list_clients_impl(pool: &SqlitePool) -> Result<Vec<ClientResponse>, ZapError> {
+    let clients = sqlx::query_as::<_, ClientResponse>(
+        "SELECT id, name, created_at FROM clients ORDER BY name",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(clients)
+}
+
+pub async fn create_client_impl(
+    pool: &SqlitePool,
+    req: SaveClientRequest,
+) -> Result<ClientResponse, ZapError> {
+    let name = validate_name(&req.name)?;
+
+    let res = sqlx::query("INSERT INTO clients (name) VALUES (?)")
+        .bind(name)
+        .execute(pool)
+        .await?;
+
+    get_client_by_id(pool, res.last_insert_rowid() as u32).await
+}
+
+pub async fn update_client_impl(
+    pool: &SqlitePool,
+    client_id: u32,
+    req: SaveClientRequest,
+) -> Result<ClientResponse, ZapError> {
+    let name = validate_name(&req.name)?;
+
+    let res = sqlx::query("UPDATE clients SET name = ? WHERE id = ?")
+        .bind(name)
+        .bind(client_id)
+        .execute(pool)
+        .await?;
+
+    if res.rows_affected() == 0 {
+        return Err(ZapError::ClientNotFound(client_id));
+    }
+
+    get_client_by_id(pool, client_id).await
+}
+
+pub async fn delete_client_impl(pool: &SqlitePool, client_id: u32) -> Result<(), ZapError> {
+    let mut tx = pool.begin().await?;
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM projects WHERE client_id = ?")
+        .bind(client_id)
+        .fetch_one(tx.as_mut())
+        .await?;
+
+    if count > 0 {
+        return Err(ZapError::ClientHasProjects);
+    }
+
+    let res = sqlx::query("DELETE FROM clients WHERE id = ?")
+        .bind(client_id)
+        .execute(tx.as_mut())
+        .await?;
+
+    if res.rows_affected() == 0 {
+        return Err(ZapError::ClientNotFound(client_id));
+    }
+
+    tx.commit().await?;
+    Ok(())
+}
+
+async fn get_client_by_id(pool: &SqlitePool, client_id: u32) -> Result<ClientResponse, ZapError> {
+    sqlx::query_as::<_, ClientResponse>("SELECT id, name, created_at FROM clients WHERE id = ?")
+        .bind(client_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or(ZapError::ClientNotFound(client_id))
+}
+
+fn validate_name(name: &str) -> Result<&str, ZapError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(ZapError::InvalidClientData("name cannot be empty"));
+    }
+    Ok(name)
+}
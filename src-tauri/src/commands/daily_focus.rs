@@ -1,15 +1,83 @@
+use std::collections::BTreeMap;
+
+use chrono::{Datelike, Duration, NaiveDate};
 use serde::{Deserialize, Serialize};
-use sqlx::{FromRow, SqlitePool};
+use sqlx::{FromRow, Sqlite, SqlitePool, Transaction};
+
+use crate::{
+    commands::{
+        settings::{get_setting, set_setting},
+        task::start_task_impl,
+        types::deserialize_nullable,
+    },
+    error::ZapError,
+};
+
+/// 每日重点数量规则在 settings 表中的键
+const FOCUS_LIST_POLICY_KEY: &str = "daily_focus_policy";
+
+/// 未完成重点顺延方式在 settings 表中的键
+const ROLLOVER_POLICY_KEY: &str = "daily_focus_rollover";
+
+/// 最近一次执行顺延的本地日期在 settings 表中的键
+const LAST_ROLLOVER_KEY: &str = "daily_focus_last_rollover";
+
+/// 复盘笔记的最大长度（字符）
+const MAX_REFLECTION_CHARS: usize = 500;
+
+/// 周回顾中列出的最常顺延条目数
+const MOST_POSTPONED_LIMIT: usize = 5;
+
+/// 默认查询最近 30 天的历史
+const DEFAULT_HISTORY_DAYS: i64 = 30;
+
+/// 新的一天对前一天未完成重点的处理方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum RolloverPolicy {
+    /// 不顺延
+    #[default]
+    Off,
+    /// 复制到今天，原日期的条目保留
+    Copy,
+    /// 移动到今天：原日期的条目保留并标记为已移动，仍按未完成统计
+    Move,
+}
 
-use crate::error::ZapError;
+/// 每日重点数量规则：每天一条主重点（排序最靠前的一条），外加若干条次要事项
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DailyFocusPolicy {
+    /// 次要事项的最大数量，0 到 20；为空表示不限制
+    pub max_secondary_items: Option<u32>,
+}
 
-#[derive(Debug, Serialize, Deserialize, FromRow)]
+impl Default for DailyFocusPolicy {
+    fn default() -> Self {
+        Self {
+            max_secondary_items: Some(3),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct DailyFocus {
     pub id: i64,
     pub focus_date: String,
     pub content: String,
     pub is_done: i32,
     pub position: i32,
+    /// 关联的任务（可选）
+    pub task_id: Option<u32>,
+    /// 关联任务在重点当天（本地日期）的计时时长，含进行中的计时
+    pub tracked_seconds: i64,
+    /// 顺延而来时为最初计划的日期
+    pub carried_from: Option<String>,
+    /// 已顺延的次数
+    pub carry_count: i64,
+    /// 按移动方式顺延后为移动到的日期，原条目保留在原日期
+    pub moved_to: Option<String>,
+    /// 当天结束时的复盘笔记
+    pub reflection: Option<String>,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -20,14 +88,87 @@ pub struct UpsertDailyFocusRequest {
     pub content: String,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct AddDailyFocusRequest {
+    /// 本地日期 YYYY-MM-DD
+    pub date: String,
+    /// 内容；关联任务时可为空，默认为任务标题
+    #[serde(default)]
+    pub content: String,
+    pub task_id: Option<u32>,
+}
+
+/// 更新每日重点：字段缺省表示不修改，task_id 传 null 表示解除关联
+#[derive(Debug, Default, Deserialize)]
+pub struct UpdateDailyFocusRequest {
+    pub content: Option<String>,
+    pub is_done: Option<bool>,
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    pub task_id: Option<Option<u32>>,
+    /// 复盘笔记，最多 500 字符；传 null 或空字符串表示清空
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    pub reflection: Option<Option<String>>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct DailyFocusHistoryQuery {
+    /// 本地日期 YYYY-MM-DD，默认为结束日期前 29 天
+    pub start_date: Option<String>,
+    /// 本地日期 YYYY-MM-DD，默认今天
+    pub end_date: Option<String>,
+}
+
+/// 某一天的重点
+#[derive(Debug, Serialize)]
+pub struct DailyFocusHistoryDay {
+    pub date: String,
+    /// 按排序排列，第一条为主重点
+    pub items: Vec<DailyFocus>,
+    pub done_count: i64,
+    /// 当天重点是否全部完成
+    pub completed: bool,
+}
+
+/// 一周（周一至周日）的重点回顾
+#[derive(Debug, Serialize)]
+pub struct WeeklyFocusReview {
+    pub start_date: String,
+    pub end_date: String,
+    pub total_items: i64,
+    pub done_items: i64,
+    /// 完成率（百分比，保留一位小数），没有重点时为 0
+    pub completion_rate: f64,
+    /// 设置了重点的天数
+    pub planned_days: i64,
+    /// 重点全部完成的天数
+    pub completed_days: i64,
+    /// 关联任务在各自重点当天的计时之和
+    pub tracked_seconds: i64,
+    /// 顺延次数最多的条目（同一条重点多次复制时只取顺延次数最多的一次）
+    pub most_postponed: Vec<DailyFocus>,
+}
+
+const FOCUS_COLUMNS: &str = r#"
+    SELECT f.id, f.focus_date, f.content, f.is_done, f.position, f.task_id,
+           COALESCE((
+               SELECT SUM(CASE WHEN te.ended_at IS NULL
+                   THEN MAX(CAST(strftime('%s', 'now') - strftime('%s', te.started_at) AS INTEGER), 0)
+                   ELSE te.duration_seconds END)
+               FROM time_entries te
+               WHERE te.task_id = f.task_id AND date(te.started_at, 'localtime') = f.focus_date
+           ), 0) AS tracked_seconds,
+           f.carried_from, f.carry_count, f.moved_to, f.reflection, f.created_at, f.updated_at
+    FROM daily_focus f
+"#;
+
+/// 获取某天的主重点（排序最靠前的一条）
 pub async fn get_daily_focus_impl(
     pool: &SqlitePool,
     date: String,
 ) -> Result<Option<DailyFocus>, ZapError> {
-    let focus = sqlx::query_as::<_, DailyFocus>(
-        "SELECT id, focus_date, content, is_done, position, created_at, updated_at
-         FROM daily_focus WHERE focus_date = ? LIMIT 1",
-    )
+    let focus = sqlx::query_as::<_, DailyFocus>(&format!(
+        "{FOCUS_COLUMNS} WHERE f.focus_date = ? ORDER BY position, id LIMIT 1"
+    ))
     .bind(date)
     .fetch_optional(pool)
     .await?;
@@ -35,6 +176,208 @@ pub async fn get_daily_focus_impl(
     Ok(focus)
 }
 
+/// 按排序列出某天的全部重点，第一条为主重点
+pub async fn list_daily_focus_impl(
+    pool: &SqlitePool,
+    date: String,
+) -> Result<Vec<DailyFocus>, ZapError> {
+    let items = sqlx::query_as::<_, DailyFocus>(&format!(
+        "{FOCUS_COLUMNS} WHERE f.focus_date = ? ORDER BY position, id"
+    ))
+    .bind(date)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(items)
+}
+
+/// 在某天的列表末尾追加一条重点，受数量规则限制
+pub async fn add_daily_focus_impl(
+    pool: &SqlitePool,
+    req: AddDailyFocusRequest,
+) -> Result<DailyFocus, ZapError> {
+    if NaiveDate::parse_from_str(&req.date, "%Y-%m-%d").is_err() {
+        return Err(ZapError::InvalidDailyFocusData(
+            "date must be in YYYY-MM-DD format",
+        ));
+    }
+
+    let policy = get_daily_focus_policy_impl(pool).await?;
+    let mut tx = pool.begin().await?;
+
+    let content = match req.task_id {
+        Some(task_id) if req.content.trim().is_empty() => task_title(&mut tx, task_id).await?,
+        Some(task_id) => {
+            task_title(&mut tx, task_id).await?;
+            req.content.trim().to_string()
+        }
+        None if req.content.trim().is_empty() => {
+            return Err(ZapError::InvalidDailyFocusData("content cannot be empty"));
+        }
+        None => req.content.trim().to_string(),
+    };
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM daily_focus WHERE focus_date = ?")
+        .bind(&req.date)
+        .fetch_one(tx.as_mut())
+        .await?;
+    if let Some(max) = policy.max_secondary_items
+        && count > max as i64
+    {
+        return Err(ZapError::InvalidDailyFocusData("daily focus limit reached"));
+    }
+
+    let res = sqlx::query(
+        "INSERT INTO daily_focus (focus_date, content, task_id, position)
+         VALUES (?, ?, ?, (SELECT COALESCE(MAX(position), -1) + 1 FROM daily_focus WHERE focus_date = ?))",
+    )
+    .bind(&req.date)
+    .bind(content)
+    .bind(req.task_id)
+    .bind(&req.date)
+    .execute(tx.as_mut())
+    .await?;
+
+    tx.commit().await?;
+    get_daily_focus_by_id(pool, res.last_insert_rowid()).await
+}
+
+pub async fn update_daily_focus_impl(
+    pool: &SqlitePool,
+    id: i64,
+    req: UpdateDailyFocusRequest,
+) -> Result<DailyFocus, ZapError> {
+    let focus = get_daily_focus_by_id(pool, id).await?;
+
+    let content = match req.content {
+        Some(ref content) if content.trim().is_empty() => {
+            return Err(ZapError::InvalidDailyFocusData("content cannot be empty"));
+        }
+        Some(ref content) => content.trim().to_string(),
+        None => focus.content,
+    };
+    let is_done = req.is_done.map_or(focus.is_done, i32::from);
+    let task_id = req.task_id.unwrap_or(focus.task_id);
+    let reflection = match req.reflection {
+        Some(reflection) => {
+            let reflection = reflection
+                .map(|r| r.trim().to_string())
+                .filter(|r| !r.is_empty());
+            if reflection
+                .as_ref()
+                .is_some_and(|r| r.chars().count() > MAX_REFLECTION_CHARS)
+            {
+                return Err(ZapError::InvalidDailyFocusData(
+                    "reflection must be at most 500 characters",
+                ));
+            }
+            reflection
+        }
+        None => focus.reflection,
+    };
+
+    let mut tx = pool.begin().await?;
+    if let Some(task_id) = task_id {
+        task_title(&mut tx, task_id).await?;
+    }
+
+    sqlx::query(
+        "UPDATE daily_focus
+         SET content = ?, is_done = ?, task_id = ?, reflection = ?, updated_at = datetime('now')
+         WHERE id = ?",
+    )
+    .bind(content)
+    .bind(is_done)
+    .bind(task_id)
+    .bind(reflection)
+    .bind(id)
+    .execute(tx.as_mut())
+    .await?;
+
+    tx.commit().await?;
+    get_daily_focus_by_id(pool, id).await
+}
+
+pub async fn delete_daily_focus_impl(pool: &SqlitePool, id: i64) -> Result<(), ZapError> {
+    let res = sqlx::query("DELETE FROM daily_focus WHERE id = ?")
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+    if res.rows_affected() == 0 {
+        return Err(ZapError::DailyFocusNotFound(id));
+    }
+    Ok(())
+}
+
+/// 从重点面板开始计时：启动关联的任务
+pub async fn start_daily_focus_impl(pool: &SqlitePool, id: i64) -> Result<DailyFocus, ZapError> {
+    let focus = get_daily_focus_by_id(pool, id).await?;
+    let task_id = focus.task_id.ok_or(ZapError::InvalidDailyFocusData(
+        "focus item is not linked to a task",
+    ))?;
+
+    start_task_impl(pool, task_id).await?;
+    get_daily_focus_by_id(pool, id).await
+}
+
+/// 按给定顺序重排某天的全部重点，ids 必须恰好包含当天的所有重点；排第一的成为主重点
+pub async fn reorder_daily_focus_impl(
+    pool: &SqlitePool,
+    date: String,
+    ids: Vec<i64>,
+) -> Result<Vec<DailyFocus>, ZapError> {
+    let mut tx = pool.begin().await?;
+
+    let mut existing: Vec<i64> =
+        sqlx::query_scalar("SELECT id FROM daily_focus WHERE focus_date = ?")
+            .bind(&date)
+            .fetch_all(tx.as_mut())
+            .await?;
+    let mut requested = ids.clone();
+    existing.sort_unstable();
+    requested.sort_unstable();
+    if existing != requested {
+        return Err(ZapError::InvalidDailyFocusData(
+            "ids must list every focus item of the day exactly once",
+        ));
+    }
+
+    for (position, id) in ids.iter().enumerate() {
+        sqlx::query(
+            "UPDATE daily_focus SET position = ?, updated_at = datetime('now') WHERE id = ?",
+        )
+        .bind(position as i64)
+        .bind(id)
+        .execute(tx.as_mut())
+        .await?;
+    }
+
+    tx.commit().await?;
+    list_daily_focus_impl(pool, date).await
+}
+
+/// 获取每日重点数量规则，未配置时为一条主重点加三条次要事项
+pub async fn get_daily_focus_policy_impl(pool: &SqlitePool) -> Result<DailyFocusPolicy, ZapError> {
+    Ok(get_setting(pool, FOCUS_LIST_POLICY_KEY)
+        .await?
+        .unwrap_or_default())
+}
+
+/// 保存每日重点数量规则，已有的超量条目保留
+pub async fn set_daily_focus_policy_impl(
+    pool: &SqlitePool,
+    policy: DailyFocusPolicy,
+) -> Result<DailyFocusPolicy, ZapError> {
+    if policy.max_secondary_items.is_some_and(|n| n > 20) {
+        return Err(ZapError::InvalidSetting(
+            "secondary focus items must be between 0 and 20",
+        ));
+    }
+    set_setting(pool, FOCUS_LIST_POLICY_KEY, &policy).await?;
+    Ok(policy)
+}
+
 pub async fn upsert_daily_focus_impl(
     pool: &SqlitePool,
     req: UpsertDailyFocusRequest,
@@ -59,25 +402,253 @@ pub async fn upsert_daily_focus_impl(
 
     get_daily_focus_impl(pool, req.date)
         .await?
-        .ok_or(ZapError::InvalidTaskData(
+        .ok_or(ZapError::InvalidDailyFocusData(
             "Failed to retrieve upserted focus",
         ))
 }
 
 pub async fn toggle_daily_focus_impl(pool: &SqlitePool, id: i64) -> Result<DailyFocus, ZapError> {
-    sqlx::query(
+    let res = sqlx::query(
         "UPDATE daily_focus SET is_done = 1 - is_done, updated_at = datetime('now') WHERE id = ?",
     )
     .bind(id)
     .execute(pool)
     .await?;
 
-    sqlx::query_as::<_, DailyFocus>(
-        "SELECT id, focus_date, content, is_done, position, created_at, updated_at
-         FROM daily_focus WHERE id = ?",
+    if res.rows_affected() == 0 {
+        return Err(ZapError::DailyFocusNotFound(id));
+    }
+    get_daily_focus_by_id(pool, id).await
+}
+
+/// 按日期列出范围内的重点（含完成状态、关联任务计时和复盘笔记），最近的日期在前，
+/// 没有重点的日期不列出
+pub async fn get_daily_focus_history_impl(
+    pool: &SqlitePool,
+    query: DailyFocusHistoryQuery,
+) -> Result<Vec<DailyFocusHistoryDay>, ZapError> {
+    let end = match query.end_date.as_deref() {
+        Some(date) => parse_focus_date(date)?,
+        None => chrono::Local::now().date_naive(),
+    };
+    let start = match query.start_date.as_deref() {
+        Some(date) => parse_focus_date(date)?,
+        None => end - Duration::days(DEFAULT_HISTORY_DAYS - 1),
+    };
+    if end < start {
+        return Err(ZapError::InvalidDailyFocusData(
+            "end_date must not be before start_date",
+        ));
+    }
+
+    let items = list_focus_between(pool, start, end).await?;
+
+    let mut days: BTreeMap<String, Vec<DailyFocus>> = BTreeMap::new();
+    for item in items {
+        days.entry(item.focus_date.clone()).or_default().push(item);
+    }
+
+    Ok(days
+        .into_iter()
+        .rev()
+        .map(|(date, items)| {
+            let done_count = items.iter().filter(|i| i.is_done != 0).count() as i64;
+            DailyFocusHistoryDay {
+                date,
+                completed: done_count == items.len() as i64,
+                done_count,
+                items,
+            }
+        })
+        .collect())
+}
+
+/// 汇总 date（默认今天）所在周的重点完成情况和最常顺延的条目
+pub async fn get_weekly_focus_review_impl(
+    pool: &SqlitePool,
+    date: Option<String>,
+) -> Result<WeeklyFocusReview, ZapError> {
+    let date = match date.as_deref() {
+        Some(date) => parse_focus_date(date)?,
+        None => chrono::Local::now().date_naive(),
+    };
+    let start = date - Duration::days(date.weekday().num_days_from_monday() as i64);
+    let end = start + Duration::days(6);
+
+    let items = list_focus_between(pool, start, end).await?;
+
+    let total_items = items.len() as i64;
+    let done_items = items.iter().filter(|i| i.is_done != 0).count() as i64;
+    let mut days: BTreeMap<&str, bool> = BTreeMap::new();
+    for item in &items {
+        let completed = days.entry(item.focus_date.as_str()).or_insert(true);
+        *completed &= item.is_done != 0;
+    }
+
+    let mut postponed: Vec<&DailyFocus> = items.iter().filter(|i| i.carry_count > 0).collect();
+    postponed.sort_by(|a, b| {
+        b.carry_count
+            .cmp(&a.carry_count)
+            .then_with(|| b.focus_date.cmp(&a.focus_date))
+    });
+    let mut seen = Vec::new();
+    let mut most_postponed = Vec::new();
+    for item in postponed {
+        let key = (item.carried_from.as_deref(), item.content.as_str());
+        if seen.contains(&key) {
+            continue;
+        }
+        seen.push(key);
+        most_postponed.push(item.clone());
+        if most_postponed.len() == MOST_POSTPONED_LIMIT {
+            break;
+        }
+    }
+
+    let completion_rate = if total_items > 0 {
+        (done_items as f64 / total_items as f64 * 1000.0).round() / 10.0
+    } else {
+        0.0
+    };
+    let planned_days = days.len() as i64;
+    let completed_days = days.values().filter(|c| **c).count() as i64;
+    let tracked_seconds = items.iter().map(|i| i.tracked_seconds).sum();
+
+    Ok(WeeklyFocusReview {
+        start_date: start.to_string(),
+        end_date: end.to_string(),
+        total_items,
+        done_items,
+        completion_rate,
+        planned_days,
+        completed_days,
+        tracked_seconds,
+        most_postponed,
+    })
+}
+
+/// 获取未完成重点的顺延方式，未配置时不顺延
+pub async fn get_rollover_policy_impl(pool: &SqlitePool) -> Result<RolloverPolicy, ZapError> {
+    Ok(get_setting(pool, ROLLOVER_POLICY_KEY)
+        .await?
+        .unwrap_or_default())
+}
+
+/// 设置未完成重点的顺延方式
+pub async fn set_rollover_policy_impl(
+    pool: &SqlitePool,
+    policy: RolloverPolicy,
+) -> Result<RolloverPolicy, ZapError> {
+    set_setting(pool, ROLLOVER_POLICY_KEY, &policy).await?;
+    Ok(policy)
+}
+
+/// 新的一天首次运行时，按配置将最近一个有重点的日期中未完成的条目复制或移动到 today，
+/// 追加在当天列表末尾（不受数量规则限制）；每天只执行一次，返回顺延到今天的条目
+pub async fn rollover_daily_focus_impl(
+    pool: &SqlitePool,
+    today: NaiveDate,
+) -> Result<Vec<DailyFocus>, ZapError> {
+    let policy = get_rollover_policy_impl(pool).await?;
+    if policy == RolloverPolicy::Off {
+        return Ok(Vec::new());
+    }
+
+    // 标记与顺延在同一事务内读写，避免提交后写标记失败导致同一天重复顺延
+    let today = today.to_string();
+    let mut tx = pool.begin().await?;
+    let last: Option<String> = get_setting(tx.as_mut(), LAST_ROLLOVER_KEY).await?;
+    if last.as_deref() >= Some(today.as_str()) {
+        return Ok(Vec::new());
+    }
+
+    let sources: Vec<i64> = sqlx::query_scalar(
+        "SELECT id FROM daily_focus
+         WHERE is_done = 0
+           AND focus_date = (SELECT MAX(focus_date) FROM daily_focus WHERE focus_date < ?)
+         ORDER BY position, id",
     )
-    .bind(id)
-    .fetch_optional(pool)
-    .await?
-    .ok_or(ZapError::TaskNotFound(id as u32))
+    .bind(&today)
+    .fetch_all(tx.as_mut())
+    .await?;
+
+    let mut ids = Vec::with_capacity(sources.len());
+    for id in &sources {
+        let next_position: i64 = sqlx::query_scalar(
+            "SELECT COALESCE(MAX(position), -1) + 1 FROM daily_focus WHERE focus_date = ?",
+        )
+        .bind(&today)
+        .fetch_one(tx.as_mut())
+        .await?;
+
+        let carried_id = sqlx::query(
+            "INSERT INTO daily_focus (focus_date, content, task_id, position, carried_from, carry_count)
+             SELECT ?, content, task_id, ?, COALESCE(carried_from, focus_date), carry_count + 1
+             FROM daily_focus WHERE id = ?",
+        )
+        .bind(&today)
+        .bind(next_position)
+        .bind(id)
+        .execute(tx.as_mut())
+        .await?
+        .last_insert_rowid();
+
+        // 移动时原条目留在原日期，只标记去向，原日期的完成情况不受影响
+        if policy == RolloverPolicy::Move {
+            sqlx::query(
+                "UPDATE daily_focus SET moved_to = ?, updated_at = datetime('now') WHERE id = ?",
+            )
+            .bind(&today)
+            .bind(id)
+            .execute(tx.as_mut())
+            .await?;
+        }
+        ids.push(carried_id);
+    }
+
+    set_setting(tx.as_mut(), LAST_ROLLOVER_KEY, &today).await?;
+    tx.commit().await?;
+
+    let mut items = Vec::with_capacity(ids.len());
+    for id in ids {
+        items.push(get_daily_focus_by_id(pool, id).await?);
+    }
+    Ok(items)
+}
+
+async fn list_focus_between(
+    pool: &SqlitePool,
+    start: NaiveDate,
+    end: NaiveDate,
+) -> Result<Vec<DailyFocus>, ZapError> {
+    let items = sqlx::query_as::<_, DailyFocus>(&format!(
+        "{FOCUS_COLUMNS} WHERE f.focus_date >= ? AND f.focus_date <= ? ORDER BY f.focus_date, f.position, f.id"
+    ))
+    .bind(start.to_string())
+    .bind(end.to_string())
+    .fetch_all(pool)
+    .await?;
+
+    Ok(items)
+}
+
+fn parse_focus_date(date: &str) -> Result<NaiveDate, ZapError> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map_err(|_| ZapError::InvalidDailyFocusData("date must be in YYYY-MM-DD format"))
+}
+
+async fn task_title(tx: &mut Transaction<'_, Sqlite>, task_id: u32) -> Result<String, ZapError> {
+    sqlx::query_scalar("SELECT title FROM tasks WHERE id = ?")
+        .bind(task_id)
+        .fetch_optional(tx.as_mut())
+        .await?
+        .ok_or(ZapError::TaskNotFound(task_id))
+}
+
+async fn get_daily_focus_by_id(pool: &SqlitePool, id: i64) -> Result<DailyFocus, ZapError> {
+    sqlx::query_as::<_, DailyFocus>(&format!("{FOCUS_COLUMNS} WHERE f.id = ?"))
+        .bind(id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or(ZapError::DailyFocusNotFound(id))
 }
@@ -0,0 +1,448 @@
+use std::collections::BTreeMap;
+
+use chrono::{Datelike, Duration, Local, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, Sqlite, SqlitePool, Transaction, Type};
+
+use crate::{
+    commands::{
+        rounding::get_rounding_policy_impl,
+        statistics::{DATETIME_FORMAT, local_midnight_utc, parse_utc, split_by_local_day},
+    },
+    error::ZapError,
+};
+
+/// 上限类目标达到该比例时视为即将超出
+const APPROACHING_RATIO: f64 = 0.9;
+
+/// 目标类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(rename_all = "snake_case")]
+pub enum GoalKind {
+    /// 周期内至少达到目标时长，如“每周学习 10 小时”
+    Target,
+    /// 周期内不超过上限，如“每周会议最多 5 小时”
+    Limit,
+}
+
+/// 目标周期，按本地日期划分
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(rename_all = "snake_case")]
+pub enum GoalPeriod {
+    Day,
+    /// 自然周（周一至周日）
+    Week,
+    /// 自然月
+    Month,
+}
+
+impl GoalPeriod {
+    /// 包含 date 的周期起止日期（含）
+    pub fn range(self, date: NaiveDate) -> (NaiveDate, NaiveDate) {
+        match self {
+            GoalPeriod::Day => (date, date),
+            GoalPeriod::Week => {
+                let start = date - Duration::days(date.weekday().num_days_from_monday() as i64);
+                (start, start + Duration::days(6))
+            }
+            GoalPeriod::Month => {
+                let start = date.with_day(1).unwrap_or(date);
+                let end = start
+                    .checked_add_months(chrono::Months::new(1))
+                    .and_then(|d| d.pred_opt())
+                    .unwrap_or(date);
+                (start, end)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Goal {
+    pub id: u32,
+    pub kind: GoalKind,
+    pub period: GoalPeriod,
+    /// 目标或上限时长（秒）
+    pub target_seconds: i64,
+    /// 作用范围：分类、标签、任务三选一
+    pub category_id: Option<u32>,
+    pub tag_id: Option<u32>,
+    pub task_id: Option<u32>,
+    /// 作用范围的名称（分类名、标签名或任务标题）
+    pub scope_name: String,
+    pub is_active: bool,
+    pub created_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SaveGoalRequest {
+    pub kind: GoalKind,
+    pub period: GoalPeriod,
+    pub target_seconds: i64,
+    /// category_id、tag_id、task_id 必须且只能指定一个
+    pub category_id: Option<u32>,
+    pub tag_id: Option<u32>,
+    pub task_id: Option<u32>,
+    /// 是否启用，缺省为启用
+    pub is_active: Option<bool>,
+}
+
+impl Goal {
+    /// 作用范围对应的过滤条件（任务表别名为 t）及绑定的 id
+    pub(crate) fn scope_condition(&self) -> Result<(&'static str, u32), ZapError> {
+        match (self.category_id, self.tag_id, self.task_id) {
+            (Some(id), _, _) => Ok(("t.category_id = ?", id)),
+            (_, Some(id), _) => Ok((
+                "EXISTS (SELECT 1 FROM task_tags tt WHERE tt.task_id = t.id AND tt.tag_id = ?)",
+                id,
+            )),
+            (_, _, Some(id)) => Ok(("t.id = ?", id)),
+            _ => Err(ZapError::InvalidGoalData("goal has no scope")),
+        }
+    }
+}
+
+/// 目标在当前周期内的状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(rename_all = "snake_case")]
+pub enum GoalStatus {
+    /// 目标类：尚未达到
+    InProgress,
+    /// 目标类：已达到
+    Met,
+    /// 上限类：低于上限的 90%
+    UnderLimit,
+    /// 上限类：达到上限的 90% 但未超出
+    Approaching,
+    /// 上限类：已超出
+    Exceeded,
+}
+
+impl GoalStatus {
+    fn of(kind: GoalKind, tracked_seconds: i64, target_seconds: i64) -> Self {
+        match kind {
+            GoalKind::Target if tracked_seconds >= target_seconds => GoalStatus::Met,
+            GoalKind::Target => GoalStatus::InProgress,
+            GoalKind::Limit if tracked_seconds > target_seconds => GoalStatus::Exceeded,
+            GoalKind::Limit
+                if tracked_seconds as f64 >= target_seconds as f64 * APPROACHING_RATIO =>
+            {
+                GoalStatus::Approaching
+            }
+            GoalKind::Limit => GoalStatus::UnderLimit,
+        }
+    }
+
+    /// 需要提醒的状态：目标已达成，或上限即将/已经超出
+    fn is_alert(self) -> bool {
+        matches!(
+            self,
+            GoalStatus::Met | GoalStatus::Approaching | GoalStatus::Exceeded
+        )
+    }
+
+    /// 相对本周期已提醒的状态是否需要再次提醒：仅上限从即将超出升级为已超出
+    fn escalates(self, notified: Option<GoalStatus>) -> bool {
+        matches!(
+            (notified, self),
+            (Some(GoalStatus::Approaching), GoalStatus::Exceeded)
+        )
+    }
+}
+
+/// 目标进度
+#[derive(Debug, Clone, Serialize)]
+pub struct GoalProgress {
+    pub goal: Goal,
+    /// 当前周期起止日期 YYYY-MM-DD（含）
+    pub period_start: String,
+    pub period_end: String,
+    /// 周期内的计时时长（含进行中的计时）
+    pub tracked_seconds: i64,
+    /// 距目标或上限的剩余时长，不小于 0
+    pub remaining_seconds: i64,
+    /// 已计时 / 目标，保留一位小数
+    pub percent: f64,
+    pub status: GoalStatus,
+}
+
+const GOAL_COLUMNS: &str = r#"
+    SELECT g.id, g.kind, g.period, g.target_seconds, g.category_id, g.tag_id, g.task_id,
+           COALESCE(c.name, tg.name, t.title, '') AS scope_name, g.is_active, g.created_at
+    FROM goals g
+    LEFT JOIN categories c ON g.category_id = c.id
+    LEFT JOIN tags tg ON g.tag_id = tg.id
+    LEFT JOIN tasks t ON g.task_id = t.id
+"#;
+
+pub async fn list_goals_impl(pool: &SqlitePool) -> Result<Vec<Goal>, ZapError> {
+    let goals = sqlx::query_as::<_, Goal>(&format!("{GOAL_COLUMNS} ORDER BY g.id"))
+        .fetch_all(pool)
+        .await?;
+
+    Ok(goals)
+}
+
+pub async fn create_goal_impl(pool: &SqlitePool, req: SaveGoalRequest) -> Result<Goal, ZapError> {
+    let mut tx = pool.begin().await?;
+    validate_goal(&mut tx, &req).await?;
+
+    let res = sqlx::query(
+        "INSERT INTO goals (kind, period, target_seconds, category_id, tag_id, task_id, is_active)
+         VALUES (?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(req.kind)
+    .bind(req.period)
+    .bind(req.target_seconds)
+    .bind(req.category_id)
+    .bind(req.tag_id)
+    .bind(req.task_id)
+    .bind(req.is_active.unwrap_or(true))
+    .execute(tx.as_mut())
+    .await?;
+
+    let goal = get_goal(tx.as_mut(), res.last_insert_rowid() as u32).await?;
+    tx.commit().await?;
+    Ok(goal)
+}
+
+/// 更新目标；修改后重置提醒状态，当前周期可再次提醒
+pub async fn update_goal_impl(
+    pool: &SqlitePool,
+    goal_id: u32,
+    req: SaveGoalRequest,
+) -> Result<Goal, ZapError> {
+    let mut tx = pool.begin().await?;
+    validate_goal(&mut tx, &req).await?;
+
+    let res = sqlx::query(
+        "UPDATE goals
+         SET kind = ?, period = ?, target_seconds = ?, category_id = ?, tag_id = ?, task_id = ?,
+             is_active = ?, notified_period = NULL, notified_status = NULL,
+             updated_at = datetime('now')
+         WHERE id = ?",
+    )
+    .bind(req.kind)
+    .bind(req.period)
+    .bind(req.target_seconds)
+    .bind(req.category_id)
+    .bind(req.tag_id)
+    .bind(req.task_id)
+    .bind(req.is_active.unwrap_or(true))
+    .bind(goal_id)
+    .execute(tx.as_mut())
+    .await?;
+
+    if res.rows_affected() == 0 {
+        return Err(ZapError::GoalNotFound(goal_id));
+    }
+
+    let goal = get_goal(tx.as_mut(), goal_id).await?;
+    tx.commit().await?;
+    Ok(goal)
+}
+
+pub async fn delete_goal_impl(pool: &SqlitePool, goal_id: u32) -> Result<(), ZapError> {
+    let res = sqlx::query("DELETE FROM goals WHERE id = ?")
+        .bind(goal_id)
+        .execute(pool)
+        .await?;
+
+    if res.rows_affected() == 0 {
+        return Err(ZapError::GoalNotFound(goal_id));
+    }
+    Ok(())
+}
+
+/// 计算所有启用目标在 date（YYYY-MM-DD，默认今天）所在周期内的进度
+pub async fn get_goal_progress_impl(
+    pool: &SqlitePool,
+    date: Option<String>,
+) -> Result<Vec<GoalProgress>, ZapError> {
+    let date = match date.as_deref() {
+        Some(d) => NaiveDate::parse_from_str(d, "%Y-%m-%d")
+            .map_err(|_| ZapError::InvalidGoalData("date must be in YYYY-MM-DD format"))?,
+        None => Local::now().date_naive(),
+    };
+
+    let entries = get_rounding_policy_impl(pool).await?.entries_source();
+    let goals = sqlx::query_as::<_, Goal>(&format!(
+        "{GOAL_COLUMNS} WHERE g.is_active = 1 ORDER BY g.id"
+    ))
+    .fetch_all(pool)
+    .await?;
+
+    let mut progress = Vec::with_capacity(goals.len());
+    for goal in goals {
+        progress.push(goal_progress(pool, &entries, goal, date).await?);
+    }
+    Ok(progress)
+}
+
+/// 找出当前周期内已达成的目标和即将/已经超出的上限，并标记为已提醒；
+/// 每个周期只返回一次，上限从即将超出升级为已超出时再返回一次
+pub async fn take_goal_alerts_impl(pool: &SqlitePool) -> Result<Vec<GoalProgress>, ZapError> {
+    let today = Local::now().date_naive();
+    let entries = get_rounding_policy_impl(pool).await?.entries_source();
+    let mut tx = pool.begin().await?;
+
+    let goals = sqlx::query_as::<_, Goal>(&format!(
+        "{GOAL_COLUMNS} WHERE g.is_active = 1 ORDER BY g.id"
+    ))
+    .fetch_all(tx.as_mut())
+    .await?;
+
+    let mut alerts = Vec::new();
+    for goal in goals {
+        let (notified_period, notified_status): (Option<String>, Option<GoalStatus>) =
+            sqlx::query_as("SELECT notified_period, notified_status FROM goals WHERE id = ?")
+                .bind(goal.id)
+                .fetch_one(tx.as_mut())
+                .await?;
+
+        let progress = goal_progress(tx.as_mut(), &entries, goal, today).await?;
+        if !progress.status.is_alert() {
+            continue;
+        }
+        if notified_period.as_deref() == Some(progress.period_start.as_str())
+            && !progress.status.escalates(notified_status)
+        {
+            continue;
+        }
+
+        sqlx::query("UPDATE goals SET notified_period = ?, notified_status = ? WHERE id = ?")
+            .bind(&progress.period_start)
+            .bind(progress.status)
+            .bind(progress.goal.id)
+            .execute(tx.as_mut())
+            .await?;
+        alerts.push(progress);
+    }
+
+    tx.commit().await?;
+    Ok(alerts)
+}
+
+/// 统计目标作用范围在周期内的计时，口径见 `tracked_seconds_by_day`
+async fn goal_progress<'e, E>(
+    executor: E,
+    entries: &str,
+    goal: Goal,
+    date: NaiveDate,
+) -> Result<GoalProgress, ZapError>
+where
+    E: sqlx::Executor<'e, Database = Sqlite>,
+{
+    let (start, end) = goal.period.range(date);
+    let tracked_seconds = tracked_seconds_by_day(executor, entries, &goal, Some(start), end)
+        .await?
+        .values()
+        .sum();
+
+    let target_seconds = goal.target_seconds;
+    Ok(GoalProgress {
+        period_start: start.to_string(),
+        period_end: end.to_string(),
+        tracked_seconds,
+        remaining_seconds: (target_seconds - tracked_seconds).max(0),
+        percent: (tracked_seconds as f64 / target_seconds as f64 * 1000.0).round() / 10.0,
+        status: GoalStatus::of(goal.kind, tracked_seconds, target_seconds),
+        goal,
+    })
+}
+
+/// 目标作用范围在 [start, end]（本地日期，start 为空时不限）内每天的计时。
+/// entries 为取整后的数据源（`RoundingPolicy::entries_source`），进行中的记录截止到
+/// 当前时刻，跨零点的记录按本地零点拆分，与统计的口径一致
+pub(crate) async fn tracked_seconds_by_day<'e, E>(
+    executor: E,
+    entries: &str,
+    goal: &Goal,
+    start: Option<NaiveDate>,
+    end: NaiveDate,
+) -> Result<BTreeMap<NaiveDate, i64>, ZapError>
+where
+    E: sqlx::Executor<'e, Database = Sqlite>,
+{
+    let (scope, scope_id) = goal.scope_condition()?;
+    let range_start = start.map(local_midnight_utc);
+    let range_end = local_midnight_utc(end + Duration::days(1));
+
+    let rows = sqlx::query_as::<_, (String, Option<String>, i64)>(&format!(
+        r#"
+        SELECT te.started_at, te.ended_at,
+               CASE WHEN te.ended_at IS NULL
+                    THEN MAX(CAST(strftime('%s', 'now') - strftime('%s', te.started_at) AS INTEGER), 0)
+                    ELSE te.duration_seconds END
+        FROM {entries} te
+        JOIN tasks t ON te.task_id = t.id
+        WHERE {scope} AND te.started_at < ?
+          AND (? IS NULL OR te.ended_at IS NULL OR te.ended_at >= ?)
+    "#
+    ))
+    .bind(scope_id)
+    .bind(range_end.format(DATETIME_FORMAT).to_string())
+    .bind(range_start.map(|d| d.format(DATETIME_FORMAT).to_string()))
+    .bind(range_start.map(|d| d.format(DATETIME_FORMAT).to_string()))
+    .fetch_all(executor)
+    .await?;
+
+    let now = Utc::now();
+    let range_start = range_start.unwrap_or(chrono::DateTime::<Utc>::MIN_UTC);
+    let mut days = BTreeMap::new();
+    for (started_at, ended_at, seconds) in rows {
+        let Some(started_at) = parse_utc(&started_at) else {
+            continue;
+        };
+        let ended_at = ended_at.as_deref().and_then(parse_utc).unwrap_or(now);
+        for (day, seconds) in
+            split_by_local_day(started_at, ended_at, seconds, range_start, range_end)
+        {
+            *days.entry(day).or_default() += seconds;
+        }
+    }
+    Ok(days)
+}
+
+async fn validate_goal(
+    tx: &mut Transaction<'_, Sqlite>,
+    req: &SaveGoalRequest,
+) -> Result<(), ZapError> {
+    if req.target_seconds <= 0 {
+        return Err(ZapError::InvalidGoalData("target must be positive"));
+    }
+
+    let (table, id) = match (req.category_id, req.tag_id, req.task_id) {
+        (Some(id), None, None) => ("categories", id),
+        (None, Some(id), None) => ("tags", id),
+        (None, None, Some(id)) => ("tasks", id),
+        _ => {
+            return Err(ZapError::InvalidGoalData(
+                "exactly one of category_id, tag_id and task_id is required",
+            ));
+        }
+    };
+
+    let count: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM {table} WHERE id = ?"))
+        .bind(id)
+        .fetch_one(tx.as_mut())
+        .await?;
+    if count == 0 {
+        return Err(ZapError::InvalidGoalData("goal scope not found"));
+    }
+    Ok(())
+}
+
+pub(crate) async fn get_goal<'e, E>(executor: E, goal_id: u32) -> Result<Goal, ZapError>
+where
+    E: sqlx::Executor<'e, Database = Sqlite>,
+{
+    sqlx::query_as::<_, Goal>(&format!("{GOAL_COLUMNS} WHERE g.id = ?"))
+        .bind(goal_id)
+        .fetch_optional(executor)
+        .await?
+        .ok_or(ZapError::GoalNotFound(goal_id))
+}
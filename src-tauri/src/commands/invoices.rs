@@ -0,0 +1,425 @@
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+
+use crate::{
+    commands::{
+        billing::{BILLABLE_SQL, HOURLY_RATE_SQL, earned_amount, format_amount, get_currency_impl},
+        rounding::get_rounding_policy_impl,
+        settings::{get_setting, set_setting},
+    },
+    error::ZapError,
+};
+
+/// 最近使用的发票顺序号在 settings 表中的键，保证编号只增不减、不会复用
+const INVOICE_SEQ_KEY: &str = "invoice_last_seq";
+
+/// 生成发票请求
+#[derive(Debug, Deserialize)]
+pub struct GenerateInvoiceRequest {
+    /// 开票客户id
+    pub client_id: u32,
+    /// 计费起始日期 YYYY-MM-DD（含）
+    pub start_date: String,
+    /// 计费结束日期 YYYY-MM-DD（含）
+    pub end_date: String,
+}
+
+/// 发票导出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InvoiceFormat {
+    Html,
+    Markdown,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct InvoiceSummary {
+    /// 主键
+    pub id: u32,
+    /// 发票编号
+    pub number: String,
+    /// 客户id
+    pub client_id: u32,
+    /// 客户名称
+    pub client_name: String,
+    /// 计费起始日期
+    pub period_start: String,
+    /// 计费结束日期
+    pub period_end: String,
+    /// 币种
+    pub currency: String,
+    /// 计费总时长（秒）
+    pub total_seconds: i64,
+    /// 总金额（最小货币单位）
+    pub total_amount: i64,
+    /// 创建时间
+    pub created_at: String,
+    /// 作废时间，未作废为空
+    pub voided_at: Option<String>,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct InvoiceLine {
+    /// 对应任务id
+    pub task_id: Option<u32>,
+    /// 明细描述
+    pub description: String,
+    /// 计费时长（秒）
+    pub seconds: i64,
+    /// 小时费率（最小货币单位）
+    pub hourly_rate: i64,
+    /// 金额（最小货币单位）
+    pub amount: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct InvoiceResponse {
+    #[serde(flatten)]
+    pub invoice: InvoiceSummary,
+    pub lines: Vec<InvoiceLine>,
+}
+
+/// 待开票的计时记录
+#[derive(Debug, FromRow)]
+struct BillableEntrySql {
+    entry_id: u32,
+    task_id: u32,
+    title: String,
+    project_name: String,
+    day: String,
+    duration_seconds: i64,
+    hourly_rate: i64,
+}
+
+const INVOICE_COLUMNS: &str = "SELECT i.id, i.number, i.client_id, cl.name AS client_name, \
+     i.period_start, i.period_end, i.currency, i.total_seconds, i.total_amount, i.created_at, i.voided_at \
+     FROM invoices i JOIN clients cl ON i.client_id = cl.id";
+
+/// 按客户与日期范围生成发票：每个任务一行明细，应用取整规则与生效费率，
+/// 并记录已开票的计时记录，避免重复开票
+pub async fn generate_invoice_impl(
+    pool: &SqlitePool,
+    req: GenerateInvoiceRequest,
+) -> Result<InvoiceResponse, ZapError> {
+    let start = parse_date(&req.start_date)?;
+    let end = parse_date(&req.end_date)?;
+    if start > end {
+        return Err(ZapError::InvalidInvoiceData(
+            "start date must not be after end date",
+        ));
+    }
+
+    let policy = get_rounding_policy_impl(pool).await?;
+    let currency = get_currency_impl(pool).await?;
+
+    let mut tx = pool.begin().await?;
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM clients WHERE id = ?")
+        .bind(req.client_id)
+        .fetch_one(tx.as_mut())
+        .await?;
+    if count == 0 {
+        return Err(ZapError::ClientNotFound(req.client_id));
+    }
+
+    let entries = sqlx::query_as::<_, BillableEntrySql>(&format!(
+        r#"
+        SELECT te.id AS entry_id, te.task_id, t.title, p.name AS project_name,
+               date(te.started_at, 'localtime') AS day, te.duration_seconds,
+               {HOURLY_RATE_SQL} AS hourly_rate
+        FROM time_entries te
+        JOIN tasks t ON te.task_id = t.id
+        JOIN projects p ON t.project_id = p.id
+        LEFT JOIN categories c ON t.category_id = c.id
+        WHERE p.client_id = ?
+          AND te.ended_at IS NOT NULL
+          AND {BILLABLE_SQL} = 1
+          AND date(te.started_at, 'localtime') >= date(?)
+          AND date(te.started_at, 'localtime') <= date(?)
+          AND te.id NOT IN (SELECT time_entry_id FROM invoice_entries)
+        ORDER BY p.name, t.title, te.task_id, te.started_at
+    "#
+    ))
+    .bind(req.client_id)
+    .bind(&req.start_date)
+    .bind(&req.end_date)
+    .fetch_all(tx.as_mut())
+    .await?;
+
+    if entries.is_empty() {
+        return Err(ZapError::InvalidInvoiceData("no billable time to invoice"));
+    }
+
+    // 按任务分组生成明细（记录已按任务排序）
+    let mut lines: Vec<InvoiceLine> = Vec::new();
+    for group in entries.chunk_by(|a, b| a.task_id == b.task_id) {
+        let first = &group[0];
+        let seconds =
+            policy.round_task_total(group.iter().map(|e| (e.day.as_str(), e.duration_seconds)));
+        lines.push(InvoiceLine {
+            task_id: Some(first.task_id),
+            description: format!("{} / {}", first.project_name, first.title),
+            seconds,
+            hourly_rate: first.hourly_rate,
+            amount: earned_amount(seconds, first.hourly_rate),
+        });
+    }
+
+    let total_seconds: i64 = lines.iter().map(|l| l.seconds).sum();
+    let total_amount: i64 = lines.iter().map(|l| l.amount).sum();
+
+    let last_seq: Option<i64> = get_setting(tx.as_mut(), INVOICE_SEQ_KEY).await?;
+    let max_seq: i64 = sqlx::query_scalar("SELECT COALESCE(MAX(seq), 0) FROM invoices")
+        .fetch_one(tx.as_mut())
+        .await?;
+    let seq = last_seq.unwrap_or(0).max(max_seq) + 1;
+    set_setting(tx.as_mut(), INVOICE_SEQ_KEY, &seq).await?;
+    let number = format!("INV-{:05}", seq);
+
+    let res = sqlx::query(
+        "INSERT INTO invoices (seq, number, client_id, period_start, period_end, currency, total_seconds, total_amount)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(seq)
+    .bind(&number)
+    .bind(req.client_id)
+    .bind(start.to_string())
+    .bind(end.to_string())
+    .bind(&currency)
+    .bind(total_seconds)
+    .bind(total_amount)
+    .execute(tx.as_mut())
+    .await?;
+    let invoice_id = res.last_insert_rowid();
+
+    for (position, line) in lines.iter().enumerate() {
+        sqlx::query(
+            "INSERT INTO invoice_lines (invoice_id, task_id, description, seconds, hourly_rate, amount, position)
+             VALUES (?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(invoice_id)
+        .bind(line.task_id)
+        .bind(&line.description)
+        .bind(line.seconds)
+        .bind(line.hourly_rate)
+        .bind(line.amount)
+        .bind(position as i64)
+        .execute(tx.as_mut())
+        .await?;
+    }
+
+    for entry in &entries {
+        sqlx::query("INSERT INTO invoice_entries (time_entry_id, invoice_id) VALUES (?, ?)")
+            .bind(entry.entry_id)
+            .bind(invoice_id)
+            .execute(tx.as_mut())
+            .await?;
+    }
+
+    tx.commit().await?;
+
+    get_invoice_impl(pool, invoice_id as u32).await
+}
+
+/// 列出发票，传入 client_id 时只返回该客户的发票
+pub async fn list_invoices_impl(
+    pool: &SqlitePool,
+    client_id: Option<u32>,
+) -> Result<Vec<InvoiceSummary>, ZapError> {
+    let invoices = match client_id {
+        Some(client_id) => {
+            sqlx::query_as::<_, InvoiceSummary>(&format!(
+                "{INVOICE_COLUMNS} WHERE i.client_id = ? ORDER BY i.seq DESC"
+            ))
+            .bind(client_id)
+            .fetch_all(pool)
+            .await?
+        }
+        None => {
+            sqlx::query_as::<_, InvoiceSummary>(&format!("{INVOICE_COLUMNS} ORDER BY i.seq DESC"))
+                .fetch_all(pool)
+                .await?
+        }
+    };
+
+    Ok(invoices)
+}
+
+pub async fn get_invoice_impl(
+    pool: &SqlitePool,
+    invoice_id: u32,
+) -> Result<InvoiceResponse, ZapError> {
+    let invoice = sqlx::query_as::<_, InvoiceSummary>(&format!("{INVOICE_COLUMNS} WHERE i.id = ?"))
+        .bind(invoice_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or(ZapError::InvoiceNotFound(invoice_id))?;
+
+    let lines = sqlx::query_as::<_, InvoiceLine>(
+        "SELECT task_id, description, seconds, hourly_rate, amount
+         FROM invoice_lines WHERE invoice_id = ? ORDER BY position",
+    )
+    .bind(invoice_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(InvoiceResponse { invoice, lines })
+}
+
+/// 作废发票：保留发票与编号，释放其包含的计时记录以便重新开票
+pub async fn void_invoice_impl(
+    pool: &SqlitePool,
+    invoice_id: u32,
+) -> Result<InvoiceResponse, ZapError> {
+    let mut tx = pool.begin().await?;
+
+    let voided_at: Option<Option<String>> =
+        sqlx::query_scalar("SELECT voided_at FROM invoices WHERE id = ?")
+            .bind(invoice_id)
+            .fetch_optional(tx.as_mut())
+            .await?;
+    match voided_at {
+        None => return Err(ZapError::InvoiceNotFound(invoice_id)),
+        Some(Some(_)) => return Err(ZapError::InvalidInvoiceData("invoice already voided")),
+        Some(None) => {}
+    }
+
+    sqlx::query("UPDATE invoices SET voided_at = datetime('now') WHERE id = ?")
+        .bind(invoice_id)
+        .execute(tx.as_mut())
+        .await?;
+    sqlx::query("DELETE FROM invoice_entries WHERE invoice_id = ?")
+        .bind(invoice_id)
+        .execute(tx.as_mut())
+        .await?;
+
+    tx.commit().await?;
+    get_invoice_impl(pool, invoice_id).await
+}
+
+/// 导出发票为 HTML 或 Markdown 文本
+pub async fn export_invoice_impl(
+    pool: &SqlitePool,
+    invoice_id: u32,
+    format: InvoiceFormat,
+) -> Result<String, ZapError> {
+    let invoice = get_invoice_impl(pool, invoice_id).await?;
+
+    Ok(match format {
+        InvoiceFormat::Html => render_html(&invoice),
+        InvoiceFormat::Markdown => render_markdown(&invoice),
+    })
+}
+
+fn render_markdown(invoice: &InvoiceResponse) -> String {
+    let head = &invoice.invoice;
+    let mut out = format!(
+        "# Invoice {}{}\n\n**Client:** {}  \n**Period:** {} – {}  \n**Issued:** {}\n\n",
+        head.number,
+        void_mark(head),
+        escape_markdown(&head.client_name),
+        head.period_start,
+        head.period_end,
+        head.created_at,
+    );
+    out.push_str("| Description | Hours | Rate | Amount |\n");
+    out.push_str("|---|---:|---:|---:|\n");
+    for line in &invoice.lines {
+        out.push_str(&format!(
+            "| {} | {} | {} | {} |\n",
+            escape_markdown(&line.description),
+            format_hours(line.seconds),
+            format_amount(line.hourly_rate, &head.currency),
+            format_amount(line.amount, &head.currency),
+        ));
+    }
+    out.push_str(&format!(
+        "| **Total** | **{}** | | **{}** |\n",
+        format_hours(head.total_seconds),
+        format_amount(head.total_amount, &head.currency),
+    ));
+    out
+}
+
+fn render_html(invoice: &InvoiceResponse) -> String {
+    let head = &invoice.invoice;
+    let mut rows = String::new();
+    for line in &invoice.lines {
+        rows.push_str(&format!(
+            "<tr><td>{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td></tr>\n",
+            escape_html(&line.description),
+            format_hours(line.seconds),
+            format_amount(line.hourly_rate, &head.currency),
+            format_amount(line.amount, &head.currency),
+        ));
+    }
+
+    format!(
+        r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>Invoice {number}</title>
+<style>
+body {{ font-family: sans-serif; }}
+table {{ border-collapse: collapse; width: 100%; }}
+th, td {{ border-bottom: 1px solid #ddd; padding: 6px 8px; text-align: left; }}
+.num {{ text-align: right; }}
+</style>
+</head>
+<body>
+<h1>Invoice {number}{void}</h1>
+<p><strong>Client:</strong> {client}<br>
+<strong>Period:</strong> {start} – {end}<br>
+<strong>Issued:</strong> {issued}</p>
+<table>
+<thead><tr><th>Description</th><th class="num">Hours</th><th class="num">Rate</th><th class="num">Amount</th></tr></thead>
+<tbody>
+{rows}</tbody>
+<tfoot><tr><th>Total</th><th class="num">{hours}</th><th></th><th class="num">{total}</th></tr></tfoot>
+</table>
+</body>
+</html>
+"#,
+        number = escape_html(&head.number),
+        void = void_mark(head),
+        client = escape_html(&head.client_name),
+        start = head.period_start,
+        end = head.period_end,
+        issued = head.created_at,
+        rows = rows,
+        hours = format_hours(head.total_seconds),
+        total = format_amount(head.total_amount, &head.currency),
+    )
+}
+
+/// 已作废发票在导出标题后追加的标记
+fn void_mark(head: &InvoiceSummary) -> &'static str {
+    if head.voided_at.is_some() {
+        " (VOID)"
+    } else {
+        ""
+    }
+}
+
+fn parse_date(date: &str) -> Result<chrono::NaiveDate, ZapError> {
+    chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map_err(|_| ZapError::InvalidInvoiceData("date must be YYYY-MM-DD"))
+}
+
+/// 时长格式化为 `H:MM`
+fn format_hours(seconds: i64) -> String {
+    let minutes = seconds / 60;
+    format!("{}:{:02}", minutes / 60, minutes % 60)
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn escape_markdown(text: &str) -> String {
+    text.replace('|', "\\|")
+}
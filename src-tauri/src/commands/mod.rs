@@ -1,22 +1,93 @@
+pub mod billing;
 pub mod categories;
+pub mod clients;
 pub mod daily_focus;
+pub mod goals;
+pub mod invoices;
+pub mod projects;
+pub mod recurrence;
+pub mod rollups;
+pub mod rounding;
+pub mod search;
+pub mod settings;
 pub mod statistics;
+pub mod streaks;
+pub mod tags;
 pub mod task;
+pub mod task_items;
+pub mod time_entries;
 pub mod types;
 pub use crate::commands::{
+    billing::{
+        earned_amount, get_currency_impl, set_category_rate_impl, set_currency_impl,
+        set_time_entry_billable_impl,
+    },
     categories::{CategoryResponse, list_categories_impl},
+    clients::{
+        ClientResponse, SaveClientRequest, create_client_impl, delete_client_impl,
+        list_clients_impl, update_client_impl,
+    },
+    goals::{
+        Goal, GoalKind, GoalPeriod, GoalProgress, GoalStatus, SaveGoalRequest, create_goal_impl,
+        delete_goal_impl, get_goal_progress_impl, list_goals_impl, take_goal_alerts_impl,
+        update_goal_impl,
+    },
+    invoices::{
+        GenerateInvoiceRequest, InvoiceFormat, InvoiceResponse, InvoiceSummary,
+        export_invoice_impl, generate_invoice_impl, get_invoice_impl, list_invoices_impl,
+        void_invoice_impl,
+    },
+    projects::{
+        ProjectResponse, SaveProjectRequest, create_project_impl, delete_project_impl,
+        list_projects_impl, update_project_impl,
+    },
+    recurrence::{
+        CreateRecurringTaskRequest, RecurrenceRule, RecurringTask, RecurringTaskStats,
+        create_recurring_task_impl, delete_recurring_task_impl, get_recurring_task_stats_impl,
+        list_recurring_tasks_impl, materialize_recurring_tasks_impl,
+        set_recurring_task_active_impl,
+    },
+    rollups::{
+        RollupCheckReport, RollupMismatch, check_task_day_totals_impl, rebuild_task_day_totals_impl,
+    },
+    rounding::{
+        RoundingMode, RoundingPolicy, RoundingScope, get_rounding_policy_impl,
+        set_rounding_policy_impl,
+    },
+    search::{SearchHit, SearchHitKind, SearchRequest, search_impl},
     statistics::{
-        MonthStatsResponse, StatsDateQuery, TodayStatsResponse, WeekStatsResponse,
-        get_month_stats_impl, get_today_stats_impl, get_week_stats_impl,
+        EstimateAccuracyResponse, HourlyStatsResponse, MonthStatsResponse, StatsBucket,
+        StatsDateQuery, StatsGranularity, StatsGroup, StatsGroupBy, StatsGroupItem, StatsReport,
+        TagStatsResponse, TodayStatsResponse, WeekStatsResponse, YearStatsResponse,
+        get_estimate_accuracy_impl, get_hourly_stats_impl, get_month_stats_impl,
+        get_range_stats_impl, get_stats_impl, get_tag_stats_impl, get_today_stats_impl,
+        get_week_stats_impl, get_year_stats_impl,
+    },
+    streaks::{StreakKind, StreakQuery, StreakResponse, StreakRun, get_streaks_impl},
+    tags::{
+        SaveTagRequest, TagResponse, create_tag_impl, delete_tag_impl, list_tags_impl,
+        list_task_tags_impl, set_task_tags_impl, update_tag_impl,
     },
     task::{
-        CreateTaskRequestBuilder, TaskQueryBuilder, add_task_impl, finish_task_impl,
-        list_tasks_impl, start_task_impl, stop_task_impl, toggle_task_done_impl,
+        CreateTaskRequestBuilder, DueFilter, EstimateOverrun, TaskPriority, TaskQueryBuilder,
+        TaskSort, UpdateTaskRequestBuilder, add_task_impl, count_overdue_tasks_impl,
+        finish_task_impl, list_tasks_impl, reorder_tasks_impl, start_task_impl, stop_task_impl,
+        take_estimate_overruns_impl, toggle_task_done_impl, update_task_impl,
+    },
+    task_items::{
+        AddTaskItemRequest, OpenItemsPolicy, TaskItem, UpdateTaskItemRequest, add_task_item_impl,
+        delete_task_item_impl, get_open_items_policy_impl, list_task_items_impl,
+        reorder_task_items_impl, set_open_items_policy_impl, update_task_item_impl,
     },
+    time_entries::{
+        FocusPolicy, SessionKind, TimeEntryQuery, TimeEntryResponse, get_focus_policy_impl,
+        list_time_entries_impl, set_focus_policy_impl, set_session_kind_impl,
+    },
+    types::PaginationMode,
 };
 use crate::{
     commands::{
-        task::{CreateTaskRequest, TaskQuery, TaskResponse},
+        task::{CreateTaskRequest, TaskQuery, TaskResponse, UpdateTaskRequest},
         types::PaginatedResponse,
     },
     error::ZapError,
@@ -31,11 +102,243 @@ pub async fn add_task(
     add_task_impl(&db.pool, create_task).await
 }
 
+#[tauri::command]
+pub async fn update_task(
+    db: tauri::State<'_, Db>,
+    task_id: u32,
+    req: UpdateTaskRequest,
+) -> Result<(), ZapError> {
+    update_task_impl(&db.pool, task_id, req).await
+}
+
+#[tauri::command]
+pub async fn list_time_entries(
+    db: tauri::State<'_, Db>,
+    query: TimeEntryQuery,
+) -> Result<PaginatedResponse<TimeEntryResponse>, ZapError> {
+    list_time_entries_impl(&db.pool, query).await
+}
+
+#[tauri::command]
+pub async fn set_session_kind(
+    db: tauri::State<'_, Db>,
+    entry_id: u32,
+    kind: SessionKind,
+) -> Result<(), ZapError> {
+    set_session_kind_impl(&db.pool, entry_id, kind).await
+}
+
+#[tauri::command]
+pub async fn rebuild_task_day_totals(db: tauri::State<'_, Db>) -> Result<u64, ZapError> {
+    rebuild_task_day_totals_impl(&db.pool).await
+}
+
+#[tauri::command]
+pub async fn check_task_day_totals(
+    db: tauri::State<'_, Db>,
+) -> Result<RollupCheckReport, ZapError> {
+    check_task_day_totals_impl(&db.pool).await
+}
+
+#[tauri::command]
+pub async fn list_goals(db: tauri::State<'_, Db>) -> Result<Vec<Goal>, ZapError> {
+    list_goals_impl(&db.pool).await
+}
+
+#[tauri::command]
+pub async fn create_goal(db: tauri::State<'_, Db>, req: SaveGoalRequest) -> Result<Goal, ZapError> {
+    create_goal_impl(&db.pool, req).await
+}
+
+#[tauri::command]
+pub async fn update_goal(
+    db: tauri::State<'_, Db>,
+    goal_id: u32,
+    req: SaveGoalRequest,
+) -> Result<Goal, ZapError> {
+    update_goal_impl(&db.pool, goal_id, req).await
+}
+
+#[tauri::command]
+pub async fn delete_goal(db: tauri::State<'_, Db>, goal_id: u32) -> Result<(), ZapError> {
+    delete_goal_impl(&db.pool, goal_id).await
+}
+
+#[tauri::command]
+pub async fn get_goal_progress(
+    db: tauri::State<'_, Db>,
+    date: Option<String>,
+) -> Result<Vec<GoalProgress>, ZapError> {
+    get_goal_progress_impl(&db.pool, date).await
+}
+
+#[tauri::command]
+pub async fn list_task_items(
+    db: tauri::State<'_, Db>,
+    task_id: u32,
+) -> Result<Vec<TaskItem>, ZapError> {
+    list_task_items_impl(&db.pool, task_id).await
+}
+
+#[tauri::command]
+pub async fn add_task_item(
+    db: tauri::State<'_, Db>,
+    req: AddTaskItemRequest,
+) -> Result<TaskItem, ZapError> {
+    add_task_item_impl(&db.pool, req).await
+}
+
+#[tauri::command]
+pub async fn update_task_item(
+    db: tauri::State<'_, Db>,
+    item_id: u32,
+    req: UpdateTaskItemRequest,
+) -> Result<TaskItem, ZapError> {
+    update_task_item_impl(&db.pool, item_id, req).await
+}
+
+#[tauri::command]
+pub async fn delete_task_item(db: tauri::State<'_, Db>, item_id: u32) -> Result<(), ZapError> {
+    delete_task_item_impl(&db.pool, item_id).await
+}
+
+#[tauri::command]
+pub async fn reorder_task_items(
+    db: tauri::State<'_, Db>,
+    task_id: u32,
+    item_ids: Vec<u32>,
+) -> Result<Vec<TaskItem>, ZapError> {
+    reorder_task_items_impl(&db.pool, task_id, item_ids).await
+}
+
+#[tauri::command]
+pub async fn get_open_items_policy(db: tauri::State<'_, Db>) -> Result<OpenItemsPolicy, ZapError> {
+    get_open_items_policy_impl(&db.pool).await
+}
+
+#[tauri::command]
+pub async fn set_open_items_policy(
+    db: tauri::State<'_, Db>,
+    policy: OpenItemsPolicy,
+) -> Result<OpenItemsPolicy, ZapError> {
+    set_open_items_policy_impl(&db.pool, policy).await
+}
+
 #[tauri::command]
 pub async fn list_categories(db: tauri::State<'_, Db>) -> Result<Vec<CategoryResponse>, ZapError> {
     list_categories_impl(&db.pool).await
 }
 
+#[tauri::command]
+pub async fn set_category_rate(
+    db: tauri::State<'_, Db>,
+    category_id: u32,
+    hourly_rate: Option<i64>,
+) -> Result<(), ZapError> {
+    set_category_rate_impl(&db.pool, category_id, hourly_rate).await
+}
+
+#[tauri::command]
+pub async fn list_tags(db: tauri::State<'_, Db>) -> Result<Vec<TagResponse>, ZapError> {
+    list_tags_impl(&db.pool).await
+}
+
+#[tauri::command]
+pub async fn create_tag(
+    db: tauri::State<'_, Db>,
+    req: SaveTagRequest,
+) -> Result<TagResponse, ZapError> {
+    create_tag_impl(&db.pool, req).await
+}
+
+#[tauri::command]
+pub async fn update_tag(
+    db: tauri::State<'_, Db>,
+    tag_id: u32,
+    req: SaveTagRequest,
+) -> Result<TagResponse, ZapError> {
+    update_tag_impl(&db.pool, tag_id, req).await
+}
+
+#[tauri::command]
+pub async fn delete_tag(db: tauri::State<'_, Db>, tag_id: u32) -> Result<(), ZapError> {
+    delete_tag_impl(&db.pool, tag_id).await
+}
+
+#[tauri::command]
+pub async fn list_task_tags(
+    db: tauri::State<'_, Db>,
+    task_id: u32,
+) -> Result<Vec<TagResponse>, ZapError> {
+    list_task_tags_impl(&db.pool, task_id).await
+}
+
+#[tauri::command]
+pub async fn set_task_tags(
+    db: tauri::State<'_, Db>,
+    task_id: u32,
+    tag_ids: Vec<u32>,
+) -> Result<Vec<TagResponse>, ZapError> {
+    set_task_tags_impl(&db.pool, task_id, tag_ids).await
+}
+
+#[tauri::command]
+pub async fn list_clients(db: tauri::State<'_, Db>) -> Result<Vec<ClientResponse>, ZapError> {
+    list_clients_impl(&db.pool).await
+}
+
+#[tauri::command]
+pub async fn create_client(
+    db: tauri::State<'_, Db>,
+    req: SaveClientRequest,
+) -> Result<ClientResponse, ZapError> {
+    create_client_impl(&db.pool, req).await
+}
+
+#[tauri::command]
+pub async fn update_client(
+    db: tauri::State<'_, Db>,
+    client_id: u32,
+    req: SaveClientRequest,
+) -> Result<ClientResponse, ZapError> {
+    update_client_impl(&db.pool, client_id, req).await
+}
+
+#[tauri::command]
+pub async fn delete_client(db: tauri::State<'_, Db>, client_id: u32) -> Result<(), ZapError> {
+    delete_client_impl(&db.pool, client_id).await
+}
+
+#[tauri::command]
+pub async fn list_projects(
+    db: tauri::State<'_, Db>,
+    client_id: Option<u32>,
+) -> Result<Vec<ProjectResponse>, ZapError> {
+    list_projects_impl(&db.pool, client_id).await
+}
+
+#[tauri::command]
+pub async fn create_project(
+    db: tauri::State<'_, Db>,
+    req: SaveProjectRequest,
+) -> Result<ProjectResponse, ZapError> {
+    create_project_impl(&db.pool, req).await
+}
+
+#[tauri::command]
+pub async fn update_project(
+    db: tauri::State<'_, Db>,
+    project_id: u32,
+    req: SaveProjectRequest,
+) -> Result<ProjectResponse, ZapError> {
+    update_project_impl(&db.pool, project_id, req).await
+}
+
+#[tauri::command]
+pub async fn delete_project(db: tauri::State<'_, Db>, project_id: u32) -> Result<(), ZapError> {
+    delete_project_impl(&db.pool, project_id).await
+}
+
 #[tauri::command]
 pub async fn list_tasks(
     db: tauri::State<'_, Db>,
@@ -44,6 +347,64 @@ pub async fn list_tasks(
     list_tasks_impl(&db.pool, req).await
 }
 
+#[tauri::command]
+pub async fn reorder_tasks(db: tauri::State<'_, Db>, task_ids: Vec<u32>) -> Result<(), ZapError> {
+    reorder_tasks_impl(&db.pool, task_ids).await
+}
+
+#[tauri::command]
+pub async fn search(
+    db: tauri::State<'_, Db>,
+    req: SearchRequest,
+) -> Result<Vec<SearchHit>, ZapError> {
+    search_impl(&db.pool, req).await
+}
+
+#[tauri::command]
+pub async fn count_overdue_tasks(db: tauri::State<'_, Db>) -> Result<i64, ZapError> {
+    count_overdue_tasks_impl(&db.pool).await
+}
+
+#[tauri::command]
+pub async fn list_recurring_tasks(
+    db: tauri::State<'_, Db>,
+) -> Result<Vec<RecurringTask>, ZapError> {
+    list_recurring_tasks_impl(&db.pool).await
+}
+
+#[tauri::command]
+pub async fn create_recurring_task(
+    db: tauri::State<'_, Db>,
+    req: CreateRecurringTaskRequest,
+) -> Result<RecurringTask, ZapError> {
+    create_recurring_task_impl(&db.pool, req).await
+}
+
+#[tauri::command]
+pub async fn set_recurring_task_active(
+    db: tauri::State<'_, Db>,
+    recurring_task_id: u32,
+    is_active: bool,
+) -> Result<RecurringTask, ZapError> {
+    set_recurring_task_active_impl(&db.pool, recurring_task_id, is_active).await
+}
+
+#[tauri::command]
+pub async fn delete_recurring_task(
+    db: tauri::State<'_, Db>,
+    recurring_task_id: u32,
+) -> Result<(), ZapError> {
+    delete_recurring_task_impl(&db.pool, recurring_task_id).await
+}
+
+#[tauri::command]
+pub async fn get_recurring_task_stats(
+    db: tauri::State<'_, Db>,
+    recurring_task_id: u32,
+) -> Result<RecurringTaskStats, ZapError> {
+    get_recurring_task_stats_impl(&db.pool, recurring_task_id).await
+}
+
 #[tauri::command]
 pub async fn start_task(db: tauri::State<'_, Db>, task_id: u32) -> Result<(), ZapError> {
     start_task_impl(&db.pool, task_id).await
@@ -64,6 +425,84 @@ pub async fn toggle_task_done(db: tauri::State<'_, Db>, task_id: u32) -> Result<
     toggle_task_done_impl(&db.pool, task_id).await
 }
 
+#[tauri::command]
+pub async fn set_time_entry_billable(
+    db: tauri::State<'_, Db>,
+    entry_id: u32,
+    billable: Option<bool>,
+) -> Result<(), ZapError> {
+    set_time_entry_billable_impl(&db.pool, entry_id, billable).await
+}
+
+#[tauri::command]
+pub async fn generate_invoice(
+    db: tauri::State<'_, Db>,
+    req: GenerateInvoiceRequest,
+) -> Result<InvoiceResponse, ZapError> {
+    generate_invoice_impl(&db.pool, req).await
+}
+
+#[tauri::command]
+pub async fn list_invoices(
+    db: tauri::State<'_, Db>,
+    client_id: Option<u32>,
+) -> Result<Vec<InvoiceSummary>, ZapError> {
+    list_invoices_impl(&db.pool, client_id).await
+}
+
+#[tauri::command]
+pub async fn get_invoice(
+    db: tauri::State<'_, Db>,
+    invoice_id: u32,
+) -> Result<InvoiceResponse, ZapError> {
+    get_invoice_impl(&db.pool, invoice_id).await
+}
+
+#[tauri::command]
+pub async fn void_invoice(
+    db: tauri::State<'_, Db>,
+    invoice_id: u32,
+) -> Result<InvoiceResponse, ZapError> {
+    void_invoice_impl(&db.pool, invoice_id).await
+}
+
+#[tauri::command]
+pub async fn export_invoice(
+    db: tauri::State<'_, Db>,
+    invoice_id: u32,
+    format: InvoiceFormat,
+) -> Result<String, ZapError> {
+    export_invoice_impl(&db.pool, invoice_id, format).await
+}
+
+#[tauri::command]
+pub async fn get_currency(db: tauri::State<'_, Db>) -> Result<String, ZapError> {
+    get_currency_impl(&db.pool).await
+}
+
+#[tauri::command]
+pub async fn set_currency(db: tauri::State<'_, Db>, currency: String) -> Result<String, ZapError> {
+    set_currency_impl(&db.pool, currency).await
+}
+
+#[tauri::command]
+pub async fn get_stats(
+    db: tauri::State<'_, Db>,
+    range: StatsDateQuery,
+    granularity: StatsGranularity,
+    group_by: Vec<StatsGroupBy>,
+) -> Result<StatsReport, ZapError> {
+    get_stats_impl(&db.pool, range, granularity, &group_by).await
+}
+
+#[tauri::command]
+pub async fn get_hourly_stats(
+    db: tauri::State<'_, Db>,
+    query: StatsDateQuery,
+) -> Result<HourlyStatsResponse, ZapError> {
+    get_hourly_stats_impl(&db.pool, query).await
+}
+
 #[tauri::command]
 pub async fn get_today_stats(
     db: tauri::State<'_, Db>,
@@ -88,6 +527,72 @@ pub async fn get_month_stats(
     get_month_stats_impl(&db.pool, query).await
 }
 
+#[tauri::command]
+pub async fn get_year_stats(
+    db: tauri::State<'_, Db>,
+    query: StatsDateQuery,
+) -> Result<YearStatsResponse, ZapError> {
+    get_year_stats_impl(&db.pool, query).await
+}
+
+#[tauri::command]
+pub async fn get_range_stats(
+    db: tauri::State<'_, Db>,
+    query: StatsDateQuery,
+) -> Result<StatsReport, ZapError> {
+    get_range_stats_impl(&db.pool, query).await
+}
+
+#[tauri::command]
+pub async fn get_tag_stats(
+    db: tauri::State<'_, Db>,
+    query: StatsDateQuery,
+) -> Result<TagStatsResponse, ZapError> {
+    get_tag_stats_impl(&db.pool, query).await
+}
+
+#[tauri::command]
+pub async fn get_estimate_accuracy(
+    db: tauri::State<'_, Db>,
+    query: StatsDateQuery,
+) -> Result<EstimateAccuracyResponse, ZapError> {
+    get_estimate_accuracy_impl(&db.pool, query).await
+}
+
+#[tauri::command]
+pub async fn get_rounding_policy(db: tauri::State<'_, Db>) -> Result<RoundingPolicy, ZapError> {
+    get_rounding_policy_impl(&db.pool).await
+}
+
+#[tauri::command]
+pub async fn set_rounding_policy(
+    db: tauri::State<'_, Db>,
+    policy: RoundingPolicy,
+) -> Result<RoundingPolicy, ZapError> {
+    set_rounding_policy_impl(&db.pool, policy).await
+}
+
+#[tauri::command]
+pub async fn get_focus_policy(db: tauri::State<'_, Db>) -> Result<FocusPolicy, ZapError> {
+    get_focus_policy_impl(&db.pool).await
+}
+
+#[tauri::command]
+pub async fn set_focus_policy(
+    db: tauri::State<'_, Db>,
+    policy: FocusPolicy,
+) -> Result<FocusPolicy, ZapError> {
+    set_focus_policy_impl(&db.pool, policy).await
+}
+
+#[tauri::command]
+pub async fn get_streaks(
+    db: tauri::State<'_, Db>,
+    query: StreakQuery,
+) -> Result<StreakResponse, ZapError> {
+    get_streaks_impl(&db.pool, query).await
+}
+
 #[tauri::command]
 pub async fn get_daily_focus(
     db: tauri::State<'_, Db>,
@@ -111,3 +616,104 @@ pub async fn toggle_daily_focus(
 ) -> Result<daily_focus::DailyFocus, ZapError> {
     daily_focus::toggle_daily_focus_impl(&db.pool, id).await
 }
+
+#[tauri::command]
+pub async fn list_daily_focus(
+    db: tauri::State<'_, Db>,
+    date: String,
+) -> Result<Vec<daily_focus::DailyFocus>, ZapError> {
+    daily_focus::list_daily_focus_impl(&db.pool, date).await
+}
+
+#[tauri::command]
+pub async fn add_daily_focus(
+    db: tauri::State<'_, Db>,
+    req: daily_focus::AddDailyFocusRequest,
+) -> Result<daily_focus::DailyFocus, ZapError> {
+    daily_focus::add_daily_focus_impl(&db.pool, req).await
+}
+
+#[tauri::command]
+pub async fn update_daily_focus(
+    db: tauri::State<'_, Db>,
+    id: i64,
+    req: daily_focus::UpdateDailyFocusRequest,
+) -> Result<daily_focus::DailyFocus, ZapError> {
+    daily_focus::update_daily_focus_impl(&db.pool, id, req).await
+}
+
+#[tauri::command]
+pub async fn delete_daily_focus(db: tauri::State<'_, Db>, id: i64) -> Result<(), ZapError> {
+    daily_focus::delete_daily_focus_impl(&db.pool, id).await
+}
+
+#[tauri::command]
+pub async fn reorder_daily_focus(
+    db: tauri::State<'_, Db>,
+    date: String,
+    ids: Vec<i64>,
+) -> Result<Vec<daily_focus::DailyFocus>, ZapError> {
+    daily_focus::reorder_daily_focus_impl(&db.pool, date, ids).await
+}
+
+#[tauri::command]
+pub async fn get_daily_focus_policy(
+    db: tauri::State<'_, Db>,
+) -> Result<daily_focus::DailyFocusPolicy, ZapError> {
+    daily_focus::get_daily_focus_policy_impl(&db.pool).await
+}
+
+#[tauri::command]
+pub async fn set_daily_focus_policy(
+    db: tauri::State<'_, Db>,
+    policy: daily_focus::DailyFocusPolicy,
+) -> Result<daily_focus::DailyFocusPolicy, ZapError> {
+    daily_focus::set_daily_focus_policy_impl(&db.pool, policy).await
+}
+
+#[tauri::command]
+pub async fn start_daily_focus(
+    db: tauri::State<'_, Db>,
+    id: i64,
+) -> Result<daily_focus::DailyFocus, ZapError> {
+    daily_focus::start_daily_focus_impl(&db.pool, id).await
+}
+
+#[tauri::command]
+pub async fn rollover_daily_focus(
+    db: tauri::State<'_, Db>,
+) -> Result<Vec<daily_focus::DailyFocus>, ZapError> {
+    let today = chrono::Local::now().date_naive();
+    daily_focus::rollover_daily_focus_impl(&db.pool, today).await
+}
+
+#[tauri::command]
+pub async fn get_rollover_policy(
+    db: tauri::State<'_, Db>,
+) -> Result<daily_focus::RolloverPolicy, ZapError> {
+    daily_focus::get_rollover_policy_impl(&db.pool).await
+}
+
+#[tauri::command]
+pub async fn set_rollover_policy(
+    db: tauri::State<'_, Db>,
+    policy: daily_focus::RolloverPolicy,
+) -> Result<daily_focus::RolloverPolicy, ZapError> {
+    daily_focus::set_rollover_policy_impl(&db.pool, policy).await
+}
+
+#[tauri::command]
+pub async fn get_daily_focus_history(
+    db: tauri::State<'_, Db>,
+    query: daily_focus::DailyFocusHistoryQuery,
+) -> Result<Vec<daily_focus::DailyFocusHistoryDay>, ZapError> {
+    daily_focus::get_daily_focus_history_impl(&db.pool, query).await
+}
+
+#[tauri::command]
+pub async fn get_weekly_focus_review(
+    db: tauri::State<'_, Db>,
+    date: Option<String>,
+) -> Result<daily_focus::WeeklyFocusReview, ZapError> {
+    daily_focus::get_weekly_focus_review_impl(&db.pool, date).await
+}
@@ -0,0 +1,174 @@
+use serde::{Deserialize, Serialize};
+use sqlx::{SqlitePool, prelude::FromRow};
+
+use crate::error::ZapError;
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct ProjectResponse {
+    /// 主键
+    pub id: u32,
+    /// 项目名称
+    pub name: String,
+    /// 所属客户id
+    pub client_id: Option<u32>,
+    /// 所属客户名称
+    pub client_name: Option<String>,
+    /// 项目级小时费率（最小货币单位）
+    pub hourly_rate: Option<i64>,
+    /// 创建时间
+    pub created_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SaveProjectRequest {
+    /// 项目名称
+    pub name: String,
+    /// 所属客户id
+    pub client_id: Option<u32>,
+    /// 项目级小时费率（最小货币单位）
+    pub hourly_rate: Option<i64>,
+}
+
+const PROJECT_COLUMNS: &str = "SELECT p.id, p.name, p.client_id, cl.name AS client_name, p.hourly_rate, p.created_at \
+     FROM projects p LEFT JOIN clients cl ON p.client_id = cl.id";
+
+/// 列出项目，传入 client_id 时只返回该客户下的项目
+pub async fn list_projects_impl(
+    pool: &SqlitePool,
+    client_id: Option<u32>,
+) -> Result<Vec<ProjectResponse>, ZapError> {
+    let projects = match client_id {
+        Some(client_id) => {
+            sqlx::query_as::<_, ProjectResponse>(&format!(
+                "{PROJECT_COLUMNS} WHERE p.client_id = ? ORDER BY p.name"
+            ))
+            .bind(client_id)
+            .fetch_all(pool)
+            .await?
+        }
+        None => {
+            sqlx::query_as::<_, ProjectResponse>(&format!(
+                "{PROJECT_COLUMNS} ORDER BY cl.name, p.name"
+            ))
+            .fetch_all(pool)
+            .await?
+        }
+    };
+
+    Ok(projects)
+}
+
+pub async fn create_project_impl(
+    pool: &SqlitePool,
+    req: SaveProjectRequest,
+) -> Result<ProjectResponse, ZapError> {
+    let name = validate_project(pool, &req).await?;
+
+    let res = sqlx::query("INSERT INTO projects (name, client_id, hourly_rate) VALUES (?, ?, ?)")
+        .bind(name)
+        .bind(req.client_id)
+        .bind(req.hourly_rate)
+        .execute(pool)
+        .await
+        .map_err(duplicate_name)?;
+
+    get_project_by_id(pool, res.last_insert_rowid() as u32).await
+}
+
+pub async fn update_project_impl(
+    pool: &SqlitePool,
+    project_id: u32,
+    req: SaveProjectRequest,
+) -> Result<ProjectResponse, ZapError> {
+    let name = validate_project(pool, &req).await?;
+
+    let res =
+        sqlx::query("UPDATE projects SET name = ?, client_id = ?, hourly_rate = ? WHERE id = ?")
+            .bind(name)
+            .bind(req.client_id)
+            .bind(req.hourly_rate)
+            .bind(project_id)
+            .execute(pool)
+            .await
+            .map_err(duplicate_name)?;
+
+    if res.rows_affected() == 0 {
+        return Err(ZapError::ProjectNotFound(project_id));
+    }
+
+    get_project_by_id(pool, project_id).await
+}
+
+pub async fn delete_project_impl(pool: &SqlitePool, project_id: u32) -> Result<(), ZapError> {
+    let mut tx = pool.begin().await?;
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM tasks WHERE project_id = ?")
+        .bind(project_id)
+        .fetch_one(tx.as_mut())
+        .await?;
+
+    if count > 0 {
+        return Err(ZapError::ProjectHasTasks);
+    }
+
+    let res = sqlx::query("DELETE FROM projects WHERE id = ?")
+        .bind(project_id)
+        .execute(tx.as_mut())
+        .await?;
+
+    if res.rows_affected() == 0 {
+        return Err(ZapError::ProjectNotFound(project_id));
+    }
+
+    tx.commit().await?;
+    Ok(())
+}
+
+async fn get_project_by_id(
+    pool: &SqlitePool,
+    project_id: u32,
+) -> Result<ProjectResponse, ZapError> {
+    sqlx::query_as::<_, ProjectResponse>(&format!("{PROJECT_COLUMNS} WHERE p.id = ?"))
+        .bind(project_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or(ZapError::ProjectNotFound(project_id))
+}
+
+/// 同一客户（或均未关联客户）下项目名称重复时返回 InvalidProjectData，其余数据库错误原样返回
+fn duplicate_name(err: sqlx::Error) -> ZapError {
+    match err {
+        sqlx::Error::Database(ref e) if e.is_unique_violation() => {
+            ZapError::InvalidProjectData("project name already exists")
+        }
+        err => err.into(),
+    }
+}
+
+async fn validate_project<'a>(
+    pool: &SqlitePool,
+    req: &'a SaveProjectRequest,
+) -> Result<&'a str, ZapError> {
+    let name = req.name.trim();
+    if name.is_empty() {
+        return Err(ZapError::InvalidProjectData("name cannot be empty"));
+    }
+    if req.hourly_rate.is_some_and(|r| r < 0) {
+        return Err(ZapError::InvalidProjectData(
+            "hourly rate cannot be negative",
+        ));
+    }
+
+    if let Some(client_id) = req.client_id {
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM clients WHERE id = ?")
+            .bind(client_id)
+            .fetch_one(pool)
+            .await?;
+
+        if count == 0 {
+            return Err(ZapError::InvalidProjectData("client id not found"));
+        }
+    }
+
+    Ok(name)
+}
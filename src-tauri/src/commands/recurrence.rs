@@ -0,0 +1,569 @@
+use std::str::FromStr;
+
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, Sqlite, SqlitePool, Transaction};
+
+use crate::{
+    commands::{
+        rounding::get_rounding_policy_impl,
+        task::{ensure_category_exists, ensure_project_exists},
+    },
+    error::ZapError,
+};
+
+/// 查找下一次发生日期时最多向后搜索的天数（乘以 INTERVAL）
+const MAX_SEARCH_DAYS: i64 = 366 * 8;
+
+/// INTERVAL 的上限，避免搜索范围过大
+const MAX_INTERVAL: u32 = 1000;
+
+/// 重复频率
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+/// 重复规则，支持 RFC 5545 RRULE 的以下子集：
+///
+/// - `FREQ=DAILY|WEEKLY|MONTHLY`（必填）
+/// - `INTERVAL=n`：每 n 天/周/月，1..1000
+/// - `BYDAY=MO,TU,...`：仅 WEEKLY，缺省为起始日期的星期
+/// - `BYMONTHDAY=d`：仅 MONTHLY，1..31 或 -1..-31（倒数），缺省为起始日期的日；
+///   当月没有该日时跳过
+/// - `UNTIL=YYYYMMDD`：最后一次发生日期（含）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecurrenceRule {
+    freq: Frequency,
+    interval: u32,
+    by_day: Vec<Weekday>,
+    by_month_day: Option<i32>,
+    until: Option<NaiveDate>,
+}
+
+impl FromStr for RecurrenceRule {
+    type Err = ZapError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let s = s.strip_prefix("RRULE:").unwrap_or(s);
+
+        let mut freq = None;
+        let mut interval = 1;
+        let mut by_day = Vec::new();
+        let mut by_month_day = None;
+        let mut until = None;
+
+        for part in s.split(';').filter(|p| !p.is_empty()) {
+            let (key, value) = part
+                .split_once('=')
+                .ok_or(ZapError::InvalidRecurrence("rule parts must be KEY=VALUE"))?;
+            match key.to_ascii_uppercase().as_str() {
+                "FREQ" => {
+                    freq = Some(match value.to_ascii_uppercase().as_str() {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        _ => {
+                            return Err(ZapError::InvalidRecurrence(
+                                "FREQ must be DAILY, WEEKLY or MONTHLY",
+                            ));
+                        }
+                    });
+                }
+                "INTERVAL" => {
+                    interval = value
+                        .parse::<u32>()
+                        .ok()
+                        .filter(|n| (1..=MAX_INTERVAL).contains(n))
+                        .ok_or(ZapError::InvalidRecurrence(
+                            "INTERVAL must be an integer between 1 and 1000",
+                        ))?;
+                }
+                "BYDAY" => {
+                    for day in value.split(',') {
+                        by_day.push(parse_weekday(day)?);
+                    }
+                }
+                "BYMONTHDAY" => {
+                    by_month_day = Some(
+                        value
+                            .parse::<i32>()
+                            .ok()
+                            .filter(|d| *d != 0 && (-31..=31).contains(d))
+                            .ok_or(ZapError::InvalidRecurrence(
+                                "BYMONTHDAY must be between 1 and 31 or -31 and -1",
+                            ))?,
+                    );
+                }
+                "UNTIL" => {
+                    until = Some(
+                        value
+                            .get(..8)
+                            .and_then(|d| NaiveDate::parse_from_str(d, "%Y%m%d").ok())
+                            .ok_or(ZapError::InvalidRecurrence("UNTIL must be YYYYMMDD"))?,
+                    );
+                }
+                _ => return Err(ZapError::InvalidRecurrence("unsupported RRULE part")),
+            }
+        }
+
+        let freq = freq.ok_or(ZapError::InvalidRecurrence("FREQ is required"))?;
+        if !by_day.is_empty() && freq != Frequency::Weekly {
+            return Err(ZapError::InvalidRecurrence(
+                "BYDAY is only supported with FREQ=WEEKLY",
+            ));
+        }
+        if by_month_day.is_some() && freq != Frequency::Monthly {
+            return Err(ZapError::InvalidRecurrence(
+                "BYMONTHDAY is only supported with FREQ=MONTHLY",
+            ));
+        }
+
+        Ok(Self {
+            freq,
+            interval,
+            by_day,
+            by_month_day,
+            until,
+        })
+    }
+}
+
+impl RecurrenceRule {
+    /// 判断某个日期是否为规则（从 start 开始）的一次发生
+    pub fn matches(&self, start: NaiveDate, date: NaiveDate) -> bool {
+        if date < start || self.until.is_some_and(|until| date > until) {
+            return false;
+        }
+        let interval = self.interval as i64;
+
+        match self.freq {
+            Frequency::Daily => (date - start).num_days() % interval == 0,
+            Frequency::Weekly => {
+                let on_day = if self.by_day.is_empty() {
+                    date.weekday() == start.weekday()
+                } else {
+                    self.by_day.contains(&date.weekday())
+                };
+                let weeks = (week_start(date) - week_start(start)).num_days() / 7;
+                on_day && weeks % interval == 0
+            }
+            Frequency::Monthly => {
+                let months = (date.year() - start.year()) as i64 * 12 + date.month() as i64
+                    - start.month() as i64;
+                let day = self.by_month_day.unwrap_or(start.day() as i32);
+                let target = if day > 0 {
+                    day
+                } else {
+                    days_in_month(date) as i32 + day + 1
+                };
+                months % interval == 0 && date.day() as i32 == target
+            }
+        }
+    }
+
+    /// 从 from（含）开始查找下一次发生日期，规则已结束时返回 None
+    pub fn next_on_or_after(&self, start: NaiveDate, from: NaiveDate) -> Option<NaiveDate> {
+        let mut date = from.max(start);
+        // 接近日期上限时搜索到 NaiveDate::MAX 为止
+        let limit = date
+            .checked_add_signed(Duration::days(MAX_SEARCH_DAYS * self.interval as i64))
+            .unwrap_or(NaiveDate::MAX);
+
+        while date <= limit {
+            if self.until.is_some_and(|until| date > until) {
+                return None;
+            }
+            if self.matches(start, date) {
+                return Some(date);
+            }
+            date = date.succ_opt()?;
+        }
+        None
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct RecurringTask {
+    pub id: u32,
+    pub title: String,
+    pub category_id: Option<u32>,
+    pub project_id: Option<u32>,
+    pub estimate_seconds: Option<i64>,
+    pub notes: Option<String>,
+    pub billable: bool,
+    pub hourly_rate: Option<i64>,
+    /// 重复规则
+    pub rrule: String,
+    /// 规则起始日期
+    pub starts_on: String,
+    /// 下一个待生成实例的日期，规则结束后为空
+    pub next_date: Option<String>,
+    /// 是否启用
+    pub is_active: bool,
+    pub created_at: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct CreateRecurringTaskRequest {
+    pub title: String,
+    pub category_id: Option<u32>,
+    pub project_id: Option<u32>,
+    pub estimate_seconds: Option<i64>,
+    pub notes: Option<String>,
+    pub billable: Option<bool>,
+    pub hourly_rate: Option<i64>,
+    /// 重复规则，如 `FREQ=WEEKLY;BYDAY=MO,TU,WE,TH,FR`
+    pub rrule: String,
+    /// 规则起始日期（YYYY-MM-DD），缺省为今天
+    pub starts_on: Option<String>,
+}
+
+/// 周期任务汇总：统计该规则生成的全部实例
+#[derive(Debug, Serialize)]
+pub struct RecurringTaskStats {
+    pub recurring_task_id: u32,
+    /// 已生成的实例数
+    pub instance_count: i64,
+    /// 已完成的实例数
+    pub finished_count: i64,
+    /// 全部实例的累计时长（秒，已按取整规则处理）
+    pub total_seconds: i64,
+}
+
+const RECURRING_TASK_COLUMNS: &str = "SELECT id, title, category_id, project_id, estimate_seconds, notes, billable, hourly_rate, \
+     rrule, starts_on, next_date, is_active, created_at FROM recurring_tasks";
+
+pub async fn list_recurring_tasks_impl(pool: &SqlitePool) -> Result<Vec<RecurringTask>, ZapError> {
+    let tasks =
+        sqlx::query_as::<_, RecurringTask>(&format!("{RECURRING_TASK_COLUMNS} ORDER BY id"))
+            .fetch_all(pool)
+            .await?;
+
+    Ok(tasks)
+}
+
+/// 创建周期任务；若第一次发生日期不晚于今天，立即生成实例
+pub async fn create_recurring_task_impl(
+    pool: &SqlitePool,
+    req: CreateRecurringTaskRequest,
+) -> Result<RecurringTask, ZapError> {
+    let title = req.title.trim();
+    if title.is_empty() {
+        return Err(ZapError::InvalidTaskData("title cannot be empty"));
+    }
+    if req.estimate_seconds.is_some_and(|e| e < 0) {
+        return Err(ZapError::InvalidTaskData("estimate cannot be negative"));
+    }
+    if req.hourly_rate.is_some_and(|r| r < 0) {
+        return Err(ZapError::InvalidTaskData("hourly rate cannot be negative"));
+    }
+
+    let rule: RecurrenceRule = req.rrule.parse()?;
+    let today = chrono::Local::now().date_naive();
+    let starts_on = match req.starts_on {
+        Some(ref date) => parse_date(date)?,
+        None => today,
+    };
+    let next_date = rule.next_on_or_after(starts_on, starts_on);
+
+    let mut tx = pool.begin().await?;
+
+    if let Some(category_id) = req.category_id {
+        ensure_category_exists(&mut tx, category_id).await?;
+    }
+    if let Some(project_id) = req.project_id {
+        ensure_project_exists(&mut tx, project_id).await?;
+    }
+
+    let res = sqlx::query(
+        "INSERT INTO recurring_tasks (title, category_id, project_id, estimate_seconds, notes, billable, hourly_rate, rrule, starts_on, next_date)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(title)
+    .bind(req.category_id)
+    .bind(req.project_id)
+    .bind(req.estimate_seconds)
+    .bind(&req.notes)
+    .bind(req.billable.unwrap_or(false))
+    .bind(req.hourly_rate)
+    .bind(req.rrule.trim())
+    .bind(format_date(starts_on))
+    .bind(next_date.map(format_date))
+    .execute(tx.as_mut())
+    .await?;
+    let recurring_task_id = res.last_insert_rowid() as u32;
+
+    let series = get_recurring_task(&mut tx, recurring_task_id).await?;
+    materialize_due(&mut tx, &series, today).await?;
+
+    let series = get_recurring_task(&mut tx, recurring_task_id).await?;
+    tx.commit().await?;
+    Ok(series)
+}
+
+/// 暂停或恢复周期任务；恢复后由后台任务补生成最近一次到期的实例
+pub async fn set_recurring_task_active_impl(
+    pool: &SqlitePool,
+    recurring_task_id: u32,
+    is_active: bool,
+) -> Result<RecurringTask, ZapError> {
+    let res = sqlx::query(
+        "UPDATE recurring_tasks SET is_active = ?, updated_at = datetime('now') WHERE id = ?",
+    )
+    .bind(is_active)
+    .bind(recurring_task_id)
+    .execute(pool)
+    .await?;
+
+    if res.rows_affected() == 0 {
+        return Err(ZapError::RecurringTaskNotFound(recurring_task_id));
+    }
+
+    let task =
+        sqlx::query_as::<_, RecurringTask>(&format!("{RECURRING_TASK_COLUMNS} WHERE id = ?"))
+            .bind(recurring_task_id)
+            .fetch_one(pool)
+            .await?;
+
+    Ok(task)
+}
+
+/// 删除周期任务，已生成的实例保留（不再关联模板）
+pub async fn delete_recurring_task_impl(
+    pool: &SqlitePool,
+    recurring_task_id: u32,
+) -> Result<(), ZapError> {
+    let res = sqlx::query("DELETE FROM recurring_tasks WHERE id = ?")
+        .bind(recurring_task_id)
+        .execute(pool)
+        .await?;
+
+    if res.rows_affected() == 0 {
+        return Err(ZapError::RecurringTaskNotFound(recurring_task_id));
+    }
+    Ok(())
+}
+
+/// 汇总周期任务全部实例的数量与累计时长
+pub async fn get_recurring_task_stats_impl(
+    pool: &SqlitePool,
+    recurring_task_id: u32,
+) -> Result<RecurringTaskStats, ZapError> {
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM recurring_tasks WHERE id = ?")
+        .bind(recurring_task_id)
+        .fetch_one(pool)
+        .await?;
+    if count == 0 {
+        return Err(ZapError::RecurringTaskNotFound(recurring_task_id));
+    }
+
+    let entries = get_rounding_policy_impl(pool).await?.entries_source();
+
+    let (instance_count, finished_count): (i64, i64) = sqlx::query_as(
+        "SELECT COUNT(*), COALESCE(SUM(done = 2), 0) FROM tasks WHERE recurring_task_id = ?",
+    )
+    .bind(recurring_task_id)
+    .fetch_one(pool)
+    .await?;
+
+    let total_seconds: i64 = sqlx::query_scalar(&format!(
+        "SELECT COALESCE(SUM(te.duration_seconds), 0) FROM {entries} te
+         JOIN tasks t ON te.task_id = t.id
+         WHERE t.recurring_task_id = ?"
+    ))
+    .bind(recurring_task_id)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(RecurringTaskStats {
+        recurring_task_id,
+        instance_count,
+        finished_count,
+        total_seconds,
+    })
+}
+
+/// 为所有到期（下一次发生日期不晚于 today）的周期任务生成实例，返回新建的任务 id。
+///
+/// 错过的多次发生只补生成最近的一次。
+pub async fn materialize_recurring_tasks_impl(
+    pool: &SqlitePool,
+    today: NaiveDate,
+) -> Result<Vec<u32>, ZapError> {
+    let mut tx = pool.begin().await?;
+
+    let due = sqlx::query_as::<_, RecurringTask>(&format!(
+        "{RECURRING_TASK_COLUMNS} WHERE is_active = 1 AND next_date IS NOT NULL AND next_date <= ? ORDER BY id"
+    ))
+    .bind(format_date(today))
+    .fetch_all(tx.as_mut())
+    .await?;
+
+    let mut created = Vec::new();
+    for series in &due {
+        if let Some(task_id) = materialize_due(&mut tx, series, today).await? {
+            created.push(task_id);
+        }
+    }
+
+    tx.commit().await?;
+    Ok(created)
+}
+
+/// 实例完成后立即生成下一个实例（即使日期尚未到达），
+/// 前提是该周期任务没有其他未完成的实例
+pub(crate) async fn materialize_after_finish(
+    tx: &mut Transaction<'_, Sqlite>,
+    recurring_task_id: u32,
+) -> Result<(), ZapError> {
+    let Ok(series) = get_recurring_task(tx, recurring_task_id).await else {
+        return Ok(());
+    };
+    let Some(ref next_date) = series.next_date else {
+        return Ok(());
+    };
+    if !series.is_active {
+        return Ok(());
+    }
+
+    let open: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM tasks WHERE recurring_task_id = ? AND done IN (0, 1)",
+    )
+    .bind(recurring_task_id)
+    .fetch_one(tx.as_mut())
+    .await?;
+    if open > 0 {
+        return Ok(());
+    }
+
+    let rule: RecurrenceRule = series.rrule.parse()?;
+    let starts_on = parse_date(&series.starts_on)?;
+    let date = parse_date(next_date)?;
+    let following = date
+        .succ_opt()
+        .and_then(|d| rule.next_on_or_after(starts_on, d));
+
+    create_instance(tx, &series, date, following).await?;
+    Ok(())
+}
+
+/// 若周期任务已到期，生成最近一次发生日期的实例并推进 next_date
+async fn materialize_due(
+    tx: &mut Transaction<'_, Sqlite>,
+    series: &RecurringTask,
+    today: NaiveDate,
+) -> Result<Option<u32>, ZapError> {
+    let Some(ref next_date) = series.next_date else {
+        return Ok(None);
+    };
+    let mut date = parse_date(next_date)?;
+    if !series.is_active || date > today {
+        return Ok(None);
+    }
+
+    let rule: RecurrenceRule = series.rrule.parse()?;
+    let starts_on = parse_date(&series.starts_on)?;
+
+    // 跳过错过的发生日期，只保留不晚于今天的最近一次
+    let following = loop {
+        match date
+            .succ_opt()
+            .and_then(|d| rule.next_on_or_after(starts_on, d))
+        {
+            Some(d) if d <= today => date = d,
+            following => break following,
+        }
+    };
+
+    create_instance(tx, series, date, following).await.map(Some)
+}
+
+async fn create_instance(
+    tx: &mut Transaction<'_, Sqlite>,
+    series: &RecurringTask,
+    date: NaiveDate,
+    following: Option<NaiveDate>,
+) -> Result<u32, ZapError> {
+    let date = format_date(date);
+    let res = sqlx::query(
+        "INSERT INTO tasks (title, done, category_id, project_id, estimate_seconds, notes, billable, hourly_rate, due_date, scheduled_date, recurring_task_id, position)
+         VALUES (?, 0, ?, ?, ?, ?, ?, ?, ?, ?, ?, (SELECT COALESCE(MAX(position), 0) + 1 FROM tasks))",
+    )
+    .bind(&series.title)
+    .bind(series.category_id)
+    .bind(series.project_id)
+    .bind(series.estimate_seconds)
+    .bind(&series.notes)
+    .bind(series.billable)
+    .bind(series.hourly_rate)
+    .bind(&date)
+    .bind(&date)
+    .bind(series.id)
+    .execute(tx.as_mut())
+    .await?;
+
+    sqlx::query(
+        "UPDATE recurring_tasks SET next_date = ?, updated_at = datetime('now') WHERE id = ?",
+    )
+    .bind(following.map(format_date))
+    .bind(series.id)
+    .execute(tx.as_mut())
+    .await?;
+
+    Ok(res.last_insert_rowid() as u32)
+}
+
+async fn get_recurring_task(
+    tx: &mut Transaction<'_, Sqlite>,
+    recurring_task_id: u32,
+) -> Result<RecurringTask, ZapError> {
+    sqlx::query_as::<_, RecurringTask>(&format!("{RECURRING_TASK_COLUMNS} WHERE id = ?"))
+        .bind(recurring_task_id)
+        .fetch_optional(tx.as_mut())
+        .await?
+        .ok_or(ZapError::RecurringTaskNotFound(recurring_task_id))
+}
+
+fn parse_weekday(day: &str) -> Result<Weekday, ZapError> {
+    match day.trim().to_ascii_uppercase().as_str() {
+        "MO" => Ok(Weekday::Mon),
+        "TU" => Ok(Weekday::Tue),
+        "WE" => Ok(Weekday::Wed),
+        "TH" => Ok(Weekday::Thu),
+        "FR" => Ok(Weekday::Fri),
+        "SA" => Ok(Weekday::Sat),
+        "SU" => Ok(Weekday::Sun),
+        _ => Err(ZapError::InvalidRecurrence(
+            "BYDAY must list days as MO,TU,WE,TH,FR,SA,SU",
+        )),
+    }
+}
+
+fn parse_date(date: &str) -> Result<NaiveDate, ZapError> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map_err(|_| ZapError::InvalidTaskData("date must be in YYYY-MM-DD format"))
+}
+
+fn format_date(date: NaiveDate) -> String {
+    date.format("%Y-%m-%d").to_string()
+}
+
+/// 所在周的周一
+fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+fn days_in_month(date: NaiveDate) -> u32 {
+    let (year, month) = if date.month() == 12 {
+        (date.year() + 1, 1)
+    } else {
+        (date.year(), date.month() + 1)
+    };
+    NaiveDate::from_ymd_opt(year, month, 1)
+        .and_then(|d| d.pred_opt())
+        .map_or(31, |d| d.day())
+}
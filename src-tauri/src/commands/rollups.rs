@@ -0,0 +1,101 @@
+use serde::Serialize;
+use sqlx::{FromRow, SqlitePool};
+
+use crate::error::ZapError;
+
+/// 预汇总表与原始计时记录不一致的分组
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct RollupMismatch {
+    pub task_id: u32,
+    /// 本地日期 YYYY-MM-DD
+    pub day: String,
+    /// 记录的可计费覆盖值
+    pub billable: Option<bool>,
+    /// 按原始记录计算的时长
+    pub expected_seconds: i64,
+    /// 预汇总表中的时长
+    pub actual_seconds: i64,
+    pub expected_entries: i64,
+    pub actual_entries: i64,
+}
+
+/// 一致性检查结果
+#[derive(Debug, Serialize)]
+pub struct RollupCheckReport {
+    /// 检查的分组数（任务、日期、可计费覆盖值）
+    pub checked_groups: i64,
+    /// 不一致的分组，为空表示一致
+    pub mismatches: Vec<RollupMismatch>,
+}
+
+/// 按原始记录分组汇总已结束的计时，与触发器的计算口径相同
+const ROLLUP_SELECT: &str = r#"
+    SELECT task_id, date(started_at, 'localtime') AS day, billable,
+           SUM(duration_seconds) AS seconds, COUNT(*) AS entry_count, MAX(ended_at) AS last_ended_at
+    FROM time_entries
+    WHERE ended_at IS NOT NULL
+    GROUP BY task_id, date(started_at, 'localtime'), billable
+"#;
+
+/// 由原始计时记录重建 task_day_totals（按当前时区的本地日期），返回写入的分组数
+pub async fn rebuild_task_day_totals_impl(pool: &SqlitePool) -> Result<u64, ZapError> {
+    let mut tx = pool.begin().await?;
+
+    sqlx::query("DELETE FROM task_day_totals")
+        .execute(tx.as_mut())
+        .await?;
+    let res = sqlx::query(&format!(
+        "INSERT INTO task_day_totals (task_id, day, billable, seconds, entry_count, last_ended_at) {ROLLUP_SELECT}"
+    ))
+    .execute(tx.as_mut())
+    .await?;
+
+    tx.commit().await?;
+    Ok(res.rows_affected())
+}
+
+/// 将 task_day_totals 与原始计时记录逐组比对
+pub async fn check_task_day_totals_impl(pool: &SqlitePool) -> Result<RollupCheckReport, ZapError> {
+    let groups = format!(
+        r#"
+        WITH expected AS ({ROLLUP_SELECT}),
+        actual AS (
+            SELECT task_id, day, billable, SUM(seconds) AS seconds, SUM(entry_count) AS entry_count
+            FROM task_day_totals
+            GROUP BY task_id, day, billable
+        ),
+        keys AS (
+            SELECT task_id, day, billable FROM expected
+            UNION
+            SELECT task_id, day, billable FROM actual
+        )
+        SELECT k.task_id, k.day, k.billable,
+               COALESCE(e.seconds, 0) AS expected_seconds,
+               COALESCE(a.seconds, 0) AS actual_seconds,
+               COALESCE(e.entry_count, 0) AS expected_entries,
+               COALESCE(a.entry_count, 0) AS actual_entries
+        FROM keys k
+        LEFT JOIN expected e
+            ON e.task_id = k.task_id AND e.day = k.day AND e.billable IS k.billable
+        LEFT JOIN actual a
+            ON a.task_id = k.task_id AND a.day = k.day AND a.billable IS k.billable
+        "#
+    );
+
+    let checked_groups: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM ({groups})"))
+        .fetch_one(pool)
+        .await?;
+
+    let mismatches = sqlx::query_as::<_, RollupMismatch>(&format!(
+        "SELECT * FROM ({groups}) \
+         WHERE expected_seconds != actual_seconds OR expected_entries != actual_entries \
+         ORDER BY day, task_id"
+    ))
+    .fetch_all(pool)
+    .await?;
+
+    Ok(RollupCheckReport {
+        checked_groups,
+        mismatches,
+    })
+}
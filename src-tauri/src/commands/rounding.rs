@@ -0,0 +1,175 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+
+use crate::{
+    commands::settings::{get_setting, set_setting},
+    error::ZapError,
+};
+
+/// 取整规则在 settings 表中的键
+const ROUNDING_POLICY_KEY: &str = "rounding_policy";
+
+/// 取整数据源中原样透传的 time_entries 列（duration_seconds 除外）
+const PASSTHROUGH_COLUMNS: &str = "id, task_id, started_at, ended_at, note, billable";
+
+/// 允许的取整单位（分钟）
+const ALLOWED_UNIT_MINUTES: [u32; 5] = [1, 5, 6, 15, 30];
+
+/// 取整方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum RoundingMode {
+    /// 不取整，使用原始时长
+    #[default]
+    None,
+    /// 向上取整
+    Up,
+    /// 向下取整
+    Down,
+    /// 四舍五入（恰好一半时向上）
+    Nearest,
+}
+
+/// 取整作用范围
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum RoundingScope {
+    /// 每条计时记录单独取整
+    #[default]
+    Entry,
+    /// 同一任务同一天（本地日期）的时长合计后再取整
+    DayTask,
+}
+
+/// 时长取整规则（仅作用于报表，time_entries 中的原始数据不会被修改）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoundingPolicy {
+    /// 取整方式
+    pub mode: RoundingMode,
+    /// 取整单位（分钟）：1、5、6、15 或 30
+    pub unit_minutes: u32,
+    /// 取整作用范围
+    pub scope: RoundingScope,
+}
+
+impl Default for RoundingPolicy {
+    fn default() -> Self {
+        Self {
+            mode: RoundingMode::None,
+            unit_minutes: 1,
+            scope: RoundingScope::Entry,
+        }
+    }
+}
+
+impl RoundingPolicy {
+    /// 是否为不取整
+    pub fn is_none(&self) -> bool {
+        self.mode == RoundingMode::None
+    }
+
+    fn unit_seconds(&self) -> i64 {
+        self.unit_minutes as i64 * 60
+    }
+
+    fn validate(&self) -> Result<(), ZapError> {
+        if !ALLOWED_UNIT_MINUTES.contains(&self.unit_minutes) {
+            return Err(ZapError::InvalidSetting(
+                "rounding unit must be 1, 5, 6, 15 or 30 minutes",
+            ));
+        }
+        Ok(())
+    }
+
+    /// 对一段时长（秒）取整
+    pub fn round(&self, seconds: i64) -> i64 {
+        let unit = self.unit_seconds();
+        if seconds <= 0 || unit <= 0 {
+            return seconds;
+        }
+
+        match self.mode {
+            RoundingMode::None => seconds,
+            RoundingMode::Up => (seconds + unit - 1) / unit * unit,
+            RoundingMode::Down => seconds / unit * unit,
+            RoundingMode::Nearest => (seconds + unit / 2) / unit * unit,
+        }
+    }
+
+    /// 对同一任务的多条记录（所属日期, 时长）按规则取整后求和
+    pub fn round_task_total<'a>(&self, entries: impl IntoIterator<Item = (&'a str, i64)>) -> i64 {
+        match self.scope {
+            RoundingScope::Entry => entries.into_iter().map(|(_, s)| self.round(s)).sum(),
+            RoundingScope::DayTask => {
+                let mut per_day: BTreeMap<&str, i64> = BTreeMap::new();
+                for (day, seconds) in entries {
+                    *per_day.entry(day).or_default() += seconds;
+                }
+                per_day.into_values().map(|s| self.round(s)).sum()
+            }
+        }
+    }
+
+    /// 生成对 SQL 整数表达式取整的 SQL 片段
+    fn sql_round(&self, expr: &str) -> String {
+        let unit = self.unit_seconds();
+        match self.mode {
+            RoundingMode::None => expr.to_string(),
+            RoundingMode::Up => format!("((({expr}) + {}) / {unit} * {unit})", unit - 1),
+            RoundingMode::Down => format!("(({expr}) / {unit} * {unit})"),
+            RoundingMode::Nearest => format!("((({expr}) + {}) / {unit} * {unit})", unit / 2),
+        }
+    }
+
+    /// 生成可替代 `time_entries` 的数据源，列与原表一致，但已结束记录的
+    /// `duration_seconds` 已按规则取整；进行中的记录保持不变。
+    ///
+    /// 按日-任务取整时，差额记在该组的第一条记录上，因此按任务、分类或日期
+    /// 汇总的结果与"先合计再取整"一致，记录条数也不受影响。
+    pub fn entries_source(&self) -> String {
+        if self.is_none() {
+            return "time_entries".to_string();
+        }
+
+        match self.scope {
+            RoundingScope::Entry => format!(
+                "(SELECT {PASSTHROUGH_COLUMNS}, \
+                 CASE WHEN ended_at IS NULL THEN duration_seconds ELSE {} END AS duration_seconds \
+                 FROM time_entries)",
+                self.sql_round("duration_seconds")
+            ),
+            RoundingScope::DayTask => {
+                let partition =
+                    "PARTITION BY task_id, date(started_at, 'localtime'), ended_at IS NULL";
+                let group_sum = format!("SUM(duration_seconds) OVER ({partition})");
+                format!(
+                    "(SELECT {PASSTHROUGH_COLUMNS}, \
+                     duration_seconds + CASE WHEN ended_at IS NOT NULL \
+                     AND ROW_NUMBER() OVER ({partition} ORDER BY id) = 1 \
+                     THEN {} - {group_sum} ELSE 0 END AS duration_seconds \
+                     FROM time_entries)",
+                    self.sql_round(&group_sum)
+                )
+            }
+        }
+    }
+}
+
+/// 获取当前取整规则，未配置时为不取整
+pub async fn get_rounding_policy_impl(pool: &SqlitePool) -> Result<RoundingPolicy, ZapError> {
+    Ok(get_setting(pool, ROUNDING_POLICY_KEY)
+        .await?
+        .unwrap_or_default())
+}
+
+/// 保存取整规则
+pub async fn set_rounding_policy_impl(
+    pool: &SqlitePool,
+    policy: RoundingPolicy,
+) -> Result<RoundingPolicy, ZapError> {
+    policy.validate()?;
+    set_setting(pool, ROUNDING_POLICY_KEY, &policy).await?;
+    Ok(policy)
+}
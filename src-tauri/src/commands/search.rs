@@ -0,0 +1,238 @@
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+
+use crate::error::ZapError;
+
+/// 默认返回的最大结果数
+const DEFAULT_SEARCH_LIMIT: u32 = 50;
+
+/// 搜索结果来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(rename_all = "snake_case")]
+pub enum SearchHitKind {
+    /// 任务标题或备注
+    Task,
+    /// 计时记录备注
+    Session,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SearchRequest {
+    /// 搜索词：空格分隔的词均需命中（按子串匹配，支持中文），`词*` 为前缀匹配，`"多个 词"` 为短语匹配
+    pub query: String,
+    /// 最大结果数，默认 50
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct SearchHit {
+    pub kind: SearchHitKind,
+    pub task_id: u32,
+    /// 命中计时记录时为记录id
+    pub time_entry_id: Option<u32>,
+    /// 任务标题
+    pub title: String,
+    /// 命中片段，匹配词以 « » 标出
+    pub snippet: String,
+    /// 相关度，越大越相关
+    pub score: f64,
+}
+
+/// trigram 分词下可走索引的最短词长，更短的词（如两个汉字）用 LIKE 匹配
+const MIN_TRIGRAM_CHARS: usize = 3;
+
+/// 在任务标题、任务备注和计时记录备注中全文搜索，按相关度排序
+pub async fn search_impl(
+    pool: &SqlitePool,
+    req: SearchRequest,
+) -> Result<Vec<SearchHit>, ZapError> {
+    let Some(terms) = parse_query(&req.query) else {
+        return Ok(Vec::new());
+    };
+
+    let mut binds: Vec<String> = Vec::new();
+    let tasks = terms.source_sql(&TASK_SOURCE, &mut binds);
+    let sessions = terms.source_sql(&SESSION_SOURCE, &mut binds);
+    let sql = format!(
+        r#"
+        SELECT kind, task_id, time_entry_id, title, snippet, score FROM (
+            SELECT 'task' AS kind, t.id AS task_id, NULL AS time_entry_id, t.title, {tasks}
+            UNION ALL
+            SELECT 'session' AS kind, te.task_id, te.id AS time_entry_id, t.title, {sessions}
+        )
+        ORDER BY score DESC, task_id DESC
+        LIMIT ?
+    "#
+    );
+
+    let mut query = sqlx::query_as::<_, SearchHit>(&sql);
+    for bind in &binds {
+        query = query.bind(bind);
+    }
+    let hits = query
+        .bind(req.limit.unwrap_or(DEFAULT_SEARCH_LIMIT) as i64)
+        .fetch_all(pool)
+        .await?;
+
+    Ok(hits)
+}
+
+/// 一个搜索来源：FTS5 索引表及其对应的原表
+struct SearchSource {
+    fts_table: &'static str,
+    snippet: &'static str,
+    score: &'static str,
+    /// 走索引时的 FROM 子句
+    fts_from: &'static str,
+    /// 只有短词、直接扫描原表时的 FROM 子句
+    table_from: &'static str,
+    /// 参与匹配的列，按片段优先级排列
+    columns: &'static [&'static str],
+}
+
+// 标题的权重高于备注
+const TASK_SOURCE: SearchSource = SearchSource {
+    fts_table: "tasks_fts",
+    snippet: "snippet(tasks_fts, -1, '«', '»', '…', 32)",
+    score: "-bm25(tasks_fts, 10.0, 1.0)",
+    fts_from: "tasks_fts JOIN tasks t ON t.id = tasks_fts.rowid",
+    table_from: "tasks t",
+    columns: &["t.title", "IFNULL(t.notes, '')"],
+};
+
+const SESSION_SOURCE: SearchSource = SearchSource {
+    fts_table: "time_entries_fts",
+    snippet: "snippet(time_entries_fts, 0, '«', '»', '…', 32)",
+    score: "-bm25(time_entries_fts)",
+    fts_from: "time_entries_fts
+               JOIN time_entries te ON te.id = time_entries_fts.rowid
+               JOIN tasks t ON t.id = te.task_id",
+    table_from: "time_entries te JOIN tasks t ON t.id = te.task_id",
+    columns: &["IFNULL(te.note, '')"],
+};
+
+/// 解析后的搜索词：足够长的词合成 FTS5 查询，过短的词逐个用 LIKE 匹配
+struct SearchTerms {
+    fts: Option<String>,
+    short: Vec<String>,
+}
+
+impl SearchTerms {
+    /// 生成一个来源的 `snippet, score FROM … WHERE …` 片段，参数按出现顺序追加到 binds。
+    /// 有 FTS5 词时走索引；只有短词时扫描原表，片段为标出首个短词的原文
+    fn source_sql(&self, source: &SearchSource, binds: &mut Vec<String>) -> String {
+        let SearchSource {
+            fts_table,
+            snippet,
+            score,
+            fts_from,
+            table_from,
+            columns,
+        } = source;
+
+        let mut sql = match &self.fts {
+            Some(fts) => {
+                binds.push(fts.clone());
+                format!(
+                    "{snippet} AS snippet, {score} AS score FROM {fts_from} WHERE {fts_table} MATCH ?"
+                )
+            }
+            None => {
+                // 取第一个包含该词的列作为片段
+                let term = &self.short[0];
+                let text = columns
+                    .iter()
+                    .map(|column| {
+                        binds.push(term.clone());
+                        format!("CASE WHEN instr({column}, ?) > 0 THEN {column} END")
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let text = format!("COALESCE({text}, {})", columns[0]);
+                binds.extend([term.clone(), format!("«{term}»")]);
+                format!("replace({text}, ?, ?) AS snippet, 0.0 AS score FROM {table_from} WHERE 1")
+            }
+        };
+
+        for term in &self.short {
+            let pattern = format!("%{}%", escape_like(term));
+            let any = columns
+                .iter()
+                .map(|column| {
+                    binds.push(pattern.clone());
+                    format!("{column} LIKE ? ESCAPE '\\'")
+                })
+                .collect::<Vec<_>>()
+                .join(" OR ");
+            sql += &format!(" AND ({any})");
+        }
+        sql
+    }
+}
+
+fn escape_like(term: &str) -> String {
+    term.replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+/// 将用户输入转换为安全的搜索词：每个词或短语都加引号，避免特殊字符被当作
+/// FTS5 语法；保留 `*` 前缀匹配。不足 3 个字符的词无法走 trigram 索引，单独
+/// 留给 LIKE 匹配。没有可搜索的词时返回 None
+fn parse_query(input: &str) -> Option<SearchTerms> {
+    let mut fts_terms = Vec::new();
+    let mut short = Vec::new();
+    let mut chars = input.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+
+        let mut text = String::new();
+        if c == '"' {
+            chars.next();
+            for c in chars.by_ref() {
+                if c == '"' {
+                    break;
+                }
+                text.push(c);
+            }
+        } else {
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() || c == '"' {
+                    break;
+                }
+                text.push(c);
+                chars.next();
+            }
+        }
+
+        let mut prefix = chars.next_if_eq(&'*').is_some();
+        if let Some(stripped) = text.strip_suffix('*') {
+            text = stripped.to_string();
+            prefix = true;
+        }
+
+        if !text.chars().any(char::is_alphanumeric) {
+            continue;
+        }
+        if text.chars().count() < MIN_TRIGRAM_CHARS {
+            // trigram 本就是子串匹配，短词的前缀标记无需额外处理
+            short.push(text);
+        } else {
+            let term = format!("\"{}\"", text.replace('"', "\"\""));
+            fts_terms.push(if prefix { term + "*" } else { term });
+        }
+    }
+
+    if fts_terms.is_empty() && short.is_empty() {
+        return None;
+    }
+    Some(SearchTerms {
+        fts: (!fts_terms.is_empty()).then(|| fts_terms.join(" ")),
+        short,
+    })
+}
@@ -0,0 +1,38 @@
+use serde::{Serialize, de::DeserializeOwned};
+use sqlx::Sqlite;
+
+use crate::error::ZapError;
+
+/// 读取配置项（JSON 反序列化），未设置时返回 None
+pub async fn get_setting<'e, E, T>(executor: E, key: &str) -> Result<Option<T>, ZapError>
+where
+    E: sqlx::Executor<'e, Database = Sqlite>,
+    T: DeserializeOwned,
+{
+    let value: Option<String> = sqlx::query_scalar("SELECT value FROM settings WHERE key = ?")
+        .bind(key)
+        .fetch_optional(executor)
+        .await?;
+
+    Ok(value.map(|v| serde_json::from_str(&v)).transpose()?)
+}
+
+/// 写入配置项（JSON 序列化），已存在则覆盖
+pub async fn set_setting<'e, E, T>(executor: E, key: &str, value: &T) -> Result<(), ZapError>
+where
+    E: sqlx::Executor<'e, Database = Sqlite>,
+    T: Serialize,
+{
+    let value = serde_json::to_string(value)?;
+
+    sqlx::query(
+        "INSERT INTO settings (key, value) VALUES (?, ?)
+         ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = datetime('now')",
+    )
+    .bind(key)
+    .bind(value)
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
@@ -1,13 +1,35 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use chrono::{
+    DateTime, Datelike, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Timelike, Utc,
+};
 use serde::{Deserialize, Serialize};
 use sqlx::{FromRow, SqlitePool};
 
-use crate::error::ZapError;
+use crate::{
+    commands::{
+        billing::{BILLABLE_SQL, HOURLY_RATE_SQL, earned_amount, get_currency_impl},
+        rounding::get_rounding_policy_impl,
+        time_entries::get_focus_policy_impl,
+    },
+    error::ZapError,
+};
+
+/// 数据库中时间的存储格式（UTC）
+pub(crate) const DATETIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// 无分类、无标签时使用的颜色
+const FALLBACK_COLOR: &str = "#9CA3AF";
 
 /// 日期范围查询参数
 #[derive(Debug, Serialize, Deserialize, Default)]
 pub struct StatsDateQuery {
-    pub start_date: Option<String>, // YYYY-MM-DD 格式
-    pub end_date: Option<String>,   // YYYY-MM-DD 格式
+    pub start_date: Option<String>, // YYYY-MM-DD 格式（本地日期）
+    pub end_date: Option<String>,   // YYYY-MM-DD 格式（本地日期）
+    pub client_id: Option<u32>,     // 仅统计该客户下的任务
+    pub project_id: Option<u32>,    // 仅统计该项目下的任务
+    #[serde(default)]
+    pub compare: bool, // 同时返回与上一周期的对比
 }
 
 /// 分类统计项
@@ -17,6 +39,51 @@ pub struct CategoryStat {
     pub color: String,
     pub seconds: i64,
     pub percentage: f64,
+    pub billable_seconds: i64,
+    pub earned_amount: i64, // 最小货币单位
+}
+
+/// 客户统计项
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClientStat {
+    pub client_id: Option<u32>,
+    pub name: String,
+    pub seconds: i64,
+    pub percentage: f64,
+}
+
+/// 项目统计项
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectStat {
+    pub project_id: Option<u32>,
+    pub name: String,
+    pub client_name: Option<String>,
+    pub seconds: i64,
+    pub percentage: f64,
+}
+
+/// 标签统计项
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagStat {
+    pub tag_id: Option<u32>,
+    pub name: String,
+    pub color: String,
+    pub seconds: i64,
+    pub percentage: f64,
+    pub task_count: i64,
+}
+
+/// 分类预估准确度
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EstimateAccuracyStat {
+    pub category_id: Option<u32>,
+    pub name: String,
+    pub color: String,
+    pub task_count: i64,
+    pub estimate_seconds: i64,
+    pub actual_seconds: i64,
+    /// 实际 / 预估，大于 1 表示低估
+    pub ratio: f64,
 }
 
 /// 每日统计项（周视图）
@@ -46,528 +113,1582 @@ pub struct TaskStat {
     pub category_color: Option<String>,
     pub seconds: i64,
     pub last_time: Option<String>, // 仅 Today 视图使用
+    pub billable_seconds: i64,
+    pub earned_amount: i64, // 最小货币单位
 }
 
 /// 今日统计响应
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TodayStatsResponse {
     pub total_seconds: i64,
-    pub focused_seconds: i64,
+    pub billable_seconds: i64,
+    pub earned_amount: i64, // 最小货币单位
+    pub currency: String,
+    pub focused_seconds: i64, // 专注会话时长（取整后，不超过 total_seconds），见 FocusPolicy
     pub sessions_count: i64,
     pub categories: Vec<CategoryStat>,
+    pub clients: Vec<ClientStat>,
+    pub projects: Vec<ProjectStat>,
     pub detailed_report: Vec<TaskStat>,
+    /// 请求对比时为与上一周期的对比
+    pub comparison: Option<PeriodComparison>,
 }
 
 /// 本周统计响应
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WeekStatsResponse {
     pub total_seconds: i64,
+    pub billable_seconds: i64,
+    pub earned_amount: i64, // 最小货币单位
+    pub currency: String,
+    pub focused_seconds: i64,
     pub daily_average_seconds: i64,
     pub sessions_count: i64,
     pub daily_breakdown: Vec<DailyStat>,
     pub categories: Vec<CategoryStat>,
+    pub clients: Vec<ClientStat>,
+    pub projects: Vec<ProjectStat>,
+    /// 请求对比时为与上一周期的对比
+    pub comparison: Option<PeriodComparison>,
 }
 
 /// 本月统计响应
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MonthStatsResponse {
     pub total_seconds: i64,
+    pub billable_seconds: i64,
+    pub earned_amount: i64, // 最小货币单位
+    pub currency: String,
+    pub focused_seconds: i64,
     pub daily_average_seconds: i64,
     pub active_days: i64,
     pub monthly_overview: Vec<MonthlyDailyStat>,
     pub categories: Vec<CategoryStat>,
+    pub clients: Vec<ClientStat>,
+    pub projects: Vec<ProjectStat>,
     pub top_tasks: Vec<TaskStat>,
+    /// 请求对比时为与上一周期的对比
+    pub comparison: Option<PeriodComparison>,
 }
 
-/// 获取今日统计
-pub async fn get_today_stats_impl(
-    pool: &SqlitePool,
-    query: StatsDateQuery,
-) -> Result<TodayStatsResponse, ZapError> {
-    let date_filter = if let Some(start) = &query.start_date {
-        format!("date('{}')", start)
-    } else {
-        "date('now')".to_string()
-    };
+/// 年视图每日热力项
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct YearDayStat {
+    pub date: String,
+    pub seconds: i64,
+    /// 0 = 无记录，1-4 按有记录日期的四分位数分档
+    pub level: i32,
+}
 
-    // 总时间
-    let total_seconds: i64 = sqlx::query_scalar(&format!(
-        r#"
-        SELECT COALESCE(SUM(duration_seconds), 0) +
-               COALESCE((SELECT strftime('%s', 'now') - strftime('%s', started_at)
-                        FROM time_entries WHERE ended_at IS NULL AND date(started_at) = {}), 0)
-        FROM time_entries
-        WHERE date(started_at) = {} AND ended_at IS NOT NULL
-    "#,
-        date_filter, date_filter
-    ))
-    .fetch_one(pool)
-    .await?;
+/// 年视图每月合计
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonthTotalStat {
+    /// YYYY-MM
+    pub month: String,
+    pub seconds: i64,
+    pub active_days: i64,
+}
 
-    // 专注时间
-    let focused_seconds: i64 = sqlx::query_scalar(&format!(
-        r#"
-        SELECT COALESCE(SUM(duration_seconds), 0) +
-               COALESCE((SELECT strftime('%s', 'now') - strftime('%s', te.started_at)
-                        FROM time_entries te
-                        JOIN tasks t ON te.task_id = t.id
-                        WHERE te.ended_at IS NULL AND t.done = 1 AND date(te.started_at) = {}), 0)
-        FROM time_entries te
-        JOIN tasks t ON te.task_id = t.id
-        WHERE t.done = 1 AND date(te.started_at) = {}
-    "#,
-        date_filter, date_filter
-    ))
-    .fetch_one(pool)
-    .await?;
+/// 年度统计响应
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct YearStatsResponse {
+    pub start_date: String,
+    pub end_date: String,
+    pub total_seconds: i64,
+    pub billable_seconds: i64,
+    pub earned_amount: i64, // 最小货币单位
+    pub currency: String,
+    pub focused_seconds: i64,
+    pub daily_average_seconds: i64,
+    pub active_days: i64,
+    pub days: Vec<YearDayStat>,
+    pub months: Vec<MonthTotalStat>,
+    pub categories: Vec<CategoryStat>,
+    /// 请求对比时为与上一周期的对比
+    pub comparison: Option<PeriodComparison>,
+}
 
-    // 会话数
-    let sessions_count: i64 = sqlx::query_scalar(&format!(
-        "SELECT COUNT(*) FROM time_entries WHERE date(started_at) = {}",
-        date_filter
-    ))
-    .fetch_one(pool)
-    .await?;
+/// 星期 × 小时的时长矩阵（秒），matrix[星期][小时]，星期 0 = 周日
+pub type HourMatrix = [[i64; 24]; 7];
 
-    // 分类统计
-    let categories: Vec<CategoryStat> = sqlx::query_as::<_, CategoryStatSql>(&format!(
-        r#"
-        SELECT COALESCE(c.name, 'Other') AS name, COALESCE(c.color, '#9CA3AF') AS color,
-               COALESCE(SUM(te.duration_seconds), 0) AS seconds
-        FROM time_entries te
-        LEFT JOIN tasks t ON te.task_id = t.id
-        LEFT JOIN categories c ON t.category_id = c.id
-        WHERE date(te.started_at) = {}
-        GROUP BY c.id
-        ORDER BY seconds DESC
-    "#,
-        date_filter
-    ))
-    .fetch_all(pool)
-    .await?
-    .into_iter()
-    .map(|c| {
-        let total = if total_seconds > 0 {
-            total_seconds as f64
+/// 高峰时段摘要
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PeakHours {
+    /// 时长最多的格子：星期（0 = 周日）与小时（0-23）
+    pub weekday: u32,
+    pub hour: u32,
+    pub seconds: i64,
+    /// 不分星期合计时长最多的小时
+    pub busiest_hour: u32,
+    pub busiest_hour_seconds: i64,
+    /// 时长最多的星期
+    pub busiest_weekday: u32,
+    pub busiest_weekday_seconds: i64,
+}
+
+/// 星期 × 小时的时长分布（本地时间）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HourlyDistribution {
+    pub total_seconds: i64,
+    pub matrix: HourMatrix,
+    pub weekday_totals: [i64; 7],
+    pub hour_totals: [i64; 24],
+    /// 没有计时记录时为空
+    pub peak: Option<PeakHours>,
+}
+
+/// 单个分类的时长分布
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategoryHourlyDistribution {
+    pub category_id: Option<u32>,
+    pub name: String,
+    pub color: String,
+    pub distribution: HourlyDistribution,
+}
+
+/// 工作时段分布响应（打卡图）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HourlyStatsResponse {
+    pub start_date: String,
+    pub end_date: String,
+    pub overall: HourlyDistribution,
+    /// 按总时长降序
+    pub categories: Vec<CategoryHourlyDistribution>,
+}
+
+/// 标签统计响应（一个任务有多个标签时，其时间计入每个标签，因此占比之和可能超过 100%）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagStatsResponse {
+    pub total_seconds: i64,
+    pub tags: Vec<TagStat>,
+}
+
+/// 预估准确度报告：统计期间内完成且设置了预估的任务
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EstimateAccuracyResponse {
+    pub task_count: i64,
+    pub estimate_seconds: i64,
+    pub actual_seconds: i64,
+    pub ratio: f64,
+    pub categories: Vec<EstimateAccuracyStat>,
+}
+
+/// 统计时间线的粒度
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum StatsGranularity {
+    #[default]
+    Day,
+    /// 自然周（周一至周日）
+    Week,
+    /// 自然月
+    Month,
+}
+
+/// 统计分组维度
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StatsGroupBy {
+    Category,
+    Client,
+    Project,
+    Task,
+    /// 一个任务有多个标签时，其时间计入每个标签，因此占比之和可能超过 100%
+    Tag,
+}
+
+/// 时间线上的一段
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatsBucket {
+    /// 起始日期 YYYY-MM-DD（已裁剪到统计范围内）
+    pub start_date: String,
+    /// 结束日期 YYYY-MM-DD（含）
+    pub end_date: String,
+    pub seconds: i64,
+    pub billable_seconds: i64,
+    pub focused_seconds: i64,
+    pub sessions_count: i64,
+    pub percentage: f64,
+}
+
+/// 分组统计项
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatsGroupItem {
+    /// 分组对象 id，未归类（无分类、无客户等）时为空
+    pub id: Option<u32>,
+    pub name: String,
+    /// 分类、标签的颜色；任务为所属分类的颜色
+    pub color: Option<String>,
+    /// 上级名称：项目为客户名，任务为分类名
+    pub parent_name: Option<String>,
+    pub seconds: i64,
+    pub percentage: f64,
+    pub billable_seconds: i64,
+    pub earned_amount: i64, // 最小货币单位
+    pub task_count: i64,
+    /// 最后一次结束计时的时间
+    pub last_ended_at: Option<String>,
+}
+
+/// 单个维度的分组结果，按时长降序
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatsGroup {
+    pub group_by: StatsGroupBy,
+    pub items: Vec<StatsGroupItem>,
+}
+
+/// 与上一周期相比的时长变化
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatsDelta {
+    /// 分类或任务 id，总计时为空
+    pub id: Option<u32>,
+    pub name: String,
+    pub current_seconds: i64,
+    pub previous_seconds: i64,
+    pub delta_seconds: i64,
+    /// 变化百分比（保留一位小数），上一周期为 0 时为空
+    pub percent_change: Option<f64>,
+}
+
+impl StatsDelta {
+    fn new(id: Option<u32>, name: String, current_seconds: i64, previous_seconds: i64) -> Self {
+        let percent_change = (previous_seconds > 0).then(|| {
+            ((current_seconds - previous_seconds) as f64 / previous_seconds as f64 * 1000.0).round()
+                / 10.0
+        });
+        Self {
+            id,
+            name,
+            current_seconds,
+            previous_seconds,
+            delta_seconds: current_seconds - previous_seconds,
+            percent_change,
+        }
+    }
+}
+
+/// 上一周期对比
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PeriodComparison {
+    /// 上一周期的起止日期
+    pub start_date: String,
+    pub end_date: String,
+    pub total: StatsDelta,
+    /// 两个周期中出现过的所有分类，按本期时长降序
+    pub categories: Vec<StatsDelta>,
+    /// 本期前 10 个任务
+    pub top_tasks: Vec<StatsDelta>,
+}
+
+/// 通用统计结果：总计、时间线和各分组都来自同一组计时区间，各分组（标签除外）之和等于总时长
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatsReport {
+    pub start_date: String,
+    pub end_date: String,
+    /// 范围内的天数
+    pub days_count: i64,
+    pub total_seconds: i64,
+    pub billable_seconds: i64,
+    pub earned_amount: i64, // 最小货币单位
+    pub currency: String,
+    pub focused_seconds: i64,
+    pub sessions_count: i64,
+    /// 有计时记录的天数
+    pub active_days: i64,
+    pub timeline: Vec<StatsBucket>,
+    pub groups: Vec<StatsGroup>,
+    /// 请求对比时为与上一周期的对比
+    pub comparison: Option<PeriodComparison>,
+}
+
+impl StatsGranularity {
+    /// 按范围长度选择粒度：31 天以内按天，26 周以内按周，更长按月
+    pub fn for_range(start: NaiveDate, end: NaiveDate) -> Self {
+        let days = (end - start).num_days() + 1;
+        if days <= 31 {
+            Self::Day
+        } else if days <= 182 {
+            Self::Week
         } else {
-            1.0
-        };
-        CategoryStat {
-            name: c.name,
-            color: c.color,
-            seconds: c.seconds,
-            percentage: (c.seconds as f64 / total * 100.0).round(),
+            Self::Month
         }
-    })
-    .collect();
+    }
+}
 
-    // 详细报告
-    let detailed_report: Vec<TaskStat> = sqlx::query_as::<_, TaskStatSql>(&format!(
-        r#"
-        SELECT t.id AS task_id, t.title AS task_title, c.name AS category,
-               c.color AS category_color,
-               COALESCE(SUM(te.duration_seconds), 0) AS seconds,
-               (SELECT MAX(ended_at) FROM time_entries WHERE task_id = t.id AND date(started_at) = {}
-                ORDER BY started_at DESC LIMIT 1) AS last_time
-        FROM time_entries te
-        JOIN tasks t ON te.task_id = t.id
-        LEFT JOIN categories c ON t.category_id = c.id
-        WHERE date(te.started_at) = {}
-        GROUP BY t.id
-        ORDER BY seconds DESC, last_time DESC
-    "#,
-        date_filter, date_filter
-    ))
-    .fetch_all(pool)
-    .await?
-    .into_iter()
-    .map(|t| TaskStat {
-        task_id: t.task_id,
-        task_title: t.task_title,
-        category: t.category,
-        category_color: t.category_color,
-        seconds: t.seconds,
-        last_time: t.last_time.map(|s| format_time_only(&s)),
-    })
-    .collect();
+impl StatsReport {
+    /// 取某个维度的分组结果，未请求该维度时为空
+    pub fn group(&self, group_by: StatsGroupBy) -> &[StatsGroupItem] {
+        self.groups
+            .iter()
+            .find(|g| g.group_by == group_by)
+            .map_or(&[], |g| &g.items)
+    }
+
+    fn take_group(&mut self, group_by: StatsGroupBy) -> Vec<StatsGroupItem> {
+        self.groups
+            .iter_mut()
+            .find(|g| g.group_by == group_by)
+            .map(|g| std::mem::take(&mut g.items))
+            .unwrap_or_default()
+    }
+}
+
+/// 通用统计：按日期范围（默认今天）汇总，生成指定粒度的时间线和指定维度的分组
+pub async fn get_stats_impl(
+    pool: &SqlitePool,
+    range: StatsDateQuery,
+    granularity: StatsGranularity,
+    group_by: &[StatsGroupBy],
+) -> Result<StatsReport, ZapError> {
+    let start =
+        parse_stats_date(range.start_date.as_deref())?.unwrap_or_else(|| Local::now().date_naive());
+    let end = parse_stats_date(range.end_date.as_deref())?.unwrap_or(start);
+
+    compute_stats(
+        pool,
+        &range,
+        (start, end),
+        previous_period(start, end),
+        granularity,
+        group_by,
+    )
+    .await
+}
+
+/// 获取今日统计
+pub async fn get_today_stats_impl(
+    pool: &SqlitePool,
+    query: StatsDateQuery,
+) -> Result<TodayStatsResponse, ZapError> {
+    let day =
+        parse_stats_date(query.start_date.as_deref())?.unwrap_or_else(|| Local::now().date_naive());
+
+    let mut report = compute_stats(
+        pool,
+        &query,
+        (day, day),
+        shift_back(day, day),
+        StatsGranularity::Day,
+        &[
+            StatsGroupBy::Category,
+            StatsGroupBy::Client,
+            StatsGroupBy::Project,
+            StatsGroupBy::Task,
+        ],
+    )
+    .await?;
 
     Ok(TodayStatsResponse {
-        total_seconds,
-        focused_seconds,
-        sessions_count,
-        categories,
-        detailed_report,
+        total_seconds: report.total_seconds,
+        billable_seconds: report.billable_seconds,
+        earned_amount: report.earned_amount,
+        focused_seconds: report.focused_seconds,
+        sessions_count: report.sessions_count,
+        categories: category_stats(report.take_group(StatsGroupBy::Category)),
+        clients: client_stats(report.take_group(StatsGroupBy::Client)),
+        projects: project_stats(report.take_group(StatsGroupBy::Project)),
+        detailed_report: task_stats(report.take_group(StatsGroupBy::Task), true),
+        comparison: report.comparison.take(),
+        currency: report.currency,
     })
 }
 
-/// 获取本周统计
+/// 获取本周统计（默认最近 7 天）
 pub async fn get_week_stats_impl(
     pool: &SqlitePool,
     query: StatsDateQuery,
 ) -> Result<WeekStatsResponse, ZapError> {
-    let start_filter = query
-        .start_date
-        .as_deref()
-        .unwrap_or("date('now', '-6 days')");
-    let end_filter = query.end_date.as_deref().unwrap_or("date('now')");
-
-    let date_where = format!(
-        "date(started_at) >= date('{}') AND date(started_at) <= date('{}')",
-        start_filter, end_filter
-    );
+    let today = Local::now().date_naive();
+    let start =
+        parse_stats_date(query.start_date.as_deref())?.unwrap_or(today - chrono::Duration::days(6));
+    let end = parse_stats_date(query.end_date.as_deref())?.unwrap_or(today);
 
-    // 总时间
-    let total_seconds: i64 = sqlx::query_scalar(&format!(
-        r#"
-        SELECT COALESCE(SUM(duration_seconds), 0) +
-               COALESCE((SELECT strftime('%s', 'now') - strftime('%s', started_at)
-                        FROM time_entries WHERE ended_at IS NULL AND {}), 0)
-        FROM time_entries
-        WHERE {} AND ended_at IS NOT NULL
-    "#,
-        date_where, date_where
-    ))
-    .fetch_one(pool)
+    let mut report = compute_stats(
+        pool,
+        &query,
+        (start, end),
+        shift_back(start, end),
+        StatsGranularity::Day,
+        &[
+            StatsGroupBy::Category,
+            StatsGroupBy::Client,
+            StatsGroupBy::Project,
+        ],
+    )
     .await?;
 
-    // 会话数
-    let sessions_count: i64 = sqlx::query_scalar(&format!(
-        "SELECT COUNT(*) FROM time_entries WHERE {}",
-        date_where
-    ))
-    .fetch_one(pool)
-    .await?;
+    // 只列出有计时记录的日期
+    let daily_breakdown = report
+        .timeline
+        .iter()
+        .filter(|b| b.sessions_count > 0)
+        .map(|b| DailyStat {
+            day_name: parse_stats_date(Some(&b.start_date))
+                .ok()
+                .flatten()
+                .map(|d| d.format("%a").to_string())
+                .unwrap_or_default(),
+            date: b.start_date.clone(),
+            seconds: b.seconds,
+            percentage: b.percentage,
+        })
+        .collect();
 
-    // 计算天数
-    let days_count: i64 = sqlx::query_scalar(&format!(
-        "SELECT CAST((julianday(date('{}')) - julianday(date('{}')) + 1) AS INTEGER)",
-        end_filter, start_filter
-    ))
-    .fetch_one(pool)
+    Ok(WeekStatsResponse {
+        total_seconds: report.total_seconds,
+        billable_seconds: report.billable_seconds,
+        earned_amount: report.earned_amount,
+        focused_seconds: report.focused_seconds,
+        daily_average_seconds: report.total_seconds / report.days_count,
+        sessions_count: report.sessions_count,
+        daily_breakdown,
+        categories: category_stats(report.take_group(StatsGroupBy::Category)),
+        clients: client_stats(report.take_group(StatsGroupBy::Client)),
+        projects: project_stats(report.take_group(StatsGroupBy::Project)),
+        comparison: report.comparison.take(),
+        currency: report.currency,
+    })
+}
+
+/// 获取本月统计：start_date 为 YYYY-MM 时统计整月，为 YYYY-MM-DD 时统计到 end_date（默认当天），
+/// 未指定时统计本月 1 日至今天
+pub async fn get_month_stats_impl(
+    pool: &SqlitePool,
+    query: StatsDateQuery,
+) -> Result<MonthStatsResponse, ZapError> {
+    let today = Local::now().date_naive();
+    let (start, end) = match query.start_date.as_deref() {
+        Some(month) if month.len() == 7 => {
+            let first = parse_stats_date(Some(&format!("{month}-01")))?.unwrap_or(today);
+            (first, period_end(first, StatsGranularity::Month))
+        }
+        Some(start) => {
+            let start = parse_stats_date(Some(start))?.unwrap_or(today);
+            let end = parse_stats_date(query.end_date.as_deref())?.unwrap_or(start);
+            (start, end)
+        }
+        None => (today.with_day(1).unwrap_or(today), today),
+    };
+
+    let mut report = compute_stats(
+        pool,
+        &query,
+        (start, end),
+        previous_period(start, end),
+        StatsGranularity::Day,
+        &[
+            StatsGroupBy::Category,
+            StatsGroupBy::Client,
+            StatsGroupBy::Project,
+            StatsGroupBy::Task,
+        ],
+    )
     .await?;
-    let days_count = if days_count > 0 { days_count } else { 7 };
 
-    // 日均
-    let daily_average_seconds = if total_seconds > 0 {
-        total_seconds / days_count
-    } else {
-        0
+    // 每日概览：按当月最多的一天分为 3 档
+    let max_seconds = report.timeline.iter().map(|b| b.seconds).max().unwrap_or(0);
+    let monthly_overview = report
+        .timeline
+        .iter()
+        .map(|b| {
+            let level = if b.seconds == 0 {
+                0
+            } else if max_seconds > 0 {
+                ((b.seconds as f64 / max_seconds as f64 * 3.0).ceil() as i32).clamp(1, 3)
+            } else {
+                1
+            };
+            MonthlyDailyStat {
+                date: b.start_date.clone(),
+                seconds: b.seconds,
+                active: b.seconds > 0,
+                level,
+            }
+        })
+        .collect();
+
+    let mut top_tasks = task_stats(report.take_group(StatsGroupBy::Task), false);
+    top_tasks.truncate(10);
+
+    Ok(MonthStatsResponse {
+        total_seconds: report.total_seconds,
+        billable_seconds: report.billable_seconds,
+        earned_amount: report.earned_amount,
+        focused_seconds: report.focused_seconds,
+        daily_average_seconds: report.total_seconds / report.days_count,
+        active_days: report.active_days,
+        monthly_overview,
+        categories: category_stats(report.take_group(StatsGroupBy::Category)),
+        clients: client_stats(report.take_group(StatsGroupBy::Client)),
+        projects: project_stats(report.take_group(StatsGroupBy::Project)),
+        top_tasks,
+        comparison: report.comparison.take(),
+        currency: report.currency,
+    })
+}
+
+/// 获取年度统计：start_date 为 YYYY 时统计该自然年，否则统计截至 end_date（默认今天）的 365 天
+pub async fn get_year_stats_impl(
+    pool: &SqlitePool,
+    query: StatsDateQuery,
+) -> Result<YearStatsResponse, ZapError> {
+    let (start, end, previous) = match query.start_date.as_deref() {
+        Some(year) if year.len() == 4 => {
+            let first = parse_stats_date(Some(&format!("{year}-01-01")))?
+                .ok_or(ZapError::InvalidStatsQuery("invalid year"))?;
+            let last = year_end(first);
+            let previous_last = first - chrono::Duration::days(1);
+            let previous_first = previous_last.with_ordinal(1).unwrap_or(previous_last);
+            (first, last, (previous_first, previous_last))
+        }
+        _ => {
+            let end = parse_stats_date(query.end_date.as_deref())?
+                .unwrap_or_else(|| Local::now().date_naive());
+            let start = end - chrono::Duration::days(364);
+            (start, end, shift_back(start, end))
+        }
     };
 
-    // 每日分布
-    let daily_breakdown: Vec<DailyStat> = sqlx::query_as::<_, DailyStatSql>(&format!(
-        r#"
-        SELECT strftime('%w', started_at) AS day_num,
-               date(started_at) AS date,
-               COALESCE(SUM(duration_seconds), 0) +
-               COALESCE((SELECT strftime('%s', 'now') - strftime('%s', started_at)
-                        FROM time_entries WHERE ended_at IS NULL AND date(started_at) = date(te.started_at)), 0) AS seconds
-        FROM time_entries te
-        WHERE {}
-        GROUP BY date(started_at)
-        ORDER BY date(started_at)
-    "#,
-        date_where
-    ))
-    .fetch_all(pool)
-    .await?
-    .into_iter()
-    .map(|d| {
-        let day_names = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
-        let total = if total_seconds > 0 { total_seconds as f64 } else { 1.0 };
-        let day_idx = d.day_num.parse::<usize>().unwrap_or(0) % 7;
-        DailyStat {
-            day_name: day_names[day_idx].to_string(),
-            date: d.date,
-            seconds: d.seconds,
-            percentage: (d.seconds as f64 / total * 100.0).round(),
+    let mut report = compute_stats(
+        pool,
+        &query,
+        (start, end),
+        previous,
+        StatsGranularity::Day,
+        &[StatsGroupBy::Category],
+    )
+    .await?;
+
+    let seconds: Vec<i64> = report.timeline.iter().map(|b| b.seconds).collect();
+    let days = report
+        .timeline
+        .iter()
+        .zip(heat_levels(&seconds))
+        .map(|(b, level)| YearDayStat {
+            date: b.start_date.clone(),
+            seconds: b.seconds,
+            level,
+        })
+        .collect();
+
+    let mut months: Vec<MonthTotalStat> = Vec::new();
+    for bucket in &report.timeline {
+        let month = &bucket.start_date[..7];
+        if months.last().is_none_or(|m| m.month != month) {
+            months.push(MonthTotalStat {
+                month: month.to_string(),
+                seconds: 0,
+                active_days: 0,
+            });
         }
+        if let Some(total) = months.last_mut() {
+            total.seconds += bucket.seconds;
+            total.active_days += (bucket.sessions_count > 0) as i64;
+        }
+    }
+
+    Ok(YearStatsResponse {
+        start_date: report.start_date.clone(),
+        end_date: report.end_date.clone(),
+        total_seconds: report.total_seconds,
+        billable_seconds: report.billable_seconds,
+        earned_amount: report.earned_amount,
+        focused_seconds: report.focused_seconds,
+        daily_average_seconds: report.total_seconds / report.days_count,
+        active_days: report.active_days,
+        days,
+        months,
+        categories: category_stats(report.take_group(StatsGroupBy::Category)),
+        comparison: report.comparison.take(),
+        currency: report.currency,
     })
-    .collect();
+}
 
-    // 分类统计
-    let categories: Vec<CategoryStat> = sqlx::query_as::<_, CategoryStatSql>(&format!(
+/// 任意日期范围的统计，按范围长度自动选择时间线粒度：31 天以内按天，
+/// 26 周以内按周，更长按月
+pub async fn get_range_stats_impl(
+    pool: &SqlitePool,
+    query: StatsDateQuery,
+) -> Result<StatsReport, ZapError> {
+    let start =
+        parse_stats_date(query.start_date.as_deref())?.unwrap_or_else(|| Local::now().date_naive());
+    let end = parse_stats_date(query.end_date.as_deref())?.unwrap_or(start);
+
+    compute_stats(
+        pool,
+        &query,
+        (start, end),
+        previous_period(start, end),
+        StatsGranularity::for_range(start, end),
+        &[
+            StatsGroupBy::Category,
+            StatsGroupBy::Client,
+            StatsGroupBy::Project,
+            StatsGroupBy::Task,
+        ],
+    )
+    .await
+}
+
+/// 获取日期范围内的标签时间分布（默认今天），未打标签的时间归入 Untagged
+pub async fn get_tag_stats_impl(
+    pool: &SqlitePool,
+    query: StatsDateQuery,
+) -> Result<TagStatsResponse, ZapError> {
+    let mut report =
+        get_stats_impl(pool, query, StatsGranularity::Day, &[StatsGroupBy::Tag]).await?;
+
+    let tags = report
+        .take_group(StatsGroupBy::Tag)
+        .into_iter()
+        .map(|t| TagStat {
+            tag_id: t.id,
+            name: t.name,
+            color: t.color.unwrap_or_else(|| FALLBACK_COLOR.to_string()),
+            seconds: t.seconds,
+            percentage: t.percentage,
+            task_count: t.task_count,
+        })
+        .collect();
+
+    Ok(TagStatsResponse {
+        total_seconds: report.total_seconds,
+        tags,
+    })
+}
+
+/// 获取星期 × 小时的工作时段分布（默认最近 4 周）。日期按本地时间，跨整点的会话
+/// 按实际时长拆分到各个小时，进行中的记录截止到当前时刻；使用原始时长，不受取整影响
+pub async fn get_hourly_stats_impl(
+    pool: &SqlitePool,
+    query: StatsDateQuery,
+) -> Result<HourlyStatsResponse, ZapError> {
+    let today = Local::now().date_naive();
+    let start = parse_stats_date(query.start_date.as_deref())?
+        .unwrap_or(today - chrono::Duration::days(27));
+    let end = parse_stats_date(query.end_date.as_deref())?.unwrap_or(today);
+    if end < start {
+        return Err(ZapError::InvalidStatsQuery(
+            "end_date must not be before start_date",
+        ));
+    }
+
+    let range_start = local_midnight_utc(start);
+    let range_end = local_midnight_utc(end + chrono::Duration::days(1));
+    let entries = filter_by_task("time_entries".to_string(), &query);
+    let rows = sqlx::query_as::<_, HourlyEntrySql>(&format!(
         r#"
-        SELECT COALESCE(c.name, 'Other') AS name, COALESCE(c.color, '#9CA3AF') AS color,
-               COALESCE(SUM(te.duration_seconds), 0) AS seconds
-        FROM time_entries te
-        LEFT JOIN tasks t ON te.task_id = t.id
+        SELECT te.started_at, te.ended_at, c.id AS category_id,
+               COALESCE(c.name, 'Other') AS name, COALESCE(c.color, '{FALLBACK_COLOR}') AS color
+        FROM {entries} te
+        JOIN tasks t ON te.task_id = t.id
         LEFT JOIN categories c ON t.category_id = c.id
-        WHERE {}
-        GROUP BY c.id
-        ORDER BY seconds DESC
-    "#,
-        date_where
+        WHERE te.started_at < ? AND (te.ended_at IS NULL OR te.ended_at > ?)
+    "#
     ))
+    .bind(range_end.format(DATETIME_FORMAT).to_string())
+    .bind(range_start.format(DATETIME_FORMAT).to_string())
     .fetch_all(pool)
-    .await?
-    .into_iter()
-    .map(|c| {
-        let total = if total_seconds > 0 {
-            total_seconds as f64
-        } else {
-            1.0
+    .await?;
+
+    let now = Utc::now();
+    let mut overall = [[0; 24]; 7];
+    let mut categories: BTreeMap<Option<u32>, (String, String, HourMatrix)> = BTreeMap::new();
+    for row in rows {
+        let Some(started_at) = parse_utc(&row.started_at) else {
+            continue;
         };
-        CategoryStat {
-            name: c.name,
-            color: c.color,
-            seconds: c.seconds,
-            percentage: (c.seconds as f64 / total * 100.0).round(),
-        }
-    })
-    .collect();
+        let ended_at = row.ended_at.as_deref().and_then(parse_utc).unwrap_or(now);
+        let (from, to) = (started_at.max(range_start), ended_at.min(range_end));
 
-    Ok(WeekStatsResponse {
-        total_seconds,
-        daily_average_seconds,
-        sessions_count,
-        daily_breakdown,
+        add_to_matrix(&mut overall, from, to);
+        let (_, _, matrix) = categories
+            .entry(row.category_id)
+            .or_insert_with(|| (row.name, row.color, [[0; 24]; 7]));
+        add_to_matrix(matrix, from, to);
+    }
+
+    let mut categories: Vec<CategoryHourlyDistribution> = categories
+        .into_iter()
+        .map(
+            |(category_id, (name, color, matrix))| CategoryHourlyDistribution {
+                category_id,
+                name,
+                color,
+                distribution: HourlyDistribution::from_matrix(matrix),
+            },
+        )
+        .collect();
+    categories.sort_by(|a, b| {
+        b.distribution
+            .total_seconds
+            .cmp(&a.distribution.total_seconds)
+    });
+
+    Ok(HourlyStatsResponse {
+        start_date: start.to_string(),
+        end_date: end.to_string(),
+        overall: HourlyDistribution::from_matrix(overall),
         categories,
     })
 }
 
-/// 获取本月统计
-pub async fn get_month_stats_impl(
+/// 获取预估准确度报告，按任务完成日期筛选（默认本月），实际用时为任务的全部计时
+pub async fn get_estimate_accuracy_impl(
     pool: &SqlitePool,
     query: StatsDateQuery,
-) -> Result<MonthStatsResponse, ZapError> {
-    // 解析前端传来的 YYYY-MM 格式
-    let (start_date, end_date) = if let Some(ref start) = query.start_date {
-        if start.len() == 7 {
-            // YYYY-MM 格式
-            let start_sql = format!("date('{}-01')", start);
-            // 计算月末：下月第一天减一天
-            let end_sql = format!("date('{}-01', '+1 month', '-1 day')", start);
-            (start_sql, end_sql)
-        } else {
-            // YYYY-MM-DD 格式
-            let start_sql = format!("date('{}')", start);
-            let end_sql = query
-                .end_date
-                .as_ref()
-                .map_or_else(|| start_sql.clone(), |e| format!("date('{}')", e));
-            (start_sql, end_sql)
-        }
-    } else {
-        // 默认本月
-        let start_sql = "date('now', 'start of month')".to_string();
-        let end_sql = "date('now')".to_string();
-        (start_sql, end_sql)
-    };
+) -> Result<EstimateAccuracyResponse, ZapError> {
+    let entries = get_rounding_policy_impl(pool).await?.entries_source();
 
-    let date_where = format!(
-        "date(started_at) >= {} AND date(started_at) <= {}",
-        start_date, end_date
-    );
+    let today = Local::now().date_naive();
+    let start_date = query
+        .start_date
+        .clone()
+        .unwrap_or_else(|| today.format("%Y-%m-01").to_string());
+    let end_date = query
+        .end_date
+        .clone()
+        .unwrap_or_else(|| today.format("%Y-%m-%d").to_string());
 
-    // 总时间
-    let total_seconds: i64 = sqlx::query_scalar(&format!(
+    let rows = sqlx::query_as::<_, EstimateAccuracySql>(&format!(
         r#"
-        SELECT COALESCE(SUM(duration_seconds), 0) +
-               COALESCE((SELECT strftime('%s', 'now') - strftime('%s', started_at)
-                        FROM time_entries WHERE ended_at IS NULL AND {}), 0)
-        FROM time_entries
-        WHERE {} AND ended_at IS NOT NULL
-    "#,
-        date_where, date_where
+        SELECT c.id AS category_id, COALESCE(c.name, 'Other') AS name,
+               COALESCE(c.color, '#9CA3AF') AS color,
+               COUNT(*) AS task_count,
+               SUM(t.estimate_seconds) AS estimate_seconds,
+               COALESCE(SUM(a.seconds), 0) AS actual_seconds
+        FROM tasks t
+        LEFT JOIN categories c ON t.category_id = c.id
+        LEFT JOIN projects p ON t.project_id = p.id
+        LEFT JOIN (
+            SELECT task_id, SUM(duration_seconds) AS seconds FROM {entries} GROUP BY task_id
+        ) a ON a.task_id = t.id
+        WHERE t.done = 2 AND t.estimate_seconds > 0
+          AND date(t.completed_at, 'localtime') >= date(?)
+          AND date(t.completed_at, 'localtime') <= date(?)
+          AND (? IS NULL OR t.project_id = ?)
+          AND (? IS NULL OR p.client_id = ?)
+        GROUP BY c.id
+        ORDER BY actual_seconds DESC
+    "#
     ))
-    .fetch_one(pool)
+    .bind(&start_date)
+    .bind(&end_date)
+    .bind(query.project_id)
+    .bind(query.project_id)
+    .bind(query.client_id)
+    .bind(query.client_id)
+    .fetch_all(pool)
     .await?;
 
-    // 活跃天数
-    let active_days: i64 = sqlx::query_scalar(&format!(
-        "SELECT COUNT(DISTINCT date(started_at)) FROM time_entries WHERE {}",
-        date_where
-    ))
-    .fetch_one(pool)
-    .await?;
+    let task_count = rows.iter().map(|r| r.task_count).sum();
+    let estimate_seconds = rows.iter().map(|r| r.estimate_seconds).sum();
+    let actual_seconds = rows.iter().map(|r| r.actual_seconds).sum();
+    let categories = rows
+        .into_iter()
+        .map(|r| EstimateAccuracyStat {
+            category_id: r.category_id,
+            name: r.name,
+            color: r.color,
+            task_count: r.task_count,
+            estimate_seconds: r.estimate_seconds,
+            actual_seconds: r.actual_seconds,
+            ratio: accuracy_ratio(r.actual_seconds, r.estimate_seconds),
+        })
+        .collect();
 
-    // 计算天数
-    let days_count: i64 = sqlx::query_scalar(&format!(
-        "SELECT CAST((julianday({}) - julianday({}) + 1) AS INTEGER)",
-        end_date, start_date
-    ))
-    .fetch_one(pool)
+    Ok(EstimateAccuracyResponse {
+        task_count,
+        estimate_seconds,
+        actual_seconds,
+        ratio: accuracy_ratio(actual_seconds, estimate_seconds),
+        categories,
+    })
+}
+
+/// 统计所用的计时数据源：应用取整规则，并按客户/项目过滤
+async fn stats_entries_source(
+    pool: &SqlitePool,
+    query: &StatsDateQuery,
+) -> Result<String, ZapError> {
+    // 需要逐条裁剪到统计范围并按本地日期拆分，预汇总表（按天合计）无法做到，只能逐条读取
+    let entries = get_rounding_policy_impl(pool).await?.entries_source();
+    Ok(filter_by_task(entries, query))
+}
+
+/// 按客户/项目过滤计时数据源
+fn filter_by_task(source: String, query: &StatsDateQuery) -> String {
+    let mut conditions = Vec::new();
+    if let Some(project_id) = query.project_id {
+        conditions.push(format!("t.project_id = {}", project_id));
+    }
+    if let Some(client_id) = query.client_id {
+        conditions.push(format!("p.client_id = {}", client_id));
+    }
+    if conditions.is_empty() {
+        return source;
+    }
+
+    format!(
+        "(SELECT * FROM {} WHERE task_id IN \
+         (SELECT t.id FROM tasks t LEFT JOIN projects p ON t.project_id = p.id WHERE {}))",
+        source,
+        conditions.join(" AND ")
+    )
+}
+
+/// 统计引擎入口：计算本期统计，请求对比时再计算上一周期 `previous` 并附上变化
+async fn compute_stats(
+    pool: &SqlitePool,
+    filter: &StatsDateQuery,
+    (start, end): (NaiveDate, NaiveDate),
+    previous: (NaiveDate, NaiveDate),
+    granularity: StatsGranularity,
+    group_by: &[StatsGroupBy],
+) -> Result<StatsReport, ZapError> {
+    if !filter.compare {
+        return compute_report(pool, filter, start, end, granularity, group_by).await;
+    }
+
+    // 对比需要分类和任务分组，计算后只保留请求的维度
+    let mut with_compared = group_by.to_vec();
+    for by in [StatsGroupBy::Category, StatsGroupBy::Task] {
+        if !with_compared.contains(&by) {
+            with_compared.push(by);
+        }
+    }
+    let mut report = compute_report(pool, filter, start, end, granularity, &with_compared).await?;
+    let last = compute_report(
+        pool,
+        filter,
+        previous.0,
+        previous.1,
+        StatsGranularity::Day,
+        &[StatsGroupBy::Category, StatsGroupBy::Task],
+    )
     .await?;
-    let days_count = if days_count > 0 { days_count } else { 30 };
 
-    // 日均
-    let daily_average_seconds = if total_seconds > 0 {
-        total_seconds / days_count
-    } else {
-        0
+    report.comparison = Some(compare_reports(&report, &last));
+    report.groups.retain(|g| group_by.contains(&g.group_by));
+    Ok(report)
+}
+
+fn compare_reports(current: &StatsReport, previous: &StatsReport) -> PeriodComparison {
+    let previous_seconds = |by: StatsGroupBy, id: Option<u32>| {
+        previous
+            .group(by)
+            .iter()
+            .find(|item| item.id == id)
+            .map_or(0, |item| item.seconds)
     };
 
-    // 每日概览
-    let month_data: Vec<MonthlyDailyStatSql> = sqlx::query_as::<_, MonthlyDailyStatSql>(&format!(
+    let mut categories: Vec<StatsDelta> = current
+        .group(StatsGroupBy::Category)
+        .iter()
+        .map(|c| {
+            StatsDelta::new(
+                c.id,
+                c.name.clone(),
+                c.seconds,
+                previous_seconds(StatsGroupBy::Category, c.id),
+            )
+        })
+        .collect();
+    for c in previous.group(StatsGroupBy::Category) {
+        if !categories.iter().any(|d| d.id == c.id) {
+            categories.push(StatsDelta::new(c.id, c.name.clone(), 0, c.seconds));
+        }
+    }
+
+    let top_tasks = current
+        .group(StatsGroupBy::Task)
+        .iter()
+        .take(10)
+        .map(|t| {
+            StatsDelta::new(
+                t.id,
+                t.name.clone(),
+                t.seconds,
+                previous_seconds(StatsGroupBy::Task, t.id),
+            )
+        })
+        .collect();
+
+    PeriodComparison {
+        start_date: previous.start_date.clone(),
+        end_date: previous.end_date.clone(),
+        total: StatsDelta::new(
+            None,
+            "Total".to_string(),
+            current.total_seconds,
+            previous.total_seconds,
+        ),
+        categories,
+        top_tasks,
+    }
+}
+
+/// 上一个等长周期：整月对应上个月，月初至某天对应上个月的同一段（不超过月末），
+/// 其余范围向前平移相同天数
+fn previous_period(start: NaiveDate, end: NaiveDate) -> (NaiveDate, NaiveDate) {
+    let month_end = period_end(start, StatsGranularity::Month);
+    if start.day() == 1 && end <= month_end && end > start {
+        let previous_end_of_month = start - chrono::Duration::days(1);
+        let previous_start = previous_end_of_month
+            .with_day(1)
+            .unwrap_or(previous_end_of_month);
+        if end == month_end {
+            return (previous_start, previous_end_of_month);
+        }
+        return (
+            previous_start,
+            (previous_start + (end - start)).min(previous_end_of_month),
+        );
+    }
+
+    shift_back(start, end)
+}
+
+/// 向前平移相同天数的周期
+fn shift_back(start: NaiveDate, end: NaiveDate) -> (NaiveDate, NaiveDate) {
+    let length = end - start + chrono::Duration::days(1);
+    (start - length, end - length)
+}
+
+/// 日期所在年份的最后一天
+fn year_end(date: NaiveDate) -> NaiveDate {
+    date.with_ordinal(date.leap_year() as u32 + 365)
+        .unwrap_or(date)
+}
+
+/// 统计引擎：一次读取与范围相交的计时记录（进行中的记录截止到当前时刻），裁剪到
+/// 范围内并按本地日期拆分，再在内存中计算总计、时间线和各维度分组
+async fn compute_report(
+    pool: &SqlitePool,
+    filter: &StatsDateQuery,
+    start: NaiveDate,
+    end: NaiveDate,
+    granularity: StatsGranularity,
+    group_by: &[StatsGroupBy],
+) -> Result<StatsReport, ZapError> {
+    if end < start {
+        return Err(ZapError::InvalidStatsQuery(
+            "end_date must not be before start_date",
+        ));
+    }
+
+    // 是否专注按原始记录的整段时长判断，不受取整影响；专注时长与总时长同取取整后的时长，
+    // 因此专注时长不会超过总时长
+    let focus = get_focus_policy_impl(pool).await?;
+    let raw_seconds = "CASE WHEN raw.ended_at IS NULL \
+         THEN MAX(CAST(strftime('%s', 'now') - strftime('%s', raw.started_at) AS INTEGER), 0) \
+         ELSE raw.duration_seconds END";
+    let range_start = local_midnight_utc(start);
+    let range_end = local_midnight_utc(end + chrono::Duration::days(1));
+    let entries = stats_entries_source(pool, filter).await?;
+    let intervals = sqlx::query_as::<_, StatsIntervalSql>(&format!(
         r#"
-        SELECT date(started_at) AS date,
-               COALESCE(SUM(duration_seconds), 0) +
-               COALESCE((SELECT strftime('%s', 'now') - strftime('%s', started_at)
-                        FROM time_entries WHERE ended_at IS NULL AND date(started_at) = date(te.started_at)), 0) AS seconds
-        FROM time_entries te
-        WHERE {}
-        GROUP BY date(started_at)
-        ORDER BY date(started_at)
+        SELECT te.task_id, te.started_at, te.ended_at,
+               CASE WHEN te.ended_at IS NULL
+                    THEN MAX(CAST(strftime('%s', 'now') - strftime('%s', te.started_at) AS INTEGER), 0)
+                    ELSE te.duration_seconds END AS seconds,
+               {focused} AS focused,
+               COALESCE({BILLABLE_SQL}, 0) = 1 AS billable,
+               {HOURLY_RATE_SQL} AS hourly_rate,
+               t.title,
+               c.id AS category_id, c.name AS category_name, c.color AS category_color,
+               p.id AS project_id, p.name AS project_name,
+               cl.id AS client_id, cl.name AS client_name
+        FROM {entries} te
+        JOIN time_entries raw ON raw.id = te.id
+        JOIN tasks t ON te.task_id = t.id
+        LEFT JOIN projects p ON t.project_id = p.id
+        LEFT JOIN categories c ON t.category_id = c.id
+        LEFT JOIN clients cl ON p.client_id = cl.id
+        WHERE te.started_at < ? AND (te.ended_at IS NULL OR te.ended_at >= ?)
     "#,
-        date_where
+        focused = focus.sql_condition("raw.session_kind", raw_seconds),
     ))
+    .bind(range_end.format(DATETIME_FORMAT).to_string())
+    .bind(range_start.format(DATETIME_FORMAT).to_string())
     .fetch_all(pool)
     .await?;
 
-    // 转换为带 level 的格式
-    let max_seconds = month_data.iter().map(|d| d.seconds).max().unwrap_or(0);
+    let now = Utc::now();
+    let pieces: Vec<StatsPiece> = intervals
+        .iter()
+        .flat_map(|interval| interval.pieces(range_start, range_end, now))
+        .collect();
 
-    // 生成范围内的每一天
-    // 从 start_date 和 end_date 解析出日期范围
-    let start_parsed = if let Some(ref s) = query.start_date {
-        if s.len() == 7 {
-            // YYYY-MM 格式，取该月第一天
-            chrono::NaiveDate::parse_from_str(&format!("{}-01", s), "%Y-%m-%d")
-                .unwrap_or_else(|_| chrono::Utc::now().date_naive())
-        } else {
-            chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d")
-                .unwrap_or_else(|_| chrono::Utc::now().date_naive())
-        }
-    } else {
-        chrono::Utc::now().date_naive()
-    };
-    let end_parsed = if let Some(ref e) = query.end_date {
-        chrono::NaiveDate::parse_from_str(e, "%Y-%m-%d")
-            .unwrap_or_else(|_| start_parsed + chrono::Duration::days(29))
-    } else if query.start_date.as_ref().is_some_and(|s| s.len() == 7) {
-        // 如果只有 YYYY-MM，计算月末
-        start_parsed + chrono::Duration::days(days_count - 1)
+    let task_tags = if group_by.contains(&StatsGroupBy::Tag) {
+        sqlx::query_as::<_, TaskTagSql>(
+            "SELECT tt.task_id, g.id AS tag_id, g.name, g.color
+             FROM task_tags tt JOIN tags g ON tt.tag_id = g.id",
+        )
+        .fetch_all(pool)
+        .await?
     } else {
-        start_parsed + chrono::Duration::days(29)
+        Vec::new()
     };
 
-    let mut all_days: Vec<MonthlyDailyStat> = Vec::new();
-    let mut current = start_parsed;
-    while current <= end_parsed {
-        let date = current.format("%Y-%m-%d").to_string();
-        let day_data = month_data.iter().find(|d| d.date == date);
-        let seconds = day_data.map(|d| d.seconds).unwrap_or(0);
-
-        let active = seconds > 0;
-        let level = if seconds == 0 {
-            0
-        } else if max_seconds > 0 {
-            ((seconds as f64 / max_seconds as f64 * 3.0).ceil() as i32).clamp(1, 3)
-        } else {
-            1
+    let mut report = build_report(start, end, granularity, group_by, &pieces, &task_tags);
+    report.currency = get_currency_impl(pool).await?;
+    Ok(report)
+}
+
+/// 一条计时记录落在某个本地日期内的部分
+struct StatsPiece<'a> {
+    interval: &'a StatsIntervalSql,
+    day: NaiveDate,
+    /// 按实际时长占比分摊到这一段的（取整后）时长
+    seconds: i64,
+    focused_seconds: i64,
+    /// 是否为该记录的第一段，会话数只在第一段计入
+    first: bool,
+}
+
+impl StatsIntervalSql {
+    /// 裁剪到 [range_start, range_end) 并按本地日期拆分，取整后的时长和专注时长分别分摊
+    fn pieces(
+        &self,
+        range_start: DateTime<Utc>,
+        range_end: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Vec<StatsPiece<'_>> {
+        let Some(started_at) = parse_utc(&self.started_at) else {
+            return Vec::new();
         };
+        let ended_at = self.ended_at.as_deref().and_then(parse_utc).unwrap_or(now);
+        let focused_seconds = if self.focused { self.seconds } else { 0 };
 
-        all_days.push(MonthlyDailyStat {
-            date,
-            seconds,
-            active,
-            level,
-        });
-        current = current.succ_opt().unwrap();
+        let split =
+            |seconds| split_by_local_day(started_at, ended_at, seconds, range_start, range_end);
+        split(self.seconds)
+            .into_iter()
+            .zip(split(focused_seconds))
+            .enumerate()
+            .map(|(i, ((day, seconds), (_, focused_seconds)))| StatsPiece {
+                interval: self,
+                day,
+                seconds,
+                focused_seconds,
+                first: i == 0,
+            })
+            .collect()
     }
+}
 
-    // 分类统计
-    let categories: Vec<CategoryStat> = sqlx::query_as::<_, CategoryStatSql>(&format!(
-        r#"
-        SELECT COALESCE(c.name, 'Other') AS name, COALESCE(c.color, '#9CA3AF') AS color,
-               COALESCE(SUM(te.duration_seconds), 0) AS seconds
-        FROM time_entries te
-        LEFT JOIN tasks t ON te.task_id = t.id
-        LEFT JOIN categories c ON t.category_id = c.id
-        WHERE {}
-        GROUP BY c.id
-        ORDER BY seconds DESC
-    "#,
-        date_where
-    ))
-    .fetch_all(pool)
-    .await?
-    .into_iter()
-    .map(|c| {
-        let total = if total_seconds > 0 {
-            total_seconds as f64
-        } else {
-            1.0
+/// 将一条记录 [started_at, ended_at) 裁剪到 [range_start, range_end) 并按本地零点拆分，
+/// seconds（可为取整后的时长）按各段实际时长的占比分摊。累计取整，完整落在范围内的
+/// 记录各段之和等于 seconds；开始与结束相同的记录整体计入开始日期
+pub(crate) fn split_by_local_day(
+    started_at: DateTime<Utc>,
+    ended_at: DateTime<Utc>,
+    seconds: i64,
+    range_start: DateTime<Utc>,
+    range_end: DateTime<Utc>,
+) -> Vec<(NaiveDate, i64)> {
+    let span = (ended_at - started_at).num_seconds();
+    if span <= 0 {
+        if started_at < range_start || started_at >= range_end {
+            return Vec::new();
+        }
+        return vec![(started_at.with_timezone(&Local).date_naive(), seconds)];
+    }
+
+    let share = |elapsed: i64| (seconds as i128 * elapsed as i128 / span as i128) as i64;
+    let mut pieces = Vec::new();
+    let mut cursor = started_at.max(range_start);
+    let to = ended_at.min(range_end);
+    while cursor < to {
+        let day = cursor.with_timezone(&Local).date_naive();
+        let next = local_midnight_utc(day + chrono::Duration::days(1)).min(to);
+        let next = if next > cursor { next } else { to };
+        pieces.push((
+            day,
+            share((next - started_at).num_seconds()) - share((cursor - started_at).num_seconds()),
+        ));
+        cursor = next;
+    }
+    pieces
+}
+
+/// 按任务汇总的中间结果
+struct TaskTotals<'a> {
+    interval: &'a StatsIntervalSql,
+    seconds: i64,
+    billable_seconds: i64,
+    last_ended_at: Option<&'a str>,
+}
+
+impl TaskTotals<'_> {
+    fn earned_amount(&self) -> i64 {
+        earned_amount(self.billable_seconds, self.interval.hourly_rate)
+    }
+}
+
+fn build_report(
+    start: NaiveDate,
+    end: NaiveDate,
+    granularity: StatsGranularity,
+    group_by: &[StatsGroupBy],
+    pieces: &[StatsPiece],
+    task_tags: &[TaskTagSql],
+) -> StatsReport {
+    let mut tasks: BTreeMap<u32, TaskTotals> = BTreeMap::new();
+    let mut days = BTreeSet::new();
+    let mut sessions_count = 0;
+    let mut focused_seconds = 0;
+
+    let mut timeline = Vec::new();
+    let mut bucket_start = start;
+    while bucket_start <= end {
+        let bucket_end = period_end(bucket_start, granularity).min(end);
+        timeline.push((
+            bucket_start,
+            StatsBucket {
+                start_date: bucket_start.to_string(),
+                end_date: bucket_end.to_string(),
+                seconds: 0,
+                billable_seconds: 0,
+                focused_seconds: 0,
+                sessions_count: 0,
+                percentage: 0.0,
+            },
+        ));
+        bucket_start = bucket_end + chrono::Duration::days(1);
+    }
+
+    // 同一记录的各段相邻，跨天但仍在同一时间线分段内时会话只计一次
+    let mut last_bucket = None;
+    for piece in pieces {
+        let interval = piece.interval;
+        let billable_seconds = if interval.billable { piece.seconds } else { 0 };
+
+        let task = tasks.entry(interval.task_id).or_insert(TaskTotals {
+            interval,
+            seconds: 0,
+            billable_seconds: 0,
+            last_ended_at: None,
+        });
+        task.seconds += piece.seconds;
+        task.billable_seconds += billable_seconds;
+        task.last_ended_at = task.last_ended_at.max(interval.ended_at.as_deref());
+
+        sessions_count += piece.first as i64;
+        focused_seconds += piece.focused_seconds;
+        days.insert(piece.day);
+
+        let Some(index) = bucket_index(&timeline, piece.day) else {
+            continue;
         };
-        CategoryStat {
-            name: c.name,
-            color: c.color,
-            seconds: c.seconds,
-            percentage: (c.seconds as f64 / total * 100.0).round(),
+        let bucket = &mut timeline[index].1;
+        bucket.seconds += piece.seconds;
+        bucket.billable_seconds += billable_seconds;
+        bucket.focused_seconds += piece.focused_seconds;
+        if piece.first || last_bucket != Some(index) {
+            bucket.sessions_count += 1;
         }
-    })
-    .collect();
+        last_bucket = Some(index);
+    }
 
-    // Top 任务
-    let top_tasks: Vec<TaskStat> = sqlx::query_as::<_, TaskStatSql>(&format!(
-        r#"
-        SELECT t.id AS task_id, t.title AS task_title, c.name AS category,
-               c.color AS category_color,
-               COALESCE(SUM(te.duration_seconds), 0) AS seconds,
-               NULL AS last_time
-        FROM time_entries te
-        JOIN tasks t ON te.task_id = t.id
-        LEFT JOIN categories c ON t.category_id = c.id
-        WHERE {}
-        GROUP BY t.id
-        ORDER BY seconds DESC
-        LIMIT 10
-    "#,
-        date_where
-    ))
-    .fetch_all(pool)
-    .await?
-    .into_iter()
-    .map(|t| TaskStat {
-        task_id: t.task_id,
-        task_title: t.task_title,
-        category: t.category,
-        category_color: t.category_color,
-        seconds: t.seconds,
-        last_time: None,
-    })
-    .collect();
+    let total_seconds = tasks.values().map(|t| t.seconds).sum();
+    let timeline = timeline
+        .into_iter()
+        .map(|(_, mut bucket)| {
+            bucket.percentage = percentage(bucket.seconds, total_seconds);
+            bucket
+        })
+        .collect();
 
-    Ok(MonthStatsResponse {
+    let groups = group_by
+        .iter()
+        .map(|&by| StatsGroup {
+            group_by: by,
+            items: group_items(by, &tasks, task_tags, total_seconds),
+        })
+        .collect();
+
+    StatsReport {
+        start_date: start.to_string(),
+        end_date: end.to_string(),
+        days_count: (end - start).num_days() + 1,
         total_seconds,
-        daily_average_seconds,
-        active_days,
-        monthly_overview: all_days,
-        categories,
-        top_tasks,
+        billable_seconds: tasks.values().map(|t| t.billable_seconds).sum(),
+        earned_amount: tasks.values().map(TaskTotals::earned_amount).sum(),
+        currency: String::new(),
+        focused_seconds,
+        sessions_count,
+        active_days: days.len() as i64,
+        timeline,
+        groups,
+        comparison: None,
+    }
+}
+
+/// 查找日期所在的时间线分段
+fn bucket_index(timeline: &[(NaiveDate, StatsBucket)], day: NaiveDate) -> Option<usize> {
+    timeline.partition_point(|(s, _)| *s <= day).checked_sub(1)
+}
+
+/// 分组键：(id, 名称, 颜色, 上级名称)
+type GroupKey = (Option<u32>, String, Option<String>, Option<String>);
+
+fn group_keys(
+    by: StatsGroupBy,
+    task: &StatsIntervalSql,
+    task_tags: &[TaskTagSql],
+) -> Vec<GroupKey> {
+    match by {
+        StatsGroupBy::Category => vec![(
+            task.category_id,
+            task.category_name
+                .clone()
+                .unwrap_or_else(|| "Other".to_string()),
+            Some(
+                task.category_color
+                    .clone()
+                    .unwrap_or_else(|| FALLBACK_COLOR.to_string()),
+            ),
+            None,
+        )],
+        StatsGroupBy::Client => vec![(
+            task.client_id,
+            task.client_name
+                .clone()
+                .unwrap_or_else(|| "No Client".to_string()),
+            None,
+            None,
+        )],
+        StatsGroupBy::Project => vec![(
+            task.project_id,
+            task.project_name
+                .clone()
+                .unwrap_or_else(|| "No Project".to_string()),
+            None,
+            task.client_name.clone(),
+        )],
+        StatsGroupBy::Task => vec![(
+            Some(task.task_id),
+            task.title.clone(),
+            task.category_color.clone(),
+            task.category_name.clone(),
+        )],
+        StatsGroupBy::Tag => {
+            let tags: Vec<GroupKey> = task_tags
+                .iter()
+                .filter(|t| t.task_id == task.task_id)
+                .map(|t| (Some(t.tag_id), t.name.clone(), Some(t.color.clone()), None))
+                .collect();
+            if tags.is_empty() {
+                vec![(
+                    None,
+                    "Untagged".to_string(),
+                    Some(FALLBACK_COLOR.to_string()),
+                    None,
+                )]
+            } else {
+                tags
+            }
+        }
+    }
+}
+
+/// 按维度汇总任务；金额先按任务（各自生效费率）计算再汇总
+fn group_items(
+    by: StatsGroupBy,
+    tasks: &BTreeMap<u32, TaskTotals>,
+    task_tags: &[TaskTagSql],
+    total_seconds: i64,
+) -> Vec<StatsGroupItem> {
+    let mut groups: BTreeMap<Option<u32>, StatsGroupItem> = BTreeMap::new();
+    for task in tasks.values() {
+        for (id, name, color, parent_name) in group_keys(by, task.interval, task_tags) {
+            let item = groups.entry(id).or_insert(StatsGroupItem {
+                id,
+                name,
+                color,
+                parent_name,
+                seconds: 0,
+                percentage: 0.0,
+                billable_seconds: 0,
+                earned_amount: 0,
+                task_count: 0,
+                last_ended_at: None,
+            });
+            item.seconds += task.seconds;
+            item.billable_seconds += task.billable_seconds;
+            item.earned_amount += task.earned_amount();
+            item.task_count += 1;
+            item.last_ended_at = item
+                .last_ended_at
+                .take()
+                .max(task.last_ended_at.map(str::to_string));
+        }
+    }
+
+    let mut items: Vec<StatsGroupItem> = groups
+        .into_values()
+        .map(|mut item| {
+            item.percentage = percentage(item.seconds, total_seconds);
+            item
+        })
+        .collect();
+    items.sort_by(|a, b| {
+        b.seconds
+            .cmp(&a.seconds)
+            .then_with(|| b.last_ended_at.cmp(&a.last_ended_at))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    items
+}
+
+/// 日期所在时间段的最后一天
+fn period_end(date: NaiveDate, granularity: StatsGranularity) -> NaiveDate {
+    match granularity {
+        StatsGranularity::Day => date,
+        StatsGranularity::Week => {
+            date + chrono::Duration::days(6 - date.weekday().num_days_from_monday() as i64)
+        }
+        StatsGranularity::Month => {
+            let (year, month) = if date.month() == 12 {
+                (date.year() + 1, 1)
+            } else {
+                (date.year(), date.month() + 1)
+            };
+            NaiveDate::from_ymd_opt(year, month, 1)
+                .and_then(|d| d.pred_opt())
+                .unwrap_or(date)
+        }
+    }
+}
+
+/// 按有记录日期的四分位数为每天分档：0 = 无记录，1-4 依次递增，
+/// 避免个别特别长的一天把其余日期都压到最低档
+fn heat_levels(seconds: &[i64]) -> Vec<i32> {
+    let mut active: Vec<i64> = seconds.iter().copied().filter(|&s| s > 0).collect();
+    active.sort_unstable();
+    let quantile = |q: usize| active.get((active.len() * q).div_ceil(4).saturating_sub(1));
+    let thresholds: Vec<i64> = (1..4).filter_map(|q| quantile(q).copied()).collect();
+
+    seconds
+        .iter()
+        .map(|&s| {
+            if s <= 0 {
+                0
+            } else {
+                1 + thresholds.iter().filter(|&&t| s > t).count() as i32
+            }
+        })
+        .collect()
+}
+
+fn parse_stats_date(date: Option<&str>) -> Result<Option<NaiveDate>, ZapError> {
+    date.map(|d| {
+        NaiveDate::parse_from_str(d, "%Y-%m-%d")
+            .map_err(|_| ZapError::InvalidStatsQuery("date must be in YYYY-MM-DD format"))
     })
+    .transpose()
+}
+
+impl HourlyDistribution {
+    fn from_matrix(matrix: HourMatrix) -> Self {
+        let weekday_totals = matrix.map(|row| row.iter().sum());
+        let hour_totals: [i64; 24] =
+            std::array::from_fn(|hour| matrix.iter().map(|row| row[hour]).sum());
+        let total_seconds = weekday_totals.iter().sum();
+
+        let peak = (total_seconds > 0).then(|| {
+            let (weekday, hour) =
+                (0..7)
+                    .flat_map(|w| (0..24).map(move |h| (w, h)))
+                    .fold((0, 0), |best, (w, h)| {
+                        if matrix[w][h] > matrix[best.0][best.1] {
+                            (w, h)
+                        } else {
+                            best
+                        }
+                    });
+            let busiest_hour = argmax(&hour_totals);
+            let busiest_weekday = argmax(&weekday_totals);
+            PeakHours {
+                weekday: weekday as u32,
+                hour: hour as u32,
+                seconds: matrix[weekday][hour],
+                busiest_hour: busiest_hour as u32,
+                busiest_hour_seconds: hour_totals[busiest_hour],
+                busiest_weekday: busiest_weekday as u32,
+                busiest_weekday_seconds: weekday_totals[busiest_weekday],
+            }
+        });
+
+        Self {
+            total_seconds,
+            matrix,
+            weekday_totals,
+            hour_totals,
+            peak,
+        }
+    }
+}
+
+/// 最大值的下标，相同时取靠前的
+fn argmax(values: &[i64]) -> usize {
+    (0..values.len()).fold(0, |best, i| if values[i] > values[best] { i } else { best })
+}
+
+/// 将 [from, to) 按本地时间的整点拆分，累加到矩阵对应的星期和小时
+fn add_to_matrix(matrix: &mut HourMatrix, from: DateTime<Utc>, to: DateTime<Utc>) {
+    let mut cursor = from;
+    while cursor < to {
+        let local = cursor.with_timezone(&Local);
+        let into_hour = (local.minute() * 60 + local.second()) as i64;
+        let next = (cursor + chrono::Duration::seconds(3600 - into_hour)).min(to);
+        matrix[local.weekday().num_days_from_sunday() as usize][local.hour() as usize] +=
+            (next - cursor).num_seconds();
+        cursor = next;
+    }
+}
+
+/// 本地日期零点对应的 UTC 时间
+pub(crate) fn local_midnight_utc(date: NaiveDate) -> DateTime<Utc> {
+    let midnight = date.and_time(NaiveTime::MIN);
+    Local
+        .from_local_datetime(&midnight)
+        .earliest()
+        .map(|d| d.with_timezone(&Utc))
+        .unwrap_or_else(|| Utc.from_utc_datetime(&midnight))
+}
+
+/// 解析数据库中的 UTC 时间
+pub(crate) fn parse_utc(datetime: &str) -> Option<DateTime<Utc>> {
+    NaiveDateTime::parse_from_str(datetime, DATETIME_FORMAT)
+        .ok()
+        .map(|d| Utc.from_utc_datetime(&d))
+}
+
+fn category_stats(items: Vec<StatsGroupItem>) -> Vec<CategoryStat> {
+    items
+        .into_iter()
+        .map(|c| CategoryStat {
+            name: c.name,
+            color: c.color.unwrap_or_else(|| FALLBACK_COLOR.to_string()),
+            seconds: c.seconds,
+            percentage: c.percentage,
+            billable_seconds: c.billable_seconds,
+            earned_amount: c.earned_amount,
+        })
+        .collect()
+}
+
+fn client_stats(items: Vec<StatsGroupItem>) -> Vec<ClientStat> {
+    items
+        .into_iter()
+        .map(|c| ClientStat {
+            client_id: c.id,
+            name: c.name,
+            seconds: c.seconds,
+            percentage: c.percentage,
+        })
+        .collect()
+}
+
+fn project_stats(items: Vec<StatsGroupItem>) -> Vec<ProjectStat> {
+    items
+        .into_iter()
+        .map(|p| ProjectStat {
+            project_id: p.id,
+            name: p.name,
+            client_name: p.parent_name,
+            seconds: p.seconds,
+            percentage: p.percentage,
+        })
+        .collect()
+}
+
+fn task_stats(items: Vec<StatsGroupItem>, with_last_time: bool) -> Vec<TaskStat> {
+    items
+        .into_iter()
+        .filter_map(|t| {
+            Some(TaskStat {
+                task_id: t.id?,
+                task_title: t.name,
+                category: t.parent_name,
+                category_color: t.color,
+                seconds: t.seconds,
+                last_time: t
+                    .last_ended_at
+                    .filter(|_| with_last_time)
+                    .map(|s| format_time_only(&s)),
+                billable_seconds: t.billable_seconds,
+                earned_amount: t.earned_amount,
+            })
+        })
+        .collect()
+}
+
+/// 实际 / 预估，保留两位小数
+fn accuracy_ratio(actual_seconds: i64, estimate_seconds: i64) -> f64 {
+    if estimate_seconds <= 0 {
+        return 0.0;
+    }
+    (actual_seconds as f64 / estimate_seconds as f64 * 100.0).round() / 100.0
+}
+
+fn percentage(seconds: i64, total_seconds: i64) -> f64 {
+    let total = if total_seconds > 0 {
+        total_seconds as f64
+    } else {
+        1.0
+    };
+    (seconds as f64 / total * 100.0).round()
 }
 
 // SQL 查询辅助结构体
 #[derive(Debug, FromRow)]
-struct CategoryStatSql {
-    name: String,
-    color: String,
+struct StatsIntervalSql {
+    task_id: u32,
+    started_at: String,
+    ended_at: Option<String>,
     seconds: i64,
+    focused: bool,
+    billable: bool,
+    hourly_rate: i64,
+    title: String,
+    category_id: Option<u32>,
+    category_name: Option<String>,
+    category_color: Option<String>,
+    project_id: Option<u32>,
+    project_name: Option<String>,
+    client_id: Option<u32>,
+    client_name: Option<String>,
 }
 
 #[derive(Debug, FromRow)]
-struct DailyStatSql {
-    day_num: String,
-    date: String,
-    seconds: i64,
+struct HourlyEntrySql {
+    started_at: String,
+    ended_at: Option<String>,
+    category_id: Option<u32>,
+    name: String,
+    color: String,
 }
 
 #[derive(Debug, FromRow)]
-struct MonthlyDailyStatSql {
-    date: String,
-    seconds: i64,
+struct TaskTagSql {
+    task_id: u32,
+    tag_id: u32,
+    name: String,
+    color: String,
 }
 
 #[derive(Debug, FromRow)]
-struct TaskStatSql {
-    task_id: u32,
-    task_title: String,
-    category: Option<String>,
-    category_color: Option<String>,
-    seconds: i64,
-    last_time: Option<String>,
+struct EstimateAccuracySql {
+    category_id: Option<u32>,
+    name: String,
+    color: String,
+    task_count: i64,
+    estimate_seconds: i64,
+    actual_seconds: i64,
 }
 
 fn format_time_only(datetime: &str) -> String {
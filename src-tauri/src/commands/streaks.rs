@@ -0,0 +1,174 @@
+use std::collections::BTreeSet;
+
+use chrono::{Datelike, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+
+use crate::{
+    commands::{
+        goals::{GoalKind, GoalPeriod, get_goal, tracked_seconds_by_day},
+        rounding::get_rounding_policy_impl,
+    },
+    error::ZapError,
+};
+
+/// 连续记录的判定口径
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum StreakKind {
+    /// 当天有计时记录（含进行中的计时）
+    #[default]
+    Tracked,
+    /// 当天设置了每日重点且全部完成
+    FocusDone,
+    /// 当天达到指定的每日目标（goal_id，类型为 target、周期为 day），按天计时的口径与目标进度一致
+    DailyGoal,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct StreakQuery {
+    #[serde(default)]
+    pub kind: StreakKind,
+    /// kind 为 daily_goal 时必填
+    pub goal_id: Option<u32>,
+    /// 跳过周末：周六、周日未达成不中断连续记录，达成时照常计入
+    #[serde(default)]
+    pub skip_weekends: bool,
+    /// 截止日期 YYYY-MM-DD（本地日期），默认今天
+    pub date: Option<String>,
+}
+
+/// 一段连续记录
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StreakRun {
+    /// 起止日期 YYYY-MM-DD（含）
+    pub start_date: String,
+    pub end_date: String,
+    /// 达成的天数（跳过的周末不计）
+    pub days: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StreakResponse {
+    pub kind: StreakKind,
+    /// 当前连续天数；今天尚未达成时不中断，从昨天往前计算
+    pub current: i64,
+    pub longest: i64,
+    /// 今天是否已达成
+    pub today_done: bool,
+    /// 全部连续记录，最近的在前
+    pub history: Vec<StreakRun>,
+}
+
+/// 计算连续记录：按本地日期判定每天是否达成，全部由 daily_focus 和 time_entries 实时推导
+pub async fn get_streaks_impl(
+    pool: &SqlitePool,
+    query: StreakQuery,
+) -> Result<StreakResponse, ZapError> {
+    let today = match query.date.as_deref() {
+        Some(d) => NaiveDate::parse_from_str(d, "%Y-%m-%d")
+            .map_err(|_| ZapError::InvalidStatsQuery("date must be in YYYY-MM-DD format"))?,
+        None => chrono::Local::now().date_naive(),
+    };
+
+    let days = done_days(pool, &query, today).await?;
+    let days: BTreeSet<NaiveDate> = days
+        .iter()
+        .filter_map(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+        .filter(|d| *d <= today)
+        .collect();
+
+    let (history, current) = streak_runs(&days, today, query.skip_weekends);
+
+    Ok(StreakResponse {
+        kind: query.kind,
+        current,
+        longest: history.iter().map(|r| r.days).max().unwrap_or(0),
+        today_done: days.contains(&today),
+        history,
+    })
+}
+
+/// 截至 today 达成的本地日期 YYYY-MM-DD
+async fn done_days(
+    pool: &SqlitePool,
+    query: &StreakQuery,
+    today: NaiveDate,
+) -> Result<Vec<String>, ZapError> {
+    let days =
+        match query.kind {
+            StreakKind::Tracked => {
+                sqlx::query_scalar(
+                    "SELECT DISTINCT date(started_at, 'localtime') FROM time_entries
+                 WHERE ended_at IS NULL OR duration_seconds > 0",
+                )
+                .fetch_all(pool)
+                .await?
+            }
+            StreakKind::FocusDone => sqlx::query_scalar(
+                "SELECT focus_date FROM daily_focus GROUP BY focus_date HAVING MIN(is_done) = 1",
+            )
+            .fetch_all(pool)
+            .await?,
+            StreakKind::DailyGoal => {
+                let goal_id = query.goal_id.ok_or(ZapError::InvalidStatsQuery(
+                    "goal_id is required for daily goal streaks",
+                ))?;
+                let goal = get_goal(pool, goal_id).await?;
+                if goal.kind != GoalKind::Target || goal.period != GoalPeriod::Day {
+                    return Err(ZapError::InvalidGoalData(
+                        "streaks require a daily target goal",
+                    ));
+                }
+
+                let entries = get_rounding_policy_impl(pool).await?.entries_source();
+                tracked_seconds_by_day(pool, &entries, &goal, None, today)
+                    .await?
+                    .into_iter()
+                    .filter(|(_, seconds)| *seconds >= goal.target_seconds)
+                    .map(|(day, _)| day.to_string())
+                    .collect()
+            }
+        };
+
+    Ok(days)
+}
+
+/// 从最早的达成日逐日扫描到 today，返回全部连续记录（最近的在前）和当前连续天数
+fn streak_runs(
+    days: &BTreeSet<NaiveDate>,
+    today: NaiveDate,
+    skip_weekends: bool,
+) -> (Vec<StreakRun>, i64) {
+    let mut runs = Vec::new();
+    let mut run: Option<StreakRun> = None;
+
+    let Some(&first) = days.first() else {
+        return (runs, 0);
+    };
+    for day in first.iter_days().take_while(|d| *d <= today) {
+        if days.contains(&day) {
+            let run = run.get_or_insert_with(|| StreakRun {
+                start_date: day.to_string(),
+                end_date: day.to_string(),
+                days: 0,
+            });
+            run.end_date = day.to_string();
+            run.days += 1;
+        } else if day == today || (skip_weekends && is_weekend(day)) {
+            continue;
+        } else if let Some(run) = run.take() {
+            runs.push(run);
+        }
+    }
+
+    // 扫描结束时未中断的记录即当前连续记录
+    let current = run.as_ref().map_or(0, |r| r.days);
+    runs.extend(run);
+    runs.reverse();
+    (runs, current)
+}
+
+fn is_weekend(date: NaiveDate) -> bool {
+    matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
+}
@@ -0,0 +1,165 @@
+use serde::{Deserialize, Serialize};
+use sqlx::{QueryBuilder, Sqlite, SqlitePool, prelude::FromRow};
+
+use crate::error::ZapError;
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct TagResponse {
+    /// 主键
+    pub id: u32,
+    /// 标签名称
+    pub name: String,
+    /// 标签颜色
+    pub color: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SaveTagRequest {
+    /// 标签名称
+    pub name: String,
+    /// 标签颜色（Hex），缺省为灰色
+    pub color: Option<String>,
+}
+
+/// 未指定颜色时的默认标签颜色
+const DEFAULT_TAG_COLOR: &str = "#9CA3AF";
+
+pub async fn list_tags_impl(pool: &SqlitePool) -> Result<Vec<TagResponse>, ZapError> {
+    let tags = sqlx::query_as::<_, TagResponse>("SELECT id, name, color FROM tags ORDER BY name")
+        .fetch_all(pool)
+        .await?;
+
+    Ok(tags)
+}
+
+pub async fn create_tag_impl(
+    pool: &SqlitePool,
+    req: SaveTagRequest,
+) -> Result<TagResponse, ZapError> {
+    let name = validate_name(&req.name)?;
+
+    let res = sqlx::query("INSERT INTO tags (name, color) VALUES (?, ?)")
+        .bind(name)
+        .bind(req.color.as_deref().unwrap_or(DEFAULT_TAG_COLOR))
+        .execute(pool)
+        .await
+        .map_err(|e| duplicate_name(e, name))?;
+
+    get_tag_by_id(pool, res.last_insert_rowid() as u32).await
+}
+
+pub async fn update_tag_impl(
+    pool: &SqlitePool,
+    tag_id: u32,
+    req: SaveTagRequest,
+) -> Result<TagResponse, ZapError> {
+    let name = validate_name(&req.name)?;
+
+    let res = sqlx::query("UPDATE tags SET name = ?, color = COALESCE(?, color) WHERE id = ?")
+        .bind(name)
+        .bind(req.color)
+        .bind(tag_id)
+        .execute(pool)
+        .await
+        .map_err(|e| duplicate_name(e, name))?;
+
+    if res.rows_affected() == 0 {
+        return Err(ZapError::TagNotFound(tag_id));
+    }
+
+    get_tag_by_id(pool, tag_id).await
+}
+
+/// 删除标签，同时解除与任务的关联
+pub async fn delete_tag_impl(pool: &SqlitePool, tag_id: u32) -> Result<(), ZapError> {
+    let res = sqlx::query("DELETE FROM tags WHERE id = ?")
+        .bind(tag_id)
+        .execute(pool)
+        .await?;
+
+    if res.rows_affected() == 0 {
+        return Err(ZapError::TagNotFound(tag_id));
+    }
+    Ok(())
+}
+
+pub async fn list_task_tags_impl(
+    pool: &SqlitePool,
+    task_id: u32,
+) -> Result<Vec<TagResponse>, ZapError> {
+    let tags = sqlx::query_as::<_, TagResponse>(
+        "SELECT g.id, g.name, g.color FROM tags g
+         JOIN task_tags tt ON tt.tag_id = g.id
+         WHERE tt.task_id = ? ORDER BY g.name",
+    )
+    .bind(task_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(tags)
+}
+
+/// 用给定标签集合替换任务的全部标签
+pub async fn set_task_tags_impl(
+    pool: &SqlitePool,
+    task_id: u32,
+    tag_ids: Vec<u32>,
+) -> Result<Vec<TagResponse>, ZapError> {
+    let mut tx = pool.begin().await?;
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM tasks WHERE id = ?")
+        .bind(task_id)
+        .fetch_one(tx.as_mut())
+        .await?;
+    if count == 0 {
+        return Err(ZapError::TaskNotFound(task_id));
+    }
+
+    sqlx::query("DELETE FROM task_tags WHERE task_id = ?")
+        .bind(task_id)
+        .execute(tx.as_mut())
+        .await?;
+
+    if !tag_ids.is_empty() {
+        let mut qb =
+            QueryBuilder::<Sqlite>::new("INSERT OR IGNORE INTO task_tags (task_id, tag_id) ");
+        qb.push_values(&tag_ids, |mut b, tag_id| {
+            b.push_bind(task_id).push_bind(*tag_id);
+        });
+        qb.build().execute(tx.as_mut()).await.map_err(|e| match e {
+            sqlx::Error::Database(ref db) if db.is_foreign_key_violation() => {
+                ZapError::InvalidTagData("tag id not found")
+            }
+            e => ZapError::Database(e),
+        })?;
+    }
+
+    tx.commit().await?;
+    list_task_tags_impl(pool, task_id).await
+}
+
+async fn get_tag_by_id(pool: &SqlitePool, tag_id: u32) -> Result<TagResponse, ZapError> {
+    sqlx::query_as::<_, TagResponse>("SELECT id, name, color FROM tags WHERE id = ?")
+        .bind(tag_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or(ZapError::TagNotFound(tag_id))
+}
+
+/// 名称唯一约束冲突转换为 DuplicateTag，其余数据库错误原样返回
+fn duplicate_name(err: sqlx::Error, name: &str) -> ZapError {
+    match err {
+        sqlx::Error::Database(ref e) if e.is_unique_violation() => {
+            ZapError::DuplicateTag(name.to_string())
+        }
+        err => err.into(),
+    }
+}
+
+fn validate_name(name: &str) -> Result<&str, ZapError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(ZapError::InvalidTagData("name cannot be empty"));
+    }
+    Ok(name)
+}
@@ -1,3 +1,5 @@
+use std::collections::BTreeSet;
+
 use derive_builder::Builder;
 
 use serde::{Deserialize, Serialize};
@@ -7,7 +9,17 @@ use sqlx::{
     prelude::{FromRow, Type},
 };
 
-use crate::{commands::types::PaginatedResponse, error::ZapError};
+use crate::{
+    commands::{
+        recurrence::materialize_after_finish,
+        task_items::{apply_open_items_policy, get_open_items_policy_impl},
+        types::{
+            Cursor, PaginatedResponse, PaginationMode, SortKey, SortKeyKind, deserialize_nullable,
+            push_order_by, push_sort_key_columns,
+        },
+    },
+    error::ZapError,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize_repr, Deserialize_repr, Type, Default)]
 #[repr(u8)]
@@ -18,6 +30,115 @@ pub enum TaskStatus {
     Finished = 2,
 }
 
+/// 任务优先级，P0 最高
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize_repr, Deserialize_repr, Type, Default)]
+#[repr(u8)]
+pub enum TaskPriority {
+    P0 = 0,
+    P1 = 1,
+    #[default]
+    P2 = 2,
+    P3 = 3,
+}
+
+/// 任务列表排序方式，同值时按任务 id 排序以保证分页稳定
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskSort {
+    /// 默认：按状态（待办、进行中、已完成），同状态按截止日期（未设置的在后）、最近创建
+    #[default]
+    Default,
+    /// 优先级从高到低
+    Priority,
+    /// 最近创建
+    Created,
+    /// 最近更新
+    Updated,
+    /// 累计时长从多到少
+    TotalTime,
+    /// 今日时长从多到少
+    TodayTime,
+    /// 剩余预估时长从少到多，未设置预估的排在最后
+    EstimateRemaining,
+    /// 手动拖拽顺序
+    Manual,
+}
+
+impl TaskSort {
+    /// 排序键，作用于列表查询的外层（按 TaskResponse 的列名引用），最后按任务 id 保证稳定
+    fn keys(&self) -> &'static [SortKey] {
+        use SortKeyKind::{Int, Text};
+
+        const DEFAULT: &[SortKey] = &[
+            SortKey::asc("done", Int),
+            SortKey::asc("due_date IS NULL", Int),
+            SortKey::asc("COALESCE(due_date, '')", Text),
+            SortKey::desc("created_at", Text),
+            SortKey::desc("task_id", Int),
+        ];
+        const PRIORITY: &[SortKey] = &[
+            SortKey::asc("priority", Int),
+            SortKey::desc("created_at", Text),
+            SortKey::desc("task_id", Int),
+        ];
+        const CREATED: &[SortKey] = &[
+            SortKey::desc("created_at", Text),
+            SortKey::desc("task_id", Int),
+        ];
+        const UPDATED: &[SortKey] = &[
+            SortKey::desc("updated_at", Text),
+            SortKey::desc("task_id", Int),
+        ];
+        const TOTAL_TIME: &[SortKey] = &[
+            SortKey::desc("total_duration_seconds", Int),
+            SortKey::desc("task_id", Int),
+        ];
+        const TODAY_TIME: &[SortKey] = &[
+            SortKey::desc("today_duration_seconds", Int),
+            SortKey::desc("task_id", Int),
+        ];
+        const ESTIMATE_REMAINING: &[SortKey] = &[
+            SortKey::asc("estimate_seconds IS NULL", Int),
+            SortKey::asc(
+                "COALESCE(estimate_seconds - total_duration_seconds, 0)",
+                Int,
+            ),
+            SortKey::desc("task_id", Int),
+        ];
+        const MANUAL: &[SortKey] = &[SortKey::asc("position", Int), SortKey::asc("task_id", Int)];
+
+        match self {
+            TaskSort::Default => DEFAULT,
+            TaskSort::Priority => PRIORITY,
+            TaskSort::Created => CREATED,
+            TaskSort::Updated => UPDATED,
+            TaskSort::TotalTime => TOTAL_TIME,
+            TaskSort::TodayTime => TODAY_TIME,
+            TaskSort::EstimateRemaining => ESTIMATE_REMAINING,
+            TaskSort::Manual => MANUAL,
+        }
+    }
+
+    /// 游标作用域，保证游标只用于生成它的排序方式
+    fn cursor_scope(&self) -> String {
+        format!("tasks:{self:?}")
+    }
+}
+
+/// 按日期筛选任务的视图（日期均按本地日历日计算）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DueFilter {
+    /// 已逾期：截止日期早于今天且未完成
+    Overdue,
+    /// 今天到期
+    Today,
+    /// 本周（周一至周日）到期
+    ThisWeek,
+    /// 未安排计划日期
+    Unscheduled,
+}
+
 #[derive(Debug, Serialize, Deserialize, FromRow)]
 pub struct Tasks {
     /// 任务Id
@@ -28,12 +149,16 @@ pub struct Tasks {
     done: TaskStatus,
     /// 分类id
     category_id: Option<u32>,
+    /// 项目id
+    project_id: Option<u32>,
+    /// 所属周期任务id
+    recurring_task_id: Option<u32>,
     ///预估用时
     estimate_seconds: Option<u32>,
     /// 任务备注
     notes: Option<String>,
-    ///是否加入 Today Focus：0=否，1=是
-    is_today_focus: Option<bool>,
+    /// 是否为今日重点：今天（本地日期）的每日重点关联了该任务
+    is_today_focus: bool,
     ///创建时间
     created_at: String,
     ///更新时间
@@ -50,18 +175,66 @@ pub struct CreateTaskRequest {
     /// 分类id
     #[builder(default)]
     category_id: Option<u32>,
+    /// 项目id
+    #[builder(default)]
+    project_id: Option<u32>,
     /// 预估用时（秒）
     #[builder(default)]
     estimate_seconds: Option<i64>,
     /// 备注
     #[builder(default)]
     notes: Option<String>,
-    /// 是否加入Today Focus
-    #[builder(default)]
-    is_today_focus: Option<bool>,
     /// 是否立即开始并且开始计时
     #[builder(default)]
     start_on_create: Option<bool>,
+    /// 是否可计费
+    #[builder(default)]
+    billable: Option<bool>,
+    /// 任务级小时费率（最小货币单位）
+    #[builder(default)]
+    hourly_rate: Option<i64>,
+    /// 截止日期（YYYY-MM-DD）
+    #[builder(default)]
+    due_date: Option<String>,
+    /// 计划日期（YYYY-MM-DD）
+    #[builder(default)]
+    scheduled_date: Option<String>,
+    /// 优先级，缺省为 P2
+    #[builder(default)]
+    priority: Option<TaskPriority>,
+}
+
+/// 更新任务请求：字段缺省表示不修改，可置空字段传 null 表示清空
+#[derive(Debug, Default, Serialize, Deserialize, Builder)]
+#[builder(setter(into, strip_option), default)]
+pub struct UpdateTaskRequest {
+    /// 文本标题
+    title: Option<String>,
+    /// 分类id
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    category_id: Option<Option<u32>>,
+    /// 项目id
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    project_id: Option<Option<u32>>,
+    /// 预估用时（秒）
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    estimate_seconds: Option<Option<i64>>,
+    /// 备注
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    notes: Option<Option<String>>,
+    /// 是否可计费
+    billable: Option<bool>,
+    /// 任务级小时费率（最小货币单位）
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    hourly_rate: Option<Option<i64>>,
+    /// 截止日期（YYYY-MM-DD）
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    due_date: Option<Option<String>>,
+    /// 计划日期（YYYY-MM-DD）
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    scheduled_date: Option<Option<String>>,
+    /// 优先级
+    priority: Option<TaskPriority>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Builder)]
@@ -79,6 +252,71 @@ pub struct TaskQuery {
     /// 按任务状态分类
     #[builder(default)]
     done: Option<TaskStatus>,
+    /// 未指定状态时是否包含已完成任务
+    #[builder(default)]
+    #[serde(default)]
+    include_finished: bool,
+    /// 按分类筛选（任意一个）
+    #[builder(default)]
+    category_ids: Option<Vec<u32>>,
+    /// 包含未分类的任务；与 category_ids 同时使用时取并集
+    #[builder(default)]
+    #[serde(default)]
+    uncategorized: bool,
+    /// 创建日期范围（本地日期 YYYY-MM-DD，含）
+    #[builder(default)]
+    created_from: Option<String>,
+    #[builder(default)]
+    created_to: Option<String>,
+    /// 完成日期范围（本地日期 YYYY-MM-DD，含）
+    #[builder(default)]
+    completed_from: Option<String>,
+    #[builder(default)]
+    completed_to: Option<String>,
+    /// 在该日期范围内有计时记录（本地日期 YYYY-MM-DD，含）
+    #[builder(default)]
+    tracked_from: Option<String>,
+    #[builder(default)]
+    tracked_to: Option<String>,
+    /// 是否设置了预估用时
+    #[builder(default)]
+    has_estimate: Option<bool>,
+    /// 今天（本地日期）是否有计时
+    #[builder(default)]
+    has_time_today: Option<bool>,
+    /// 按客户筛选
+    #[builder(default)]
+    client_id: Option<u32>,
+    /// 按项目筛选
+    #[builder(default)]
+    project_id: Option<u32>,
+    /// 按标签筛选：包含其中任意一个标签
+    #[builder(default)]
+    tags_any: Option<Vec<u32>>,
+    /// 按标签筛选：同时包含全部标签
+    #[builder(default)]
+    tags_all: Option<Vec<u32>>,
+    /// 按截止/计划日期筛选
+    #[builder(default)]
+    due: Option<DueFilter>,
+    /// 按所属周期任务筛选
+    #[builder(default)]
+    recurring_task_id: Option<u32>,
+    /// 排序方式
+    #[builder(default)]
+    #[serde(default)]
+    sort: TaskSort,
+    /// 分页方式
+    #[builder(default)]
+    #[serde(default)]
+    pagination: PaginationMode,
+    /// 游标分页：上一页返回的 next_cursor，首页不传
+    #[builder(default)]
+    cursor: Option<String>,
+    /// 游标分页时是否同时统计总数
+    #[builder(default)]
+    #[serde(default)]
+    with_total: bool,
 }
 
 #[derive(Debug, Serialize, FromRow)]
@@ -95,46 +333,108 @@ pub struct TaskResponse {
     category_name: Option<String>,
     /// 分类颜色
     color: Option<String>,
+    /// 项目id
+    project_id: Option<u32>,
+    /// 项目名称
+    project_name: Option<String>,
+    /// 客户id
+    client_id: Option<u32>,
+    /// 客户名称
+    client_name: Option<String>,
+    /// 是否可计费
+    billable: bool,
+    /// 任务级小时费率（最小货币单位）
+    hourly_rate: Option<i64>,
+    /// 截止日期
+    due_date: Option<String>,
+    /// 计划日期
+    scheduled_date: Option<String>,
+    /// 所属周期任务id
+    recurring_task_id: Option<u32>,
+    /// 优先级
+    priority: TaskPriority,
+    /// 手动排序位置
+    position: i64,
+    /// 预估用时（秒）
+    estimate_seconds: Option<i64>,
+    /// 剩余预估时长（秒），超出预估时为负数
+    #[sqlx(skip)]
+    remaining_seconds: Option<i64>,
+    /// 已用时长占预估的百分比
+    #[sqlx(skip)]
+    percent_consumed: Option<f64>,
     /// 实际运行总时长（秒）
     total_duration_seconds: i64,
     /// 今日运行时长（秒）
     today_duration_seconds: i64,
+    /// 是否为今日重点：今天（本地日期）的每日重点关联了该任务
+    is_today_focus: bool,
     /// 完成时间
     completed_at: Option<String>,
     /// 当前会话时长(秒)
     session_seconds: i64,
+    /// 已完成检查项数
+    items_done: i64,
+    /// 检查项总数
+    items_total: i64,
+}
+
+impl TaskResponse {
+    /// 根据预估与实际用时（含当前会话）计算剩余时长和消耗百分比
+    fn fill_estimate_progress(&mut self) {
+        if let Some(estimate) = self.estimate_seconds.filter(|e| *e > 0) {
+            let actual = self.total_duration_seconds + self.session_seconds;
+            self.remaining_seconds = Some(estimate - actual);
+            self.percent_consumed = Some((actual as f64 / estimate as f64 * 100.0).round());
+        }
+    }
+}
+
+/// 运行中任务的实际用时首次超过预估
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct EstimateOverrun {
+    pub task_id: u32,
+    pub title: String,
+    pub estimate_seconds: i64,
+    pub actual_seconds: i64,
 }
 
 pub async fn add_task_impl(pool: &SqlitePool, req: CreateTaskRequest) -> Result<(), ZapError> {
     if req.title.is_empty() {
         return Err(ZapError::InvalidTaskData("title cannot be empty"));
     }
+    if req.hourly_rate.is_some_and(|r| r < 0) {
+        return Err(ZapError::InvalidTaskData("hourly rate cannot be negative"));
+    }
+    validate_date(req.due_date.as_deref())?;
+    validate_date(req.scheduled_date.as_deref())?;
 
     let mut tx = pool.begin().await?;
     let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
 
-    // 验证 category 存在
+    // 验证 category、project 存在
     if let Some(category_id) = req.category_id {
-        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM categories WHERE id = ?")
-            .bind(category_id)
-            .fetch_one(tx.as_mut())
-            .await?;
-
-        if count == 0 {
-            return Err(ZapError::InvalidCategoryData("category id not found"));
-        }
+        ensure_category_exists(&mut tx, category_id).await?;
+    }
+    if let Some(project_id) = req.project_id {
+        ensure_project_exists(&mut tx, project_id).await?;
     }
 
     // 插入任务
     let res = sqlx::query(
-        "INSERT INTO tasks (title, done, category_id, estimate_seconds, notes, is_today_focus)
-          VALUES (?, 0, ?, ?, ?, ?)",
+        "INSERT INTO tasks (title, done, category_id, project_id, estimate_seconds, notes, billable, hourly_rate, due_date, scheduled_date, priority, position)
+          VALUES (?, 0, ?, ?, ?, ?, ?, ?, ?, ?, ?, (SELECT COALESCE(MAX(position), 0) + 1 FROM tasks))",
     )
     .bind(&req.title)
     .bind(req.category_id)
+    .bind(req.project_id)
     .bind(req.estimate_seconds)
     .bind(&req.notes)
-    .bind(req.is_today_focus)
+    .bind(req.billable.unwrap_or(false))
+    .bind(req.hourly_rate)
+    .bind(&req.due_date)
+    .bind(&req.scheduled_date)
+    .bind(req.priority.unwrap_or_default())
     .execute(tx.as_mut())
     .await?;
 
@@ -155,46 +455,254 @@ pub async fn add_task_impl(pool: &SqlitePool, req: CreateTaskRequest) -> Result<
     Ok(())
 }
 
+pub async fn update_task_impl(
+    pool: &SqlitePool,
+    task_id: u32,
+    req: UpdateTaskRequest,
+) -> Result<(), ZapError> {
+    get_task_by_id(pool, task_id).await?;
+
+    if req.title.as_ref().is_some_and(|t| t.is_empty()) {
+        return Err(ZapError::InvalidTaskData("title cannot be empty"));
+    }
+    if let Some(Some(estimate)) = req.estimate_seconds
+        && estimate < 0
+    {
+        return Err(ZapError::InvalidTaskData("estimate cannot be negative"));
+    }
+    if let Some(Some(rate)) = req.hourly_rate
+        && rate < 0
+    {
+        return Err(ZapError::InvalidTaskData("hourly rate cannot be negative"));
+    }
+    if let Some(ref due_date) = req.due_date {
+        validate_date(due_date.as_deref())?;
+    }
+    if let Some(ref scheduled_date) = req.scheduled_date {
+        validate_date(scheduled_date.as_deref())?;
+    }
+
+    let mut tx = pool.begin().await?;
+
+    if let Some(Some(category_id)) = req.category_id {
+        ensure_category_exists(&mut tx, category_id).await?;
+    }
+    if let Some(Some(project_id)) = req.project_id {
+        ensure_project_exists(&mut tx, project_id).await?;
+    }
+
+    let mut qb = QueryBuilder::<Sqlite>::new("UPDATE tasks SET updated_at = datetime('now')");
+    if let Some(title) = req.title {
+        qb.push(", title = ").push_bind(title);
+    }
+    if let Some(category_id) = req.category_id {
+        qb.push(", category_id = ").push_bind(category_id);
+    }
+    if let Some(project_id) = req.project_id {
+        qb.push(", project_id = ").push_bind(project_id);
+    }
+    if let Some(estimate_seconds) = req.estimate_seconds {
+        qb.push(", estimate_seconds = ").push_bind(estimate_seconds);
+        qb.push(", overrun_notified_at = NULL");
+    }
+    if let Some(notes) = req.notes {
+        qb.push(", notes = ").push_bind(notes);
+    }
+    if let Some(billable) = req.billable {
+        qb.push(", billable = ").push_bind(billable);
+    }
+    if let Some(hourly_rate) = req.hourly_rate {
+        qb.push(", hourly_rate = ").push_bind(hourly_rate);
+    }
+    if let Some(due_date) = req.due_date {
+        qb.push(", due_date = ").push_bind(due_date);
+    }
+    if let Some(scheduled_date) = req.scheduled_date {
+        qb.push(", scheduled_date = ").push_bind(scheduled_date);
+    }
+    if let Some(priority) = req.priority {
+        qb.push(", priority = ").push_bind(priority);
+    }
+    qb.push(" WHERE id = ").push_bind(task_id);
+    qb.build().execute(tx.as_mut()).await?;
+
+    tx.commit().await?;
+    Ok(())
+}
+
 pub async fn list_tasks_impl(
     pool: &SqlitePool,
     req: TaskQuery,
 ) -> Result<PaginatedResponse<TaskResponse>, ZapError> {
-    let mut count_qb = QueryBuilder::<Sqlite>::new("SELECT COUNT(*) FROM tasks t WHERE 1=1");
-    apply_filters(&mut count_qb, &req);
-    let total: u32 = count_qb.build_query_as::<(i64,)>().fetch_one(pool).await?.0 as u32;
-
-    if total == 0 {
-        return Ok(PaginatedResponse::empty(req.page_index, req.page_size));
+    let keys = req.sort.keys();
+    let scope = req.sort.cursor_scope();
+    let cursor_mode = req.pagination == PaginationMode::Cursor;
+    let after = match req.cursor {
+        Some(ref cursor) if cursor_mode => Some(Cursor::decode(cursor, &scope, keys)?),
+        _ => None,
+    };
+
+    // 游标分页默认不统计总数，避免每页都做一次全量 COUNT
+    let total = if !cursor_mode || req.with_total {
+        let mut count_qb = QueryBuilder::<Sqlite>::new(
+            "SELECT COUNT(*) FROM tasks t LEFT JOIN projects p ON t.project_id = p.id WHERE 1=1",
+        );
+        apply_filters(&mut count_qb, &req);
+        Some(count_qb.build_query_as::<(i64,)>().fetch_one(pool).await?.0 as u32)
+    } else {
+        None
+    };
+
+    if total == Some(0) {
+        return Ok(if cursor_mode {
+            PaginatedResponse::with_cursor(Vec::new(), total, req.page_size, None)
+        } else {
+            PaginatedResponse::empty(req.page_index, req.page_size)
+        });
     }
 
-    let mut qb = QueryBuilder::<Sqlite>::new(
-        "SELECT t.id AS task_id, t.title, t.done, t.category_id, c.name AS category_name, c.color, \
-         COALESCE((SELECT SUM(duration_seconds) FROM time_entries WHERE task_id = t.id), 0) AS total_duration_seconds, \
-         COALESCE((SELECT SUM(duration_seconds) FROM time_entries WHERE task_id = t.id AND date(started_at) = date('now')), 0) AS today_duration_seconds, \
-         t.completed_at, \
-         CASE WHEN t.done = 1 THEN CAST((strftime('%s', 'now') - strftime('%s', te.started_at)) AS INTEGER) ELSE 0 END AS session_seconds \
+    let mut qb = QueryBuilder::<Sqlite>::new("SELECT x.*");
+    push_sort_key_columns(&mut qb, keys);
+    qb.push(
+        " FROM (SELECT t.id AS task_id, t.title, t.done, t.category_id, c.name AS category_name, c.color, \
+         t.project_id, p.name AS project_name, p.client_id, cl.name AS client_name, \
+         t.billable, t.hourly_rate, t.due_date, t.scheduled_date, t.recurring_task_id, t.priority, t.position, t.estimate_seconds, \
+         COALESCE((SELECT SUM(seconds) FROM task_day_totals WHERE task_id = t.id), 0) AS total_duration_seconds, \
+         COALESCE((SELECT SUM(seconds) FROM task_day_totals WHERE task_id = t.id AND day = date('now', 'localtime')), 0) AS today_duration_seconds, \
+         EXISTS (SELECT 1 FROM daily_focus WHERE task_id = t.id AND focus_date = date('now', 'localtime')) AS is_today_focus, \
+         t.completed_at, t.created_at, t.updated_at, \
+         CASE WHEN t.done = 1 THEN CAST((strftime('%s', 'now') - strftime('%s', te.started_at)) AS INTEGER) ELSE 0 END AS session_seconds, \
+         (SELECT COUNT(*) FROM task_items WHERE task_id = t.id AND is_done = 1) AS items_done, \
+         (SELECT COUNT(*) FROM task_items WHERE task_id = t.id) AS items_total \
          FROM tasks t \
          LEFT JOIN categories c ON t.category_id = c.id \
+         LEFT JOIN projects p ON t.project_id = p.id \
+         LEFT JOIN clients cl ON p.client_id = cl.id \
          LEFT JOIN time_entries te ON t.id = te.task_id AND te.ended_at IS NULL \
          WHERE 1=1",
     );
     apply_filters(&mut qb, &req);
-    qb.push(" ORDER BY t.done ASC,t.created_at DESC");
+    qb.push(") x");
+    if let Some(cursor) = after {
+        qb.push(" WHERE ");
+        cursor.push_after(&mut qb, keys);
+    }
+    push_order_by(&mut qb, keys);
 
-    let offset = (req.page_index.saturating_sub(1)) * req.page_size;
+    // 游标分页多取一行，用于判断是否还有下一页
     qb.push(" LIMIT ");
-    qb.push_bind(req.page_size as i64);
-    qb.push(" OFFSET ");
-    qb.push_bind(offset as i64);
-
-    let tasks = qb.build_query_as::<TaskResponse>().fetch_all(pool).await?;
-
-    Ok(PaginatedResponse::new(
-        tasks,
-        total,
-        req.page_index,
-        req.page_size,
-    ))
+    qb.push_bind(req.page_size as i64 + cursor_mode as i64);
+    if !cursor_mode {
+        let offset = (req.page_index.saturating_sub(1)) * req.page_size;
+        qb.push(" OFFSET ");
+        qb.push_bind(offset as i64);
+    }
+
+    let mut rows = qb.build().fetch_all(pool).await?;
+    let next_cursor = if cursor_mode && rows.len() > req.page_size as usize {
+        rows.truncate(req.page_size as usize);
+        match rows.last() {
+            Some(row) => Some(Cursor::from_row(&scope, keys, row)?.encode()?),
+            None => None,
+        }
+    } else {
+        None
+    };
+
+    let mut tasks = rows
+        .iter()
+        .map(TaskResponse::from_row)
+        .collect::<Result<Vec<_>, _>>()?;
+    tasks
+        .iter_mut()
+        .for_each(TaskResponse::fill_estimate_progress);
+
+    Ok(match total {
+        Some(total) if !cursor_mode => {
+            PaginatedResponse::new(tasks, total, req.page_index, req.page_size)
+        }
+        _ => PaginatedResponse::with_cursor(tasks, total, req.page_size, next_cursor),
+    })
+}
+
+/// 按拖拽结果重排任务：给定任务按新顺序重新占用它们原有的位置，
+/// 其他任务的位置不变，因此可以只提交当前页的任务
+pub async fn reorder_tasks_impl(pool: &SqlitePool, task_ids: Vec<u32>) -> Result<(), ZapError> {
+    let distinct: BTreeSet<u32> = task_ids.iter().copied().collect();
+    if distinct.len() != task_ids.len() {
+        return Err(ZapError::InvalidTaskData(
+            "task ids must not contain duplicates",
+        ));
+    }
+
+    let mut tx = pool.begin().await?;
+
+    let mut positions = Vec::with_capacity(task_ids.len());
+    for task_id in &task_ids {
+        let position: Option<i64> = sqlx::query_scalar("SELECT position FROM tasks WHERE id = ?")
+            .bind(task_id)
+            .fetch_optional(tx.as_mut())
+            .await?;
+        positions.push(position.ok_or(ZapError::TaskNotFound(*task_id))?);
+    }
+    positions.sort_unstable();
+
+    for (task_id, position) in task_ids.iter().zip(positions) {
+        // 只调整顺序，不影响“最近更新”排序
+        sqlx::query("UPDATE tasks SET position = ? WHERE id = ?")
+            .bind(position)
+            .bind(task_id)
+            .execute(tx.as_mut())
+            .await?;
+    }
+
+    tx.commit().await?;
+    Ok(())
+}
+
+/// 找出实际用时刚超过预估的运行中任务，并标记为已提醒，每个任务只返回一次
+pub async fn take_estimate_overruns_impl(
+    pool: &SqlitePool,
+) -> Result<Vec<EstimateOverrun>, ZapError> {
+    let mut tx = pool.begin().await?;
+
+    let overruns = sqlx::query_as::<_, EstimateOverrun>(
+        r#"
+        SELECT t.id AS task_id, t.title, t.estimate_seconds,
+               COALESCE(SUM(CASE WHEN te.ended_at IS NULL
+                   THEN CAST((strftime('%s', 'now') - strftime('%s', te.started_at)) AS INTEGER)
+                   ELSE te.duration_seconds END), 0) AS actual_seconds
+        FROM tasks t
+        JOIN time_entries te ON te.task_id = t.id
+        WHERE t.done = 1 AND t.estimate_seconds > 0 AND t.overrun_notified_at IS NULL
+        GROUP BY t.id
+        HAVING actual_seconds >= t.estimate_seconds
+    "#,
+    )
+    .fetch_all(tx.as_mut())
+    .await?;
+
+    for overrun in &overruns {
+        sqlx::query("UPDATE tasks SET overrun_notified_at = datetime('now') WHERE id = ?")
+            .bind(overrun.task_id)
+            .execute(tx.as_mut())
+            .await?;
+    }
+
+    tx.commit().await?;
+    Ok(overruns)
+}
+
+/// 统计已逾期的未完成任务数（用于托盘角标）
+pub async fn count_overdue_tasks_impl(pool: &SqlitePool) -> Result<i64, ZapError> {
+    let count: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM tasks
+         WHERE done IN (0, 1) AND due_date IS NOT NULL AND due_date < date('now', 'localtime')",
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(count)
 }
 
 pub async fn start_task_impl(pool: &SqlitePool, task_id: u32) -> Result<(), ZapError> {
@@ -244,9 +752,12 @@ pub async fn stop_task_impl(pool: &SqlitePool, task_id: u32) -> Result<(), ZapEr
 
 pub async fn finish_task_impl(pool: &SqlitePool, task_id: u32) -> Result<(), ZapError> {
     let task = get_task_by_id(pool, task_id).await?;
+    let open_items_policy = get_open_items_policy_impl(pool).await?;
 
     let mut tx = pool.begin().await?;
 
+    apply_open_items_policy(&mut tx, open_items_policy, task_id).await?;
+
     if task.done == TaskStatus::Running {
         update_task_status(&mut tx, task_id, TaskStatus::Todo).await?;
         update_time_entries(&mut tx, task_id).await?;
@@ -259,6 +770,19 @@ pub async fn finish_task_impl(pool: &SqlitePool, task_id: u32) -> Result<(), Zap
         .execute(tx.as_mut())
         .await?;
 
+    if let Some(recurring_task_id) = task.recurring_task_id {
+        materialize_after_finish(&mut tx, recurring_task_id).await?;
+    }
+
+    // 关联的今日重点随任务完成一并勾掉；往日未完成的重点保持原样
+    sqlx::query(
+        "UPDATE daily_focus SET is_done = 1, updated_at = datetime('now')
+         WHERE task_id = ? AND is_done = 0 AND focus_date = date('now', 'localtime')",
+    )
+    .bind(task_id)
+    .execute(tx.as_mut())
+    .await?;
+
     tx.commit().await?;
     Ok(())
 }
@@ -279,7 +803,7 @@ async fn update_task_status(
     task_id: u32,
     done: TaskStatus,
 ) -> Result<(), ZapError> {
-    sqlx::query("UPDATE tasks SET done = ? WHERE id = ?")
+    sqlx::query("UPDATE tasks SET done = ?, updated_at = datetime('now') WHERE id = ?")
         .bind(done)
         .bind(task_id)
         .execute(tx.as_mut())
@@ -310,19 +834,183 @@ async fn update_time_entries(
 
 async fn get_task_by_id(pool: &SqlitePool, task_id: u32) -> Result<Tasks, ZapError> {
     sqlx::query_as::<_, Tasks>(
-        "SELECT id , title, done, category_id, estimate_seconds, notes, is_today_focus, created_at, updated_at, completed_at FROM tasks WHERE id = ?",
+        "SELECT id , title, done, category_id, project_id, recurring_task_id, estimate_seconds, notes, \
+         EXISTS (SELECT 1 FROM daily_focus WHERE task_id = tasks.id AND focus_date = date('now', 'localtime')) AS is_today_focus, \
+         created_at, updated_at, completed_at FROM tasks WHERE id = ?",
     ).bind(task_id).fetch_optional(pool).await?.ok_or(ZapError::TaskNotFound(task_id))
 }
 
+pub(crate) async fn ensure_category_exists(
+    tx: &mut Transaction<'_, Sqlite>,
+    category_id: u32,
+) -> Result<(), ZapError> {
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM categories WHERE id = ?")
+        .bind(category_id)
+        .fetch_one(tx.as_mut())
+        .await?;
+
+    if count == 0 {
+        return Err(ZapError::InvalidCategoryData("category id not found"));
+    }
+    Ok(())
+}
+
+pub(crate) async fn ensure_project_exists(
+    tx: &mut Transaction<'_, Sqlite>,
+    project_id: u32,
+) -> Result<(), ZapError> {
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM projects WHERE id = ?")
+        .bind(project_id)
+        .fetch_one(tx.as_mut())
+        .await?;
+
+    if count == 0 {
+        return Err(ZapError::InvalidProjectData("project id not found"));
+    }
+    Ok(())
+}
+
+fn validate_date(date: Option<&str>) -> Result<(), ZapError> {
+    if let Some(date) = date
+        && chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").is_err()
+    {
+        return Err(ZapError::InvalidTaskData(
+            "date must be in YYYY-MM-DD format",
+        ));
+    }
+    Ok(())
+}
+
+/// 追加本地日期范围条件（两端均含）：起止换算成本地零点对应的 UTC 时间后与 UTC 列比较
+fn push_date_range<'a>(
+    qb: &mut QueryBuilder<'a, Sqlite>,
+    column: &str,
+    from: &'a Option<String>,
+    to: &'a Option<String>,
+) {
+    if let Some(from) = from {
+        qb.push(format!(" AND {column} >= datetime("));
+        qb.push_bind(from.as_str());
+        qb.push(", 'utc')");
+    }
+    if let Some(to) = to {
+        qb.push(format!(" AND {column} < datetime("));
+        qb.push_bind(to.as_str());
+        qb.push(", '+1 day', 'utc')");
+    }
+}
+
 fn apply_filters<'a>(qb: &mut QueryBuilder<'a, Sqlite>, req: &'a TaskQuery) {
     if let Some(ref name) = req.task_name {
         qb.push(" AND t.title LIKE ");
         qb.push_bind(format!("%{}%", name));
     }
+    if let Some(project_id) = req.project_id {
+        qb.push(" AND t.project_id = ");
+        qb.push_bind(project_id);
+    }
+    if let Some(recurring_task_id) = req.recurring_task_id {
+        qb.push(" AND t.recurring_task_id = ");
+        qb.push_bind(recurring_task_id);
+    }
+    if let Some(client_id) = req.client_id {
+        qb.push(" AND p.client_id = ");
+        qb.push_bind(client_id);
+    }
+    if let Some(ref tag_ids) = req.tags_any
+        && !tag_ids.is_empty()
+    {
+        qb.push(" AND t.id IN (SELECT task_id FROM task_tags WHERE tag_id IN (");
+        let mut separated = qb.separated(", ");
+        for tag_id in tag_ids {
+            separated.push_bind(*tag_id);
+        }
+        qb.push("))");
+    }
+    if let Some(ref tag_ids) = req.tags_all
+        && !tag_ids.is_empty()
+    {
+        let distinct: BTreeSet<u32> = tag_ids.iter().copied().collect();
+        qb.push(" AND t.id IN (SELECT task_id FROM task_tags WHERE tag_id IN (");
+        let mut separated = qb.separated(", ");
+        for tag_id in &distinct {
+            separated.push_bind(*tag_id);
+        }
+        qb.push(") GROUP BY task_id HAVING COUNT(DISTINCT tag_id) = ");
+        qb.push_bind(distinct.len() as i64);
+        qb.push(")");
+    }
+    match req.due {
+        Some(DueFilter::Overdue) => {
+            qb.push(" AND t.done IN (0, 1) AND t.due_date < date('now', 'localtime')");
+        }
+        Some(DueFilter::Today) => {
+            qb.push(" AND t.due_date = date('now', 'localtime')");
+        }
+        Some(DueFilter::ThisWeek) => {
+            qb.push(
+                " AND t.due_date BETWEEN date('now', 'localtime', 'weekday 0', '-6 days') \
+                 AND date('now', 'localtime', 'weekday 0')",
+            );
+        }
+        Some(DueFilter::Unscheduled) => {
+            qb.push(" AND t.scheduled_date IS NULL");
+        }
+        None => {}
+    }
+    if req.category_ids.as_ref().is_some_and(|ids| !ids.is_empty()) || req.uncategorized {
+        qb.push(" AND (");
+        if let Some(ref category_ids) = req.category_ids
+            && !category_ids.is_empty()
+        {
+            qb.push("t.category_id IN (");
+            let mut separated = qb.separated(", ");
+            for category_id in category_ids {
+                separated.push_bind(*category_id);
+            }
+            qb.push(")");
+            if req.uncategorized {
+                qb.push(" OR ");
+            }
+        }
+        if req.uncategorized {
+            qb.push("t.category_id IS NULL");
+        }
+        qb.push(")");
+    }
+    push_date_range(qb, "t.created_at", &req.created_from, &req.created_to);
+    push_date_range(qb, "t.completed_at", &req.completed_from, &req.completed_to);
+    if req.tracked_from.is_some() || req.tracked_to.is_some() {
+        qb.push(" AND t.id IN (SELECT te.task_id FROM time_entries te WHERE 1=1");
+        push_date_range(qb, "te.started_at", &req.tracked_from, &req.tracked_to);
+        qb.push(")");
+    }
+    match req.has_estimate {
+        Some(true) => {
+            qb.push(" AND t.estimate_seconds > 0");
+        }
+        Some(false) => {
+            qb.push(" AND COALESCE(t.estimate_seconds, 0) = 0");
+        }
+        None => {}
+    }
+    if let Some(has_time_today) = req.has_time_today {
+        qb.push(if has_time_today {
+            " AND t.id IN"
+        } else {
+            " AND t.id NOT IN"
+        });
+        qb.push(
+            " (SELECT task_id FROM time_entries \
+             WHERE started_at >= datetime('now', 'localtime', 'start of day', 'utc') \
+             AND started_at < datetime('now', 'localtime', 'start of day', '+1 day', 'utc') \
+             AND (duration_seconds > 0 OR ended_at IS NULL))",
+        );
+    }
     if let Some(done) = req.done {
         qb.push(" AND t.done = ");
         qb.push_bind(done);
-    } else {
+    } else if !req.include_finished {
         qb.push(" AND t.done IN (0, 1)");
     }
 }
@@ -0,0 +1,225 @@
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, Sqlite, SqlitePool, Transaction};
+
+use crate::{
+    commands::settings::{get_setting, set_setting},
+    error::ZapError,
+};
+
+/// 完成任务时检查项处理方式在 settings 表中的键
+const FINISH_POLICY_KEY: &str = "task_items_finish_policy";
+
+/// 完成任务时对未完成检查项的处理方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum OpenItemsPolicy {
+    /// 不处理，直接完成任务
+    #[default]
+    Ignore,
+    /// 存在未完成检查项时拒绝完成任务
+    Require,
+    /// 完成任务时自动勾选所有未完成检查项
+    Complete,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct TaskItem {
+    pub id: u32,
+    pub task_id: u32,
+    pub title: String,
+    pub is_done: bool,
+    pub position: i64,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AddTaskItemRequest {
+    pub task_id: u32,
+    pub title: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct UpdateTaskItemRequest {
+    pub title: Option<String>,
+    pub is_done: Option<bool>,
+}
+
+const ITEM_COLUMNS: &str =
+    "SELECT id, task_id, title, is_done, position, created_at, updated_at FROM task_items";
+
+pub async fn list_task_items_impl(
+    pool: &SqlitePool,
+    task_id: u32,
+) -> Result<Vec<TaskItem>, ZapError> {
+    let items = sqlx::query_as::<_, TaskItem>(&format!(
+        "{ITEM_COLUMNS} WHERE task_id = ? ORDER BY position, id"
+    ))
+    .bind(task_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(items)
+}
+
+pub async fn add_task_item_impl(
+    pool: &SqlitePool,
+    req: AddTaskItemRequest,
+) -> Result<TaskItem, ZapError> {
+    let title = req.title.trim();
+    if title.is_empty() {
+        return Err(ZapError::InvalidTaskItemData("title cannot be empty"));
+    }
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM tasks WHERE id = ?")
+        .bind(req.task_id)
+        .fetch_one(pool)
+        .await?;
+    if count == 0 {
+        return Err(ZapError::TaskNotFound(req.task_id));
+    }
+
+    let res = sqlx::query(
+        "INSERT INTO task_items (task_id, title, position)
+         VALUES (?, ?, (SELECT COALESCE(MAX(position), -1) + 1 FROM task_items WHERE task_id = ?))",
+    )
+    .bind(req.task_id)
+    .bind(title)
+    .bind(req.task_id)
+    .execute(pool)
+    .await?;
+
+    get_task_item_by_id(pool, res.last_insert_rowid() as u32).await
+}
+
+pub async fn update_task_item_impl(
+    pool: &SqlitePool,
+    item_id: u32,
+    req: UpdateTaskItemRequest,
+) -> Result<TaskItem, ZapError> {
+    let item = get_task_item_by_id(pool, item_id).await?;
+
+    let title = match req.title {
+        Some(ref title) if title.trim().is_empty() => {
+            return Err(ZapError::InvalidTaskItemData("title cannot be empty"));
+        }
+        Some(ref title) => title.trim().to_string(),
+        None => item.title,
+    };
+    let is_done = req.is_done.unwrap_or(item.is_done);
+
+    sqlx::query(
+        "UPDATE task_items SET title = ?, is_done = ?, updated_at = datetime('now') WHERE id = ?",
+    )
+    .bind(title)
+    .bind(is_done)
+    .bind(item_id)
+    .execute(pool)
+    .await?;
+
+    get_task_item_by_id(pool, item_id).await
+}
+
+pub async fn delete_task_item_impl(pool: &SqlitePool, item_id: u32) -> Result<(), ZapError> {
+    let res = sqlx::query("DELETE FROM task_items WHERE id = ?")
+        .bind(item_id)
+        .execute(pool)
+        .await?;
+
+    if res.rows_affected() == 0 {
+        return Err(ZapError::TaskItemNotFound(item_id));
+    }
+    Ok(())
+}
+
+/// 按给定顺序重排任务的全部检查项，item_ids 必须恰好包含该任务的所有检查项
+pub async fn reorder_task_items_impl(
+    pool: &SqlitePool,
+    task_id: u32,
+    item_ids: Vec<u32>,
+) -> Result<Vec<TaskItem>, ZapError> {
+    let mut tx = pool.begin().await?;
+
+    let mut existing: Vec<u32> = sqlx::query_scalar("SELECT id FROM task_items WHERE task_id = ?")
+        .bind(task_id)
+        .fetch_all(tx.as_mut())
+        .await?;
+    let mut requested = item_ids.clone();
+    existing.sort_unstable();
+    requested.sort_unstable();
+    if existing != requested {
+        return Err(ZapError::InvalidTaskItemData(
+            "item ids must list every item of the task exactly once",
+        ));
+    }
+
+    for (position, item_id) in item_ids.iter().enumerate() {
+        sqlx::query(
+            "UPDATE task_items SET position = ?, updated_at = datetime('now') WHERE id = ?",
+        )
+        .bind(position as i64)
+        .bind(item_id)
+        .execute(tx.as_mut())
+        .await?;
+    }
+
+    tx.commit().await?;
+    list_task_items_impl(pool, task_id).await
+}
+
+/// 获取完成任务时对未完成检查项的处理方式
+pub async fn get_open_items_policy_impl(pool: &SqlitePool) -> Result<OpenItemsPolicy, ZapError> {
+    Ok(get_setting(pool, FINISH_POLICY_KEY)
+        .await?
+        .unwrap_or_default())
+}
+
+/// 设置完成任务时对未完成检查项的处理方式
+pub async fn set_open_items_policy_impl(
+    pool: &SqlitePool,
+    policy: OpenItemsPolicy,
+) -> Result<OpenItemsPolicy, ZapError> {
+    set_setting(pool, FINISH_POLICY_KEY, &policy).await?;
+    Ok(policy)
+}
+
+/// 完成任务前按配置处理未完成的检查项
+pub(crate) async fn apply_open_items_policy(
+    tx: &mut Transaction<'_, Sqlite>,
+    policy: OpenItemsPolicy,
+    task_id: u32,
+) -> Result<(), ZapError> {
+    match policy {
+        OpenItemsPolicy::Ignore => {}
+        OpenItemsPolicy::Require => {
+            let open: i64 = sqlx::query_scalar(
+                "SELECT COUNT(*) FROM task_items WHERE task_id = ? AND is_done = 0",
+            )
+            .bind(task_id)
+            .fetch_one(tx.as_mut())
+            .await?;
+
+            if open > 0 {
+                return Err(ZapError::TaskHasOpenItems(task_id));
+            }
+        }
+        OpenItemsPolicy::Complete => {
+            sqlx::query(
+                "UPDATE task_items SET is_done = 1, updated_at = datetime('now')
+                 WHERE task_id = ? AND is_done = 0",
+            )
+            .bind(task_id)
+            .execute(tx.as_mut())
+            .await?;
+        }
+    }
+    Ok(())
+}
+
+async fn get_task_item_by_id(pool: &SqlitePool, item_id: u32) -> Result<TaskItem, ZapError> {
+    sqlx::query_as::<_, TaskItem>(&format!("{ITEM_COLUMNS} WHERE id = ?"))
+        .bind(item_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or(ZapError::TaskItemNotFound(item_id))
+}
@@ -0,0 +1,212 @@
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, QueryBuilder, Sqlite, SqlitePool, Type};
+
+use crate::{
+    commands::{
+        settings::{get_setting, set_setting},
+        types::{
+            Cursor, PaginatedResponse, SortKey, SortKeyKind, push_order_by, push_sort_key_columns,
+        },
+    },
+    error::ZapError,
+};
+
+/// 专注时长规则在 settings 表中的键
+const FOCUS_POLICY_KEY: &str = "focus_policy";
+
+/// 计时记录列表默认每页数量
+const DEFAULT_PAGE_SIZE: u32 = 50;
+
+/// 计时记录按开始时间倒序，同一时间按 id 倒序
+const ENTRY_SORT_KEYS: &[SortKey] = &[
+    SortKey::desc("te.started_at", SortKeyKind::Text),
+    SortKey::desc("te.id", SortKeyKind::Int),
+];
+
+const ENTRY_CURSOR_SCOPE: &str = "time_entries";
+
+/// 计时会话类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type, Default)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(rename_all = "snake_case")]
+pub enum SessionKind {
+    #[default]
+    Normal,
+    /// 专注会话，无论时长均计入专注时间
+    Focus,
+    /// 番茄钟，无论时长均计入专注时间
+    Pomodoro,
+}
+
+/// 专注时长规则：标记为专注/番茄钟的会话，或不间断时长达到阈值的会话计为专注
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FocusPolicy {
+    /// 普通会话计为专注的最短时长（分钟），1 到 480
+    pub min_session_minutes: u32,
+}
+
+impl Default for FocusPolicy {
+    fn default() -> Self {
+        Self {
+            min_session_minutes: 25,
+        }
+    }
+}
+
+impl FocusPolicy {
+    /// 生成判断会话是否为专注的 SQL 条件，`seconds` 为会话时长表达式
+    pub(crate) fn sql_condition(&self, kind: &str, seconds: &str) -> String {
+        format!(
+            "({kind} IN ('focus', 'pomodoro') OR {seconds} >= {})",
+            self.min_session_minutes as i64 * 60
+        )
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct TimeEntryQuery {
+    /// 按任务筛选
+    pub task_id: Option<u32>,
+    /// 开始日期范围（YYYY-MM-DD，含）
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
+    /// 每页数量，默认 50
+    pub page_size: Option<u32>,
+    /// 上一页返回的 next_cursor，首页不传
+    pub cursor: Option<String>,
+    /// 是否同时统计总数
+    #[serde(default)]
+    pub with_total: bool,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct TimeEntryResponse {
+    pub id: u32,
+    pub task_id: u32,
+    pub task_title: String,
+    pub started_at: String,
+    pub ended_at: Option<String>,
+    pub duration_seconds: i64,
+    pub note: Option<String>,
+    /// 单独设置的可计费标记，为空表示沿用任务设置
+    pub billable: Option<bool>,
+    pub session_kind: SessionKind,
+}
+
+/// 按游标分页列出计时记录（开始时间倒序）
+pub async fn list_time_entries_impl(
+    pool: &SqlitePool,
+    query: TimeEntryQuery,
+) -> Result<PaginatedResponse<TimeEntryResponse>, ZapError> {
+    let page_size = query.page_size.unwrap_or(DEFAULT_PAGE_SIZE);
+    let after = query
+        .cursor
+        .as_deref()
+        .map(|c| Cursor::decode(c, ENTRY_CURSOR_SCOPE, ENTRY_SORT_KEYS))
+        .transpose()?;
+
+    let total = if query.with_total {
+        let mut count_qb =
+            QueryBuilder::<Sqlite>::new("SELECT COUNT(*) FROM time_entries te WHERE 1=1");
+        apply_entry_filters(&mut count_qb, &query);
+        Some(count_qb.build_query_as::<(i64,)>().fetch_one(pool).await?.0 as u32)
+    } else {
+        None
+    };
+
+    let mut qb = QueryBuilder::<Sqlite>::new(
+        "SELECT te.id, te.task_id, t.title AS task_title, te.started_at, te.ended_at, \
+         te.duration_seconds, te.note, te.billable, te.session_kind",
+    );
+    push_sort_key_columns(&mut qb, ENTRY_SORT_KEYS);
+    qb.push(" FROM time_entries te JOIN tasks t ON te.task_id = t.id WHERE 1=1");
+    apply_entry_filters(&mut qb, &query);
+    if let Some(cursor) = after {
+        qb.push(" AND ");
+        cursor.push_after(&mut qb, ENTRY_SORT_KEYS);
+    }
+    push_order_by(&mut qb, ENTRY_SORT_KEYS);
+    qb.push(" LIMIT ");
+    qb.push_bind(page_size as i64 + 1);
+
+    let mut rows = qb.build().fetch_all(pool).await?;
+    let next_cursor = if rows.len() > page_size as usize {
+        rows.truncate(page_size as usize);
+        match rows.last() {
+            Some(row) => {
+                Some(Cursor::from_row(ENTRY_CURSOR_SCOPE, ENTRY_SORT_KEYS, row)?.encode()?)
+            }
+            None => None,
+        }
+    } else {
+        None
+    };
+
+    let entries = rows
+        .iter()
+        .map(TimeEntryResponse::from_row)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(PaginatedResponse::with_cursor(
+        entries,
+        total,
+        page_size,
+        next_cursor,
+    ))
+}
+
+/// 设置计时记录的会话类型（可用于进行中的记录）
+pub async fn set_session_kind_impl(
+    pool: &SqlitePool,
+    entry_id: u32,
+    kind: SessionKind,
+) -> Result<(), ZapError> {
+    let res = sqlx::query("UPDATE time_entries SET session_kind = ? WHERE id = ?")
+        .bind(kind)
+        .bind(entry_id)
+        .execute(pool)
+        .await?;
+
+    if res.rows_affected() == 0 {
+        return Err(ZapError::TimeEntryNotFound(entry_id));
+    }
+    Ok(())
+}
+
+/// 获取专注时长规则，未配置时为 25 分钟
+pub async fn get_focus_policy_impl(pool: &SqlitePool) -> Result<FocusPolicy, ZapError> {
+    Ok(get_setting(pool, FOCUS_POLICY_KEY)
+        .await?
+        .unwrap_or_default())
+}
+
+/// 保存专注时长规则
+pub async fn set_focus_policy_impl(
+    pool: &SqlitePool,
+    policy: FocusPolicy,
+) -> Result<FocusPolicy, ZapError> {
+    if !(1..=480).contains(&policy.min_session_minutes) {
+        return Err(ZapError::InvalidSetting(
+            "focus session threshold must be between 1 and 480 minutes",
+        ));
+    }
+    set_setting(pool, FOCUS_POLICY_KEY, &policy).await?;
+    Ok(policy)
+}
+
+fn apply_entry_filters<'a>(qb: &mut QueryBuilder<'a, Sqlite>, query: &'a TimeEntryQuery) {
+    if let Some(task_id) = query.task_id {
+        qb.push(" AND te.task_id = ");
+        qb.push_bind(task_id);
+    }
+    if let Some(ref start_date) = query.start_date {
+        qb.push(" AND date(te.started_at) >= date(");
+        qb.push_bind(start_date.as_str());
+        qb.push(")");
+    }
+    if let Some(ref end_date) = query.end_date {
+        qb.push(" AND date(te.started_at) <= date(");
+        qb.push_bind(end_date.as_str());
+        qb.push(")");
+    }
+}
@@ -1,16 +1,36 @@
-use serde::Serialize;
+use serde::{Deserialize, Deserializer, Serialize};
+use sqlx::{QueryBuilder, Row, Sqlite, sqlite::SqliteRow};
+
+use crate::error::ZapError;
+
+/// 分页方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum PaginationMode {
+    /// 页码分页（LIMIT/OFFSET），总是返回总数
+    #[default]
+    Offset,
+    /// 游标分页：按上一页返回的 next_cursor 继续，适合大数据量
+    Cursor,
+}
 
 /// 分页响应结构
 #[derive(Debug, Serialize)]
 pub struct PaginatedResponse<T> {
     /// 当前页的数据列表
     pub data: Vec<T>,
-    /// 符合条件的总记录数
+    /// 符合条件的总记录数（页码分页；游标分页时为 0，总数见 cursor_total）
     pub total: u32,
-    /// 当前页码（从 1 开始）
+    /// 当前页码（从 1 开始，游标分页时为 0）
     pub page_index: u32,
     /// 每页大小
     pub page_size: u32,
+    /// 下一页的游标，没有更多数据时为空（仅游标分页）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+    /// 游标分页时符合条件的总记录数，仅在请求时返回
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor_total: Option<u32>,
 }
 
 impl<T> PaginatedResponse<T> {
@@ -20,15 +40,197 @@ impl<T> PaginatedResponse<T> {
             total,
             page_index,
             page_size,
+            next_cursor: None,
+            cursor_total: None,
         }
     }
 
     pub fn empty(page_index: u32, page_size: u32) -> Self {
+        Self::new(Vec::new(), 0, page_index, page_size)
+    }
+
+    pub fn with_cursor(
+        data: Vec<T>,
+        cursor_total: Option<u32>,
+        page_size: u32,
+        next_cursor: Option<String>,
+    ) -> Self {
         Self {
-            data: Vec::new(),
+            data,
             total: 0,
-            page_index,
+            page_index: 0,
             page_size,
+            next_cursor,
+            cursor_total,
+        }
+    }
+}
+
+/// 排序键的取值类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SortKeyKind {
+    Int,
+    Text,
+}
+
+/// 排序键：SQL 表达式（取值不能为 NULL）与方向，最后一个键必须唯一以保证顺序稳定
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct SortKey {
+    pub expr: &'static str,
+    pub descending: bool,
+    pub kind: SortKeyKind,
+}
+
+impl SortKey {
+    pub const fn asc(expr: &'static str, kind: SortKeyKind) -> Self {
+        Self {
+            expr,
+            descending: false,
+            kind,
+        }
+    }
+
+    pub const fn desc(expr: &'static str, kind: SortKeyKind) -> Self {
+        Self {
+            expr,
+            descending: true,
+            kind,
         }
     }
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+enum CursorValue {
+    Int(i64),
+    Text(String),
+}
+
+/// 分页游标：记录上一页最后一行的排序键取值，JSON 序列化后十六进制编码，对前端不透明
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct Cursor {
+    /// 游标所属的列表与排序方式，防止混用
+    scope: String,
+    keys: Vec<CursorValue>,
+}
+
+impl Cursor {
+    /// 从查询结果行中读取排序键（列名为 sort_key_N）生成游标
+    pub fn from_row(scope: &str, keys: &[SortKey], row: &SqliteRow) -> Result<Self, ZapError> {
+        let values = keys
+            .iter()
+            .enumerate()
+            .map(|(i, key)| {
+                let column = format!("sort_key_{i}");
+                Ok(match key.kind {
+                    SortKeyKind::Int => CursorValue::Int(row.try_get(column.as_str())?),
+                    SortKeyKind::Text => CursorValue::Text(row.try_get(column.as_str())?),
+                })
+            })
+            .collect::<Result<Vec<_>, sqlx::Error>>()?;
+
+        Ok(Self {
+            scope: scope.to_string(),
+            keys: values,
+        })
+    }
+
+    pub fn encode(&self) -> Result<String, ZapError> {
+        let json = serde_json::to_vec(self)?;
+        Ok(json.iter().map(|b| format!("{b:02x}")).collect())
+    }
+
+    /// 解码游标并校验其与当前列表、排序键一致
+    pub fn decode(cursor: &str, scope: &str, keys: &[SortKey]) -> Result<Self, ZapError> {
+        let bytes = (0..cursor.len())
+            .step_by(2)
+            .map(|i| {
+                cursor
+                    .get(i..i + 2)
+                    .and_then(|h| u8::from_str_radix(h, 16).ok())
+            })
+            .collect::<Option<Vec<u8>>>()
+            .ok_or(ZapError::InvalidCursor("malformed cursor"))?;
+        let cursor: Self = serde_json::from_slice(&bytes)
+            .map_err(|_| ZapError::InvalidCursor("malformed cursor"))?;
+
+        if cursor.scope != scope {
+            return Err(ZapError::InvalidCursor(
+                "cursor belongs to a different listing or sort order",
+            ));
+        }
+        let kinds_match = cursor.keys.len() == keys.len()
+            && cursor.keys.iter().zip(keys).all(|(value, key)| {
+                matches!(
+                    (value, key.kind),
+                    (CursorValue::Int(_), SortKeyKind::Int)
+                        | (CursorValue::Text(_), SortKeyKind::Text)
+                )
+            });
+        if !kinds_match {
+            return Err(ZapError::InvalidCursor("cursor does not match sort keys"));
+        }
+        Ok(cursor)
+    }
+
+    /// 追加"位于游标之后"的条件：
+    /// (k1 > v1) OR (k1 = v1 AND k2 > v2) OR ...，降序键使用 <
+    pub fn push_after(self, qb: &mut QueryBuilder<'_, Sqlite>, keys: &[SortKey]) {
+        qb.push("(");
+        for i in 0..keys.len() {
+            if i > 0 {
+                qb.push(" OR ");
+            }
+            qb.push("(");
+            for (key, value) in keys.iter().zip(&self.keys).take(i) {
+                qb.push(format!("({}) = ", key.expr));
+                push_cursor_value(qb, value.clone());
+                qb.push(" AND ");
+            }
+            let op = if keys[i].descending { "<" } else { ">" };
+            qb.push(format!("({}) {op} ", keys[i].expr));
+            push_cursor_value(qb, self.keys[i].clone());
+            qb.push(")");
+        }
+        qb.push(")");
+    }
+}
+
+/// 追加排序键列：, expr AS sort_key_0, ...
+pub(crate) fn push_sort_key_columns(qb: &mut QueryBuilder<'_, Sqlite>, keys: &[SortKey]) {
+    for (i, key) in keys.iter().enumerate() {
+        qb.push(format!(", {} AS sort_key_{i}", key.expr));
+    }
+}
+
+/// 追加 ORDER BY 子句
+pub(crate) fn push_order_by(qb: &mut QueryBuilder<'_, Sqlite>, keys: &[SortKey]) {
+    let order = keys
+        .iter()
+        .map(|key| {
+            format!(
+                "{} {}",
+                key.expr,
+                if key.descending { "DESC" } else { "ASC" }
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    qb.push(format!(" ORDER BY {order}"));
+}
+
+fn push_cursor_value(qb: &mut QueryBuilder<'_, Sqlite>, value: CursorValue) {
+    match value {
+        CursorValue::Int(v) => qb.push_bind(v),
+        CursorValue::Text(v) => qb.push_bind(v),
+    };
+}
+
+/// 反序列化可置空字段：缺省为 None（不修改），null 为 Some(None)（清空），有值为 Some(Some(v))
+pub fn deserialize_nullable<'de, T, D>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
@@ -25,6 +25,27 @@ pub enum ZapError {
     /// 任务未完成
     #[error("Task not done: id={0}")]
     TaskNotDone(u32),
+    /// 任务存在未完成的检查项，无法完成
+    #[error("Task has open items: id={0}")]
+    TaskHasOpenItems(u32),
+    /// 检查项不存在（根据 ID 查询未命中）
+    #[error("Task item not found: id={0}")]
+    TaskItemNotFound(u32),
+    /// 检查项数据校验失败（标题为空、排序列表不完整等）
+    #[error("Invalid task item data: {0}")]
+    InvalidTaskItemData(&'static str),
+    /// 每日重点不存在（根据 ID 查询未命中）
+    #[error("Daily focus not found: id={0}")]
+    DailyFocusNotFound(i64),
+    /// 每日重点数据校验失败（内容为空、超出数量上限、排序列表不完整等）
+    #[error("Invalid daily focus data: {0}")]
+    InvalidDailyFocusData(&'static str),
+    /// 周期任务不存在（根据 ID 查询未命中）
+    #[error("Recurring task not found: id={0}")]
+    RecurringTaskNotFound(u32),
+    /// 重复规则无效（不支持的 RRULE 字段、取值越界等）
+    #[error("Invalid recurrence rule: {0}")]
+    InvalidRecurrence(&'static str),
     /// 分类不存在（根据 ID 查询未命中）
     #[error("Category not found: id={0}")]
     CategoryNotFound(u32),
@@ -40,6 +61,57 @@ pub enum ZapError {
     /// 无法删除分类（该分类下存在关联任务）
     #[error("Category has associated tasks")]
     CategoryHasTasks,
+    /// 标签不存在（根据 ID 查询未命中）
+    #[error("Tag not found: id={0}")]
+    TagNotFound(u32),
+    /// 标签数据校验失败（名称为空等）
+    #[error("Invalid tag data: {0}")]
+    InvalidTagData(&'static str),
+    /// 标签名称已存在（不区分大小写）
+    #[error("Tag already exists: {0}")]
+    DuplicateTag(String),
+    /// 客户不存在（根据 ID 查询未命中）
+    #[error("Client not found: id={0}")]
+    ClientNotFound(u32),
+    /// 客户数据校验失败（名称为空等）
+    #[error("Invalid client data: {0}")]
+    InvalidClientData(&'static str),
+    /// 无法删除客户（该客户下存在关联项目）
+    #[error("Client has associated projects")]
+    ClientHasProjects,
+    /// 项目不存在（根据 ID 查询未命中）
+    #[error("Project not found: id={0}")]
+    ProjectNotFound(u32),
+    /// 项目数据校验失败（名称为空、客户不存在等）
+    #[error("Invalid project data: {0}")]
+    InvalidProjectData(&'static str),
+    /// 无法删除项目（该项目下存在关联任务）
+    #[error("Project has associated tasks")]
+    ProjectHasTasks,
+    /// 发票不存在（根据 ID 查询未命中）
+    #[error("Invoice not found: id={0}")]
+    InvoiceNotFound(u32),
+    /// 发票数据校验失败（日期格式错误、无可开票时间等）
+    #[error("Invalid invoice data: {0}")]
+    InvalidInvoiceData(&'static str),
+    /// 目标不存在（根据 ID 查询未命中）
+    #[error("Goal not found: id={0}")]
+    GoalNotFound(u32),
+    /// 目标数据校验失败（作用范围不唯一、时长非正数等）
+    #[error("Invalid goal data: {0}")]
+    InvalidGoalData(&'static str),
+    /// 统计查询参数无效（日期格式错误、范围颠倒等）
+    #[error("Invalid stats query: {0}")]
+    InvalidStatsQuery(&'static str),
+    /// 分页游标无效（格式错误或与当前排序方式不匹配）
+    #[error("Invalid cursor: {0}")]
+    InvalidCursor(&'static str),
+    /// 配置项校验失败（取值不在允许范围内等）
+    #[error("Invalid setting: {0}")]
+    InvalidSetting(&'static str),
+    /// 配置序列化/反序列化失败
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
     /// 文件/IO 操作失败
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
@@ -0,0 +1,75 @@
+use std::{thread, time::Duration};
+
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::{
+    commands::{
+        daily_focus::rollover_daily_focus_impl, materialize_recurring_tasks_impl,
+        take_estimate_overruns_impl, take_goal_alerts_impl,
+    },
+    sqlite::Db,
+};
+
+/// 后台任务的执行间隔
+const TICK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// 运行中任务超过预估用时的事件，载荷为 `EstimateOverrun`
+pub const ESTIMATE_OVERRUN_EVENT: &str = "task-estimate-overrun";
+
+/// 目标达成或上限即将/已经超出的事件，载荷为 `GoalProgress`，每个目标每个周期一次
+pub const GOAL_ALERT_EVENT: &str = "goal-alert";
+
+/// 启动后台任务线程，定期执行周期性维护工作
+pub fn start(app: &AppHandle) {
+    let app = app.clone();
+    thread::spawn(move || {
+        loop {
+            tick(&app);
+            thread::sleep(TICK_INTERVAL);
+        }
+    });
+}
+
+fn tick(app: &AppHandle) {
+    let db = app.state::<Db>();
+    let today = chrono::Local::now().date_naive();
+
+    match tauri::async_runtime::block_on(materialize_recurring_tasks_impl(&db.pool, today)) {
+        Ok(created) if !created.is_empty() => {
+            log::info!("materialized recurring task instances: {created:?}");
+        }
+        Ok(_) => {}
+        Err(e) => log::error!("failed to materialize recurring tasks: {e}"),
+    }
+
+    // 新的一天首次运行时顺延未完成的每日重点
+    match tauri::async_runtime::block_on(rollover_daily_focus_impl(&db.pool, today)) {
+        Ok(carried) if !carried.is_empty() => {
+            log::info!("carried over {} daily focus items", carried.len());
+        }
+        Ok(_) => {}
+        Err(e) => log::error!("failed to roll over daily focus: {e}"),
+    }
+
+    match tauri::async_runtime::block_on(take_estimate_overruns_impl(&db.pool)) {
+        Ok(overruns) => {
+            for overrun in overruns {
+                if let Err(e) = app.emit(ESTIMATE_OVERRUN_EVENT, overrun) {
+                    log::error!("failed to emit estimate overrun event: {e}");
+                }
+            }
+        }
+        Err(e) => log::error!("failed to check estimate overruns: {e}"),
+    }
+
+    match tauri::async_runtime::block_on(take_goal_alerts_impl(&db.pool)) {
+        Ok(alerts) => {
+            for alert in alerts {
+                if let Err(e) = app.emit(GOAL_ALERT_EVENT, alert) {
+                    log::error!("failed to emit goal alert event: {e}");
+                }
+            }
+        }
+        Err(e) => log::error!("failed to check goal alerts: {e}"),
+    }
+}
@@ -1,5 +1,6 @@
 pub mod commands;
 mod error;
+mod jobs;
 mod sqlite;
 mod tray;
 use std::sync::Mutex;
@@ -29,22 +30,95 @@ pub fn run() {
             });
             tray::init_tray(app)?;
             sqlite::set_db(app).map_err(|e| e.to_string())?;
+            jobs::start(app.handle());
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             commands::add_task,
+            commands::update_task,
             commands::list_categories,
+            commands::set_category_rate,
+            commands::list_tags,
+            commands::create_tag,
+            commands::update_tag,
+            commands::delete_tag,
+            commands::list_task_tags,
+            commands::set_task_tags,
+            commands::list_clients,
+            commands::create_client,
+            commands::update_client,
+            commands::delete_client,
+            commands::list_projects,
+            commands::create_project,
+            commands::update_project,
+            commands::delete_project,
             commands::list_tasks,
+            commands::reorder_tasks,
+            commands::count_overdue_tasks,
+            commands::search,
+            commands::list_recurring_tasks,
+            commands::create_recurring_task,
+            commands::set_recurring_task_active,
+            commands::delete_recurring_task,
+            commands::get_recurring_task_stats,
             commands::start_task,
             commands::stop_task,
             commands::finish_task,
             commands::toggle_task_done,
+            commands::list_time_entries,
+            commands::set_session_kind,
+            commands::rebuild_task_day_totals,
+            commands::check_task_day_totals,
+            commands::list_goals,
+            commands::create_goal,
+            commands::update_goal,
+            commands::delete_goal,
+            commands::get_goal_progress,
+            commands::list_task_items,
+            commands::add_task_item,
+            commands::update_task_item,
+            commands::delete_task_item,
+            commands::reorder_task_items,
+            commands::get_open_items_policy,
+            commands::set_open_items_policy,
+            commands::set_time_entry_billable,
+            commands::generate_invoice,
+            commands::list_invoices,
+            commands::get_invoice,
+            commands::void_invoice,
+            commands::export_invoice,
+            commands::get_currency,
+            commands::set_currency,
+            commands::get_stats,
+            commands::get_hourly_stats,
             commands::get_today_stats,
             commands::get_week_stats,
             commands::get_month_stats,
+            commands::get_year_stats,
+            commands::get_range_stats,
+            commands::get_tag_stats,
+            commands::get_estimate_accuracy,
+            commands::get_rounding_policy,
+            commands::set_rounding_policy,
+            commands::get_focus_policy,
+            commands::set_focus_policy,
+            commands::get_streaks,
             commands::get_daily_focus,
             commands::upsert_daily_focus,
             commands::toggle_daily_focus,
+            commands::list_daily_focus,
+            commands::add_daily_focus,
+            commands::update_daily_focus,
+            commands::delete_daily_focus,
+            commands::reorder_daily_focus,
+            commands::start_daily_focus,
+            commands::rollover_daily_focus,
+            commands::get_rollover_policy,
+            commands::set_rollover_policy,
+            commands::get_daily_focus_history,
+            commands::get_weekly_focus_review,
+            commands::get_daily_focus_policy,
+            commands::set_daily_focus_policy,
         ])
         .build(tauri::generate_context!())
         .expect("error while running tauri application")
@@ -0,0 +1,80 @@
+mod common;
+use sqlx::SqlitePool;
+use zap_lib::commands::{self, SaveClientRequest, SaveProjectRequest, StatsDateQuery};
+
+#[test]
+fn test_earned_amount_rounding() {
+    assert_eq!(commands::earned_amount(3600, 10000), 10000);
+    assert_eq!(commands::earned_amount(90, 10000), 250);
+    assert_eq!(commands::earned_amount(1, 1800), 1);
+    assert_eq!(commands::earned_amount(1, 1799), 0);
+    assert_eq!(commands::earned_amount(0, 10000), 0);
+}
+
+#[tokio::test]
+async fn test_billable_earnings_in_stats() {
+    let pool: SqlitePool = common::setup_memory_db()
+        .await
+        .expect("Failed to setup test database");
+
+    let client = commands::create_client_impl(
+        &pool,
+        SaveClientRequest {
+            name: "计费客户".to_string(),
+        },
+    )
+    .await
+    .expect("Failed to create client");
+    let project = commands::create_project_impl(
+        &pool,
+        SaveProjectRequest {
+            name: "计费项目".to_string(),
+            client_id: Some(client.id),
+            hourly_rate: Some(6000),
+        },
+    )
+    .await
+    .expect("Failed to create project");
+
+    // 项目费率 60.00/h，任务费率 120.00/h 覆盖项目费率
+    for (title, rate) in [("项目费率", None), ("任务费率", Some(12000))] {
+        let mut builder = commands::CreateTaskRequestBuilder::default();
+        builder.title(title).project_id(project.id).billable(true);
+        if let Some(rate) = rate {
+            builder.hourly_rate(rate);
+        }
+        commands::add_task_impl(&pool, builder.build().unwrap())
+            .await
+            .expect("Failed to add task");
+    }
+
+    sqlx::query(
+        "INSERT INTO time_entries (task_id, started_at, ended_at, duration_seconds, billable)
+         SELECT id, '2025-03-10 09:00:00', '2025-03-10 09:30:00', 1800,
+                CASE WHEN title = '任务费率' THEN NULL ELSE 1 END
+         FROM tasks WHERE project_id = ?
+         UNION ALL
+         SELECT id, '2025-03-10 10:00:00', '2025-03-10 10:10:00', 600, 0
+         FROM tasks WHERE project_id = ? AND title = '任务费率'",
+    )
+    .bind(project.id)
+    .bind(project.id)
+    .execute(&pool)
+    .await
+    .expect("Failed to insert time entries");
+
+    let stats = commands::get_today_stats_impl(
+        &pool,
+        StatsDateQuery {
+            start_date: Some("2025-03-10".to_string()),
+            client_id: Some(client.id),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Failed to get day stats");
+
+    assert_eq!(stats.total_seconds, 4200);
+    assert_eq!(stats.billable_seconds, 3600);
+    assert_eq!(stats.earned_amount, 3000 + 6000);
+}
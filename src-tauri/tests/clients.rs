@@ -0,0 +1,126 @@
+mod common;
+use sqlx::SqlitePool;
+use zap_lib::commands::{self, SaveClientRequest, SaveProjectRequest};
+
+#[tokio::test]
+async fn test_client_project_task_hierarchy() {
+    let pool: SqlitePool = common::setup_memory_db()
+        .await
+        .expect("Failed to setup test database");
+
+    let client = commands::create_client_impl(
+        &pool,
+        SaveClientRequest {
+            name: "客户 A".to_string(),
+        },
+    )
+    .await
+    .expect("Failed to create client");
+
+    let project = commands::create_project_impl(
+        &pool,
+        SaveProjectRequest {
+            name: "项目 X".to_string(),
+            client_id: Some(client.id),
+            hourly_rate: None,
+        },
+    )
+    .await
+    .expect("Failed to create project");
+    assert_eq!(project.client_name.as_deref(), Some(client.name.as_str()));
+
+    let req = commands::CreateTaskRequestBuilder::default()
+        .title("项目任务")
+        .project_id(project.id)
+        .build()
+        .unwrap();
+    commands::add_task_impl(&pool, req)
+        .await
+        .expect("Failed to add task");
+
+    let query = commands::TaskQueryBuilder::default()
+        .client_id(client.id)
+        .build()
+        .unwrap();
+    let tasks = commands::list_tasks_impl(&pool, query)
+        .await
+        .expect("Failed to list tasks by client");
+    assert_eq!(tasks.total, 1);
+
+    assert!(
+        commands::delete_client_impl(&pool, client.id)
+            .await
+            .is_err()
+    );
+    assert!(
+        commands::delete_project_impl(&pool, project.id)
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn test_create_project_with_unknown_client() {
+    let pool: SqlitePool = common::setup_memory_db()
+        .await
+        .expect("Failed to setup test database");
+
+    let res = commands::create_project_impl(
+        &pool,
+        SaveProjectRequest {
+            name: "孤儿项目".to_string(),
+            client_id: Some(u32::MAX),
+            hourly_rate: None,
+        },
+    )
+    .await;
+    assert!(res.is_err());
+}
+
+#[tokio::test]
+async fn test_duplicate_project_without_client() {
+    let pool: SqlitePool = common::setup_memory_db()
+        .await
+        .expect("Failed to setup test database");
+
+    let internal = |name: &str| SaveProjectRequest {
+        name: name.to_string(),
+        client_id: None,
+        hourly_rate: None,
+    };
+    commands::create_project_impl(&pool, internal("内部工具"))
+        .await
+        .expect("Failed to create project");
+    let other = commands::create_project_impl(&pool, internal("招聘"))
+        .await
+        .expect("Failed to create project");
+
+    // 未关联客户的项目同样不能重名
+    let err = commands::create_project_impl(&pool, internal("内部工具"))
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Invalid project data: project name already exists"
+    );
+    assert!(
+        commands::update_project_impl(&pool, other.id, internal("内部工具"))
+            .await
+            .is_err()
+    );
+
+    // 关联客户后可与未关联客户的项目同名
+    let client = commands::create_client_impl(
+        &pool,
+        SaveClientRequest {
+            name: "外部客户".to_string(),
+        },
+    )
+    .await
+    .expect("Failed to create client");
+    let mut req = internal("内部工具");
+    req.client_id = Some(client.id);
+    commands::create_project_impl(&pool, req)
+        .await
+        .expect("Failed to create client project");
+}
@@ -3,7 +3,9 @@ use sqlx::{
     SqlitePool,
 };
 use std::str::FromStr;
+use zap_lib::commands::{add_task_impl, CreateTaskRequestBuilder};
 
+#[allow(dead_code)]
 pub async fn setup_test_db() -> Result<SqlitePool, sqlx::Error> {
     let options =
         SqliteConnectOptions::from_str("sqlite:///home/carole/.local/share/com.tauri.dev/zap.db")?
@@ -19,3 +21,68 @@ pub async fn setup_test_db() -> Result<SqlitePool, sqlx::Error> {
     sqlx::migrate!("./migrations").run(&pool).await?;
     Ok(pool)
 }
+
+/// 内存数据库（单连接），用于需要隔离数据或大量合成数据的测试
+#[allow(dead_code)]
+pub async fn setup_memory_db() -> Result<SqlitePool, sqlx::Error> {
+    let options = SqliteConnectOptions::from_str("sqlite::memory:")?.foreign_keys(true);
+
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect_with(options)
+        .await?;
+
+    sqlx::migrate!("./migrations").run(&pool).await?;
+    Ok(pool)
+}
+
+/// 添加任务并返回任务id，configure 用于设置标题以外的字段
+#[allow(dead_code)]
+pub async fn create_task(
+    pool: &SqlitePool,
+    title: &str,
+    configure: impl FnOnce(&mut CreateTaskRequestBuilder),
+) -> u32 {
+    let mut builder = CreateTaskRequestBuilder::default();
+    builder.title(title);
+    configure(&mut builder);
+    add_task_impl(pool, builder.build().unwrap())
+        .await
+        .expect("Failed to add task");
+
+    sqlx::query_scalar("SELECT MAX(id) FROM tasks WHERE title = ?")
+        .bind(title)
+        .fetch_one(pool)
+        .await
+        .expect("Failed to query task id")
+}
+
+/// 插入一条已结束的计时记录并返回记录id，started_at 为 UTC 时间 YYYY-MM-DD HH:MM:SS
+#[allow(dead_code)]
+pub async fn insert_entry(pool: &SqlitePool, task_id: u32, started_at: &str, seconds: i64) -> i64 {
+    sqlx::query(
+        "INSERT INTO time_entries (task_id, started_at, ended_at, duration_seconds)
+         VALUES (?, ?, datetime(?, '+' || ? || ' seconds'), ?)",
+    )
+    .bind(task_id)
+    .bind(started_at)
+    .bind(started_at)
+    .bind(seconds)
+    .bind(seconds)
+    .execute(pool)
+    .await
+    .expect("Failed to insert time entry")
+    .last_insert_rowid()
+}
+
+/// 将本地时间 YYYY-MM-DD HH:MM:SS 转换为数据库使用的 UTC 时间字符串
+#[allow(dead_code)]
+pub fn local_to_utc(datetime: &str) -> String {
+    chrono::NaiveDateTime::parse_from_str(datetime, "%Y-%m-%d %H:%M:%S")
+        .unwrap()
+        .and_local_timezone(chrono::Local)
+        .unwrap()
+        .naive_utc()
+        .format("%Y-%m-%d %H:%M:%S")
+        .to_string()
+}
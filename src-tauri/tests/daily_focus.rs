@@ -0,0 +1,436 @@
+mod common;
+use sqlx::SqlitePool;
+use zap_lib::commands::{
+    self,
+    daily_focus::{self, AddDailyFocusRequest, DailyFocusPolicy, UpdateDailyFocusRequest},
+};
+
+const DATE: &str = "2026-03-02";
+
+fn add(content: &str) -> AddDailyFocusRequest {
+    AddDailyFocusRequest {
+        date: DATE.to_string(),
+        content: content.to_string(),
+        task_id: None,
+    }
+}
+
+#[tokio::test]
+async fn test_daily_focus_list_and_reorder() {
+    let pool = common::setup_memory_db()
+        .await
+        .expect("Failed to setup test database");
+
+    // 默认一条主重点加三条次要事项
+    let mut ids = Vec::new();
+    for content in ["主重点", "次要 1", "次要 2", "次要 3"] {
+        let item = daily_focus::add_daily_focus_impl(&pool, add(content))
+            .await
+            .expect("Failed to add daily focus");
+        ids.push(item.id);
+    }
+    assert!(
+        daily_focus::add_daily_focus_impl(&pool, add("超出"))
+            .await
+            .is_err()
+    );
+    assert!(
+        daily_focus::add_daily_focus_impl(&pool, add("  "))
+            .await
+            .is_err()
+    );
+
+    let items = daily_focus::list_daily_focus_impl(&pool, DATE.to_string())
+        .await
+        .unwrap();
+    assert_eq!(
+        items.iter().map(|i| i.position).collect::<Vec<_>>(),
+        vec![0, 1, 2, 3]
+    );
+
+    // 排第一的成为主重点，upsert 修改的也是它
+    let reordered = vec![ids[2], ids[0], ids[3], ids[1]];
+    let items = daily_focus::reorder_daily_focus_impl(&pool, DATE.to_string(), reordered.clone())
+        .await
+        .expect("Failed to reorder daily focus");
+    assert_eq!(items.iter().map(|i| i.id).collect::<Vec<_>>(), reordered);
+    let highlight = daily_focus::get_daily_focus_impl(&pool, DATE.to_string())
+        .await
+        .unwrap()
+        .expect("Highlight should exist");
+    assert_eq!(highlight.content, "次要 2");
+    assert!(
+        daily_focus::reorder_daily_focus_impl(&pool, DATE.to_string(), vec![ids[0], ids[1]])
+            .await
+            .is_err()
+    );
+
+    let updated = daily_focus::update_daily_focus_impl(
+        &pool,
+        ids[1],
+        UpdateDailyFocusRequest {
+            content: Some("改写".to_string()),
+            is_done: Some(true),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Failed to update daily focus");
+    assert_eq!(updated.content, "改写");
+    assert_eq!(updated.is_done, 1);
+
+    daily_focus::delete_daily_focus_impl(&pool, ids[3])
+        .await
+        .expect("Failed to delete daily focus");
+    assert!(
+        daily_focus::delete_daily_focus_impl(&pool, ids[3])
+            .await
+            .is_err()
+    );
+    assert!(
+        daily_focus::toggle_daily_focus_impl(&pool, ids[3])
+            .await
+            .is_err()
+    );
+
+    // 放宽上限后可继续添加
+    daily_focus::set_daily_focus_policy_impl(
+        &pool,
+        DailyFocusPolicy {
+            max_secondary_items: None,
+        },
+    )
+    .await
+    .unwrap();
+    daily_focus::add_daily_focus_impl(&pool, add("新增 1"))
+        .await
+        .unwrap();
+    daily_focus::add_daily_focus_impl(&pool, add("新增 2"))
+        .await
+        .unwrap();
+    let items = daily_focus::list_daily_focus_impl(&pool, DATE.to_string())
+        .await
+        .unwrap();
+    assert_eq!(items.len(), 5);
+    assert_eq!(items.last().unwrap().content, "新增 2");
+}
+
+async fn is_today_focus(pool: &SqlitePool, task_id: u32) -> bool {
+    let query = commands::TaskQueryBuilder::default()
+        .include_finished(true)
+        .build()
+        .unwrap();
+    let tasks = commands::list_tasks_impl(pool, query)
+        .await
+        .expect("Failed to list tasks");
+    let tasks = serde_json::to_value(&tasks.data).unwrap();
+    tasks
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|t| t["task_id"] == task_id)
+        .map(|t| t["is_today_focus"] == true)
+        .expect("Task not listed")
+}
+
+#[tokio::test]
+async fn test_daily_focus_linked_task() {
+    let pool = common::setup_memory_db()
+        .await
+        .expect("Failed to setup test database");
+    let task_id = common::create_task(&pool, "写周报", |_| {}).await;
+    let today = chrono::Local::now().date_naive().to_string();
+
+    let unlinked = daily_focus::add_daily_focus_impl(
+        &pool,
+        AddDailyFocusRequest {
+            date: today.clone(),
+            content: "散步".to_string(),
+            task_id: None,
+        },
+    )
+    .await
+    .unwrap();
+    assert!(
+        daily_focus::start_daily_focus_impl(&pool, unlinked.id)
+            .await
+            .is_err()
+    );
+
+    // 关联任务时内容默认为任务标题，任务随之成为今日重点
+    let focus = daily_focus::add_daily_focus_impl(
+        &pool,
+        AddDailyFocusRequest {
+            date: today.clone(),
+            content: String::new(),
+            task_id: Some(task_id),
+        },
+    )
+    .await
+    .expect("Failed to add linked focus");
+    assert_eq!(focus.content, "写周报");
+    assert_eq!(focus.task_id, Some(task_id));
+    assert!(is_today_focus(&pool, task_id).await);
+
+    daily_focus::start_daily_focus_impl(&pool, focus.id)
+        .await
+        .expect("Failed to start focus task");
+    let running: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM time_entries WHERE task_id = ? AND ended_at IS NULL",
+    )
+    .bind(task_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(running, 1);
+
+    sqlx::query(
+        "UPDATE time_entries SET started_at = datetime('now', '-600 seconds') WHERE task_id = ?",
+    )
+    .bind(task_id)
+    .execute(&pool)
+    .await
+    .unwrap();
+    let items = daily_focus::list_daily_focus_impl(&pool, today.clone())
+        .await
+        .unwrap();
+    assert!(items[1].tracked_seconds >= 600);
+    assert_eq!(items[0].tracked_seconds, 0);
+
+    let yesterday = (chrono::Local::now().date_naive() - chrono::Duration::days(1)).to_string();
+    daily_focus::add_daily_focus_impl(
+        &pool,
+        AddDailyFocusRequest {
+            date: yesterday.clone(),
+            content: String::new(),
+            task_id: Some(task_id),
+        },
+    )
+    .await
+    .expect("Failed to add past linked focus");
+
+    // 完成任务时今日重点自动勾掉，往日未完成的重点不受影响
+    commands::finish_task_impl(&pool, task_id)
+        .await
+        .expect("Failed to finish task");
+    let items = daily_focus::list_daily_focus_impl(&pool, today.clone())
+        .await
+        .unwrap();
+    assert_eq!(items[1].is_done, 1);
+    assert_eq!(items[0].is_done, 0);
+    let items = daily_focus::list_daily_focus_impl(&pool, yesterday)
+        .await
+        .unwrap();
+    assert_eq!(items[0].is_done, 0);
+
+    // 解除今天的关联后任务不再是今日重点，往日的关联不计入
+    daily_focus::update_daily_focus_impl(
+        &pool,
+        focus.id,
+        UpdateDailyFocusRequest {
+            task_id: Some(None),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    assert!(!is_today_focus(&pool, task_id).await);
+}
+
+#[tokio::test]
+async fn test_daily_focus_rollover() {
+    let pool = common::setup_memory_db()
+        .await
+        .expect("Failed to setup test database");
+    let day = |d: &str| chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d").unwrap();
+
+    let mut ids = Vec::new();
+    for content in ["主重点", "次要 1", "次要 2"] {
+        ids.push(
+            daily_focus::add_daily_focus_impl(&pool, add(content))
+                .await
+                .unwrap()
+                .id,
+        );
+    }
+    daily_focus::toggle_daily_focus_impl(&pool, ids[1])
+        .await
+        .unwrap();
+
+    // 默认不顺延
+    assert!(
+        daily_focus::rollover_daily_focus_impl(&pool, day("2026-03-03"))
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    daily_focus::set_rollover_policy_impl(&pool, daily_focus::RolloverPolicy::Copy)
+        .await
+        .unwrap();
+    let copied = daily_focus::rollover_daily_focus_impl(&pool, day("2026-03-03"))
+        .await
+        .expect("Failed to roll over daily focus");
+    assert_eq!(
+        copied
+            .iter()
+            .map(|i| i.content.as_str())
+            .collect::<Vec<_>>(),
+        vec!["主重点", "次要 2"]
+    );
+    assert!(copied.iter().all(|i| i.focus_date == "2026-03-03"
+        && i.carried_from.as_deref() == Some(DATE)
+        && i.carry_count == 1));
+    assert_eq!(
+        daily_focus::list_daily_focus_impl(&pool, DATE.to_string())
+            .await
+            .unwrap()
+            .len(),
+        3
+    );
+
+    // 同一天只执行一次
+    assert!(
+        daily_focus::rollover_daily_focus_impl(&pool, day("2026-03-03"))
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    // 3 日完成一条，另一条移动到 5 日
+    daily_focus::toggle_daily_focus_impl(&pool, copied[0].id)
+        .await
+        .unwrap();
+    daily_focus::set_rollover_policy_impl(&pool, daily_focus::RolloverPolicy::Move)
+        .await
+        .unwrap();
+    let moved = daily_focus::rollover_daily_focus_impl(&pool, day("2026-03-05"))
+        .await
+        .unwrap();
+    assert_eq!(moved.len(), 1);
+    assert_ne!(moved[0].id, copied[1].id);
+    assert_eq!(moved[0].content, "次要 2");
+    assert_eq!(moved[0].focus_date, "2026-03-05");
+    assert_eq!(moved[0].position, 0);
+    assert_eq!(moved[0].carry_count, 2);
+    assert_eq!(moved[0].carried_from.as_deref(), Some(DATE));
+    assert!(moved[0].moved_to.is_none());
+
+    // 原条目仍留在原日期并保持未完成，原日期不会被当作全部完成
+    let sources = daily_focus::list_daily_focus_impl(&pool, "2026-03-03".to_string())
+        .await
+        .unwrap();
+    assert_eq!(sources.len(), 2);
+    assert_eq!(sources[1].is_done, 0);
+    assert_eq!(sources[1].moved_to.as_deref(), Some("2026-03-05"));
+    let streaks = commands::get_streaks_impl(
+        &pool,
+        commands::StreakQuery {
+            kind: commands::StreakKind::FocusDone,
+            date: Some("2026-03-04".to_string()),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(streaks.longest, 0);
+}
+
+#[tokio::test]
+async fn test_daily_focus_history_and_weekly_review() {
+    let pool = common::setup_memory_db()
+        .await
+        .expect("Failed to setup test database");
+    let day = |d: &str| chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d").unwrap();
+
+    let mut ids = Vec::new();
+    for content in ["主重点", "次要 1", "次要 2"] {
+        ids.push(
+            daily_focus::add_daily_focus_impl(&pool, add(content))
+                .await
+                .unwrap()
+                .id,
+        );
+    }
+    daily_focus::toggle_daily_focus_impl(&pool, ids[1])
+        .await
+        .unwrap();
+    let reflected = daily_focus::update_daily_focus_impl(
+        &pool,
+        ids[0],
+        UpdateDailyFocusRequest {
+            reflection: Some(Some("  被会议打断 ".to_string())),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Failed to save reflection");
+    assert_eq!(reflected.reflection.as_deref(), Some("被会议打断"));
+    assert!(
+        daily_focus::update_daily_focus_impl(
+            &pool,
+            ids[0],
+            UpdateDailyFocusRequest {
+                reflection: Some(Some("长".repeat(501))),
+                ..Default::default()
+            },
+        )
+        .await
+        .is_err()
+    );
+
+    // 连续两天复制顺延，最后一天完成其中一条
+    daily_focus::set_rollover_policy_impl(&pool, daily_focus::RolloverPolicy::Copy)
+        .await
+        .unwrap();
+    daily_focus::rollover_daily_focus_impl(&pool, day("2026-03-03"))
+        .await
+        .unwrap();
+    let carried = daily_focus::rollover_daily_focus_impl(&pool, day("2026-03-04"))
+        .await
+        .unwrap();
+    daily_focus::toggle_daily_focus_impl(&pool, carried[1].id)
+        .await
+        .unwrap();
+
+    let history = daily_focus::get_daily_focus_history_impl(
+        &pool,
+        daily_focus::DailyFocusHistoryQuery {
+            start_date: Some("2026-03-01".to_string()),
+            end_date: Some("2026-03-05".to_string()),
+        },
+    )
+    .await
+    .expect("Failed to get daily focus history");
+    assert_eq!(
+        history.iter().map(|d| d.date.as_str()).collect::<Vec<_>>(),
+        vec!["2026-03-04", "2026-03-03", DATE]
+    );
+    assert_eq!(history[0].done_count, 1);
+    assert!(!history[0].completed);
+    assert_eq!(history[2].items.len(), 3);
+    assert_eq!(
+        history[2].items[0].reflection.as_deref(),
+        Some("被会议打断")
+    );
+    assert!(history[1].items.iter().all(|i| i.reflection.is_none()));
+
+    let review = daily_focus::get_weekly_focus_review_impl(&pool, Some("2026-03-06".to_string()))
+        .await
+        .expect("Failed to get weekly review");
+    assert_eq!(review.start_date, DATE);
+    assert_eq!(review.end_date, "2026-03-08");
+    assert_eq!(review.total_items, 7);
+    assert_eq!(review.done_items, 2);
+    assert_eq!(review.completion_rate, 28.6);
+    assert_eq!(review.planned_days, 3);
+    assert_eq!(review.completed_days, 0);
+    assert_eq!(
+        review
+            .most_postponed
+            .iter()
+            .map(|i| (i.content.as_str(), i.carry_count))
+            .collect::<Vec<_>>(),
+        vec![("主重点", 2), ("次要 2", 2)]
+    );
+}
@@ -0,0 +1,315 @@
+mod common;
+use zap_lib::commands::{
+    self, GoalKind, GoalPeriod, GoalStatus, RoundingMode, RoundingPolicy, RoundingScope,
+    SaveGoalRequest,
+};
+
+fn goal(kind: GoalKind, period: GoalPeriod, target_seconds: i64) -> SaveGoalRequest {
+    SaveGoalRequest {
+        kind,
+        period,
+        target_seconds,
+        category_id: None,
+        tag_id: None,
+        task_id: None,
+        is_active: None,
+    }
+}
+
+#[tokio::test]
+async fn test_goal_validation_and_crud() {
+    let pool = common::setup_memory_db()
+        .await
+        .expect("Failed to setup test database");
+    let task_id = common::create_task(&pool, "目标任务", |_| {}).await;
+
+    let no_scope =
+        commands::create_goal_impl(&pool, goal(GoalKind::Target, GoalPeriod::Week, 3600)).await;
+    assert!(no_scope.is_err());
+
+    let mut two_scopes = goal(GoalKind::Target, GoalPeriod::Week, 3600);
+    two_scopes.task_id = Some(task_id);
+    two_scopes.category_id = Some(1);
+    assert!(commands::create_goal_impl(&pool, two_scopes).await.is_err());
+
+    let mut missing = goal(GoalKind::Target, GoalPeriod::Week, 3600);
+    missing.tag_id = Some(999);
+    assert!(commands::create_goal_impl(&pool, missing).await.is_err());
+
+    let mut req = goal(GoalKind::Target, GoalPeriod::Week, 0);
+    req.task_id = Some(task_id);
+    assert!(commands::create_goal_impl(&pool, req).await.is_err());
+
+    let mut req = goal(GoalKind::Target, GoalPeriod::Week, 3600);
+    req.task_id = Some(task_id);
+    let created = commands::create_goal_impl(&pool, req)
+        .await
+        .expect("Failed to create goal");
+    assert_eq!(created.scope_name, "目标任务");
+    assert!(created.is_active);
+
+    let mut req = goal(GoalKind::Limit, GoalPeriod::Day, 1800);
+    req.task_id = Some(task_id);
+    req.is_active = Some(false);
+    let updated = commands::update_goal_impl(&pool, created.id, req)
+        .await
+        .expect("Failed to update goal");
+    assert_eq!(updated.kind, GoalKind::Limit);
+    assert_eq!(updated.period, GoalPeriod::Day);
+    assert!(!updated.is_active);
+
+    // 停用的目标不计算进度
+    let progress = commands::get_goal_progress_impl(&pool, None)
+        .await
+        .expect("Failed to get goal progress");
+    assert!(progress.is_empty());
+
+    commands::delete_goal_impl(&pool, created.id)
+        .await
+        .expect("Failed to delete goal");
+    assert!(commands::delete_goal_impl(&pool, created.id).await.is_err());
+    assert!(commands::list_goals_impl(&pool).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_goal_progress_by_scope_and_period() {
+    let pool = common::setup_memory_db()
+        .await
+        .expect("Failed to setup test database");
+
+    let category_id: u32 = sqlx::query_scalar("SELECT MIN(id) FROM categories")
+        .fetch_one(&pool)
+        .await
+        .expect("Failed to query category");
+    let study = common::create_task(&pool, "学习", |b| {
+        b.category_id(category_id);
+    })
+    .await;
+    let meeting = common::create_task(&pool, "会议", |_| {}).await;
+    let tag = commands::create_tag_impl(
+        &pool,
+        commands::SaveTagRequest {
+            name: "meeting".to_string(),
+            color: None,
+        },
+    )
+    .await
+    .expect("Failed to create tag");
+    commands::set_task_tags_impl(&pool, meeting, vec![tag.id])
+        .await
+        .expect("Failed to set task tags");
+
+    // 2026-03-02 为周一；上周日的记录不计入本周
+    common::insert_entry(&pool, study, "2026-03-01 10:00:00", 7200).await;
+    common::insert_entry(&pool, study, "2026-03-02 10:00:00", 3600).await;
+    common::insert_entry(&pool, study, "2026-03-04 10:00:00", 5400).await;
+    common::insert_entry(&pool, meeting, "2026-03-03 09:00:00", 9000).await;
+    common::insert_entry(&pool, meeting, "2026-03-04 09:00:00", 7200).await;
+
+    let mut study_goal = goal(GoalKind::Target, GoalPeriod::Week, 10 * 3600);
+    study_goal.category_id = Some(category_id);
+    commands::create_goal_impl(&pool, study_goal).await.unwrap();
+
+    let mut meeting_limit = goal(GoalKind::Limit, GoalPeriod::Week, 5 * 3600);
+    meeting_limit.tag_id = Some(tag.id);
+    commands::create_goal_impl(&pool, meeting_limit)
+        .await
+        .unwrap();
+
+    let mut daily_limit = goal(GoalKind::Limit, GoalPeriod::Day, 10000);
+    daily_limit.task_id = Some(meeting);
+    commands::create_goal_impl(&pool, daily_limit)
+        .await
+        .unwrap();
+
+    let mut monthly_target = goal(GoalKind::Target, GoalPeriod::Month, 5 * 3600);
+    monthly_target.task_id = Some(study);
+    commands::create_goal_impl(&pool, monthly_target)
+        .await
+        .unwrap();
+
+    let progress = commands::get_goal_progress_impl(&pool, Some("2026-03-04".to_string()))
+        .await
+        .expect("Failed to get goal progress");
+    assert_eq!(progress.len(), 4);
+
+    let study_week = &progress[0];
+    assert_eq!(study_week.period_start, "2026-03-02");
+    assert_eq!(study_week.period_end, "2026-03-08");
+    assert_eq!(study_week.tracked_seconds, 9000);
+    assert_eq!(study_week.remaining_seconds, 27000);
+    assert_eq!(study_week.percent, 25.0);
+    assert_eq!(study_week.status, GoalStatus::InProgress);
+
+    let meeting_week = &progress[1];
+    assert_eq!(meeting_week.tracked_seconds, 16200);
+    assert_eq!(meeting_week.percent, 90.0);
+    assert_eq!(meeting_week.status, GoalStatus::Approaching);
+
+    let meeting_day = &progress[2];
+    assert_eq!(meeting_day.period_start, "2026-03-04");
+    assert_eq!(meeting_day.tracked_seconds, 7200);
+    assert_eq!(meeting_day.status, GoalStatus::UnderLimit);
+
+    let study_month = &progress[3];
+    assert_eq!(study_month.period_start, "2026-03-01");
+    assert_eq!(study_month.period_end, "2026-03-31");
+    assert_eq!(study_month.tracked_seconds, 16200);
+    assert_eq!(study_month.remaining_seconds, 1800);
+
+    common::insert_entry(&pool, meeting, "2026-03-05 09:00:00", 3600).await;
+    common::insert_entry(&pool, study, "2026-03-05 10:00:00", 1800).await;
+    let progress = commands::get_goal_progress_impl(&pool, Some("2026-03-05".to_string()))
+        .await
+        .unwrap();
+    assert_eq!(progress[1].status, GoalStatus::Exceeded);
+    assert_eq!(progress[1].remaining_seconds, 0);
+    assert_eq!(progress[3].status, GoalStatus::Met);
+}
+
+#[tokio::test]
+async fn test_goal_alerts_once_per_period() {
+    let pool = common::setup_memory_db()
+        .await
+        .expect("Failed to setup test database");
+    let task_id = common::create_task(&pool, "提醒", |_| {}).await;
+
+    let mut target = goal(GoalKind::Target, GoalPeriod::Day, 3600);
+    target.task_id = Some(task_id);
+    let target = commands::create_goal_impl(&pool, target).await.unwrap();
+    let mut limit = goal(GoalKind::Limit, GoalPeriod::Day, 36000);
+    limit.task_id = Some(task_id);
+    commands::create_goal_impl(&pool, limit).await.unwrap();
+
+    assert!(
+        commands::take_goal_alerts_impl(&pool)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    let today = chrono::Local::now().date_naive();
+    common::insert_entry(
+        &pool,
+        task_id,
+        &common::local_to_utc(&format!("{today} 00:00:00")),
+        3600,
+    )
+    .await;
+
+    let alerts = commands::take_goal_alerts_impl(&pool).await.unwrap();
+    assert_eq!(alerts.len(), 1);
+    assert_eq!(alerts[0].goal.id, target.id);
+    assert_eq!(alerts[0].status, GoalStatus::Met);
+    assert!(
+        commands::take_goal_alerts_impl(&pool)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    // 修改目标后当前周期可再次提醒
+    let mut req = goal(GoalKind::Target, GoalPeriod::Day, 1800);
+    req.task_id = Some(task_id);
+    commands::update_goal_impl(&pool, target.id, req)
+        .await
+        .unwrap();
+    assert_eq!(
+        commands::take_goal_alerts_impl(&pool).await.unwrap().len(),
+        1
+    );
+}
+
+#[tokio::test]
+async fn test_goal_progress_rounding_and_local_days() {
+    let pool = common::setup_memory_db()
+        .await
+        .expect("Failed to setup test database");
+    let task_id = common::create_task(&pool, "取整", |_| {}).await;
+    commands::set_rounding_policy_impl(
+        &pool,
+        RoundingPolicy {
+            mode: RoundingMode::Up,
+            unit_minutes: 15,
+            scope: RoundingScope::Entry,
+        },
+    )
+    .await
+    .expect("Failed to save rounding policy");
+
+    // 600 秒按 15 分钟向上取整为 900 秒；跨越本地午夜的记录只计入当天部分
+    common::insert_entry(
+        &pool,
+        task_id,
+        &common::local_to_utc("2026-03-04 10:00:00"),
+        600,
+    )
+    .await;
+    common::insert_entry(
+        &pool,
+        task_id,
+        &common::local_to_utc("2026-03-03 23:30:00"),
+        3600,
+    )
+    .await;
+
+    let mut req = goal(GoalKind::Target, GoalPeriod::Day, 3600);
+    req.task_id = Some(task_id);
+    commands::create_goal_impl(&pool, req).await.unwrap();
+
+    let progress = commands::get_goal_progress_impl(&pool, Some("2026-03-04".to_string()))
+        .await
+        .unwrap();
+    assert_eq!(progress[0].tracked_seconds, 2700);
+    let progress = commands::get_goal_progress_impl(&pool, Some("2026-03-03".to_string()))
+        .await
+        .unwrap();
+    assert_eq!(progress[0].tracked_seconds, 1800);
+}
+
+#[tokio::test]
+async fn test_goal_alert_escalates_to_exceeded() {
+    let pool = common::setup_memory_db()
+        .await
+        .expect("Failed to setup test database");
+    let task_id = common::create_task(&pool, "会议", |_| {}).await;
+    let mut limit = goal(GoalKind::Limit, GoalPeriod::Day, 3600);
+    limit.task_id = Some(task_id);
+    commands::create_goal_impl(&pool, limit).await.unwrap();
+
+    let today = chrono::Local::now().date_naive();
+    common::insert_entry(
+        &pool,
+        task_id,
+        &common::local_to_utc(&format!("{today} 00:00:00")),
+        3300,
+    )
+    .await;
+    let alerts = commands::take_goal_alerts_impl(&pool).await.unwrap();
+    assert_eq!(alerts.len(), 1);
+    assert_eq!(alerts[0].status, GoalStatus::Approaching);
+    assert!(
+        commands::take_goal_alerts_impl(&pool)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    // 同一周期内从即将超出升级为已超出时再提醒一次
+    common::insert_entry(
+        &pool,
+        task_id,
+        &common::local_to_utc(&format!("{today} 01:00:00")),
+        600,
+    )
+    .await;
+    let alerts = commands::take_goal_alerts_impl(&pool).await.unwrap();
+    assert_eq!(alerts.len(), 1);
+    assert_eq!(alerts[0].status, GoalStatus::Exceeded);
+    assert!(
+        commands::take_goal_alerts_impl(&pool)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
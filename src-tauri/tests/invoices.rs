@@ -0,0 +1,144 @@
+mod common;
+use sqlx::SqlitePool;
+use zap_lib::commands::{
+    self, GenerateInvoiceRequest, InvoiceFormat, SaveClientRequest, SaveProjectRequest,
+};
+
+#[tokio::test]
+async fn test_generate_invoice_once_per_entry() {
+    let pool: SqlitePool = common::setup_memory_db()
+        .await
+        .expect("Failed to setup test database");
+
+    let client = commands::create_client_impl(
+        &pool,
+        SaveClientRequest {
+            name: "发票客户".to_string(),
+        },
+    )
+    .await
+    .expect("Failed to create client");
+    let project = commands::create_project_impl(
+        &pool,
+        SaveProjectRequest {
+            name: "官网改版".to_string(),
+            client_id: Some(client.id),
+            hourly_rate: Some(10000),
+        },
+    )
+    .await
+    .expect("Failed to create project");
+
+    let req = commands::CreateTaskRequestBuilder::default()
+        .title("首页设计")
+        .project_id(project.id)
+        .billable(true)
+        .build()
+        .unwrap();
+    commands::add_task_impl(&pool, req)
+        .await
+        .expect("Failed to add task");
+
+    sqlx::query(
+        "INSERT INTO time_entries (task_id, started_at, ended_at, duration_seconds)
+         SELECT id, '2026-01-05 09:00:00', '2026-01-05 10:30:00', 5400
+         FROM tasks WHERE project_id = ?",
+    )
+    .bind(project.id)
+    .execute(&pool)
+    .await
+    .expect("Failed to insert time entry");
+
+    let req = || GenerateInvoiceRequest {
+        client_id: client.id,
+        start_date: "2026-01-01".to_string(),
+        end_date: "2026-01-31".to_string(),
+    };
+    let invoice = commands::generate_invoice_impl(&pool, req())
+        .await
+        .expect("Failed to generate invoice");
+    assert_eq!(invoice.lines.len(), 1);
+    assert_eq!(invoice.invoice.total_seconds, 5400);
+    assert_eq!(invoice.invoice.total_amount, 15000);
+    assert!(invoice.invoice.number.starts_with("INV-"));
+
+    // 同一批计时记录不能重复开票
+    assert!(commands::generate_invoice_impl(&pool, req()).await.is_err());
+
+    let markdown =
+        commands::export_invoice_impl(&pool, invoice.invoice.id, InvoiceFormat::Markdown)
+            .await
+            .expect("Failed to export invoice");
+    assert!(markdown.contains("官网改版 / 首页设计"));
+    assert!(markdown.contains("1:30"));
+
+    let html = commands::export_invoice_impl(&pool, invoice.invoice.id, InvoiceFormat::Html)
+        .await
+        .expect("Failed to export invoice");
+    assert!(html.contains(&invoice.invoice.number));
+}
+
+#[tokio::test]
+async fn test_void_invoice_keeps_number() {
+    let pool = common::setup_memory_db()
+        .await
+        .expect("Failed to setup test database");
+
+    let client = commands::create_client_impl(
+        &pool,
+        SaveClientRequest {
+            name: "作废客户".to_string(),
+        },
+    )
+    .await
+    .expect("Failed to create client");
+    let project = commands::create_project_impl(
+        &pool,
+        SaveProjectRequest {
+            name: "维护".to_string(),
+            client_id: Some(client.id),
+            hourly_rate: Some(10000),
+        },
+    )
+    .await
+    .expect("Failed to create project");
+    let task_id = common::create_task(&pool, "巡检", |b| {
+        b.project_id(project.id).billable(true);
+    })
+    .await;
+    common::insert_entry(&pool, task_id, "2026-01-05 09:00:00", 3600).await;
+
+    let req = || GenerateInvoiceRequest {
+        client_id: client.id,
+        start_date: "2026-01-01".to_string(),
+        end_date: "2026-01-31".to_string(),
+    };
+    let first = commands::generate_invoice_impl(&pool, req()).await.unwrap();
+    assert_eq!(first.invoice.number, "INV-00001");
+
+    // 作废后保留发票与编号，计时记录可重新开票，新发票不会复用旧编号
+    let voided = commands::void_invoice_impl(&pool, first.invoice.id)
+        .await
+        .expect("Failed to void invoice");
+    assert!(voided.invoice.voided_at.is_some());
+    assert!(
+        commands::void_invoice_impl(&pool, first.invoice.id)
+            .await
+            .is_err()
+    );
+    let markdown = commands::export_invoice_impl(&pool, first.invoice.id, InvoiceFormat::Markdown)
+        .await
+        .unwrap();
+    assert!(markdown.starts_with("# Invoice INV-00001 (VOID)"));
+
+    let second = commands::generate_invoice_impl(&pool, req()).await.unwrap();
+    assert_eq!(second.invoice.number, "INV-00002");
+    assert_eq!(second.invoice.total_seconds, 3600);
+    assert_eq!(
+        commands::list_invoices_impl(&pool, Some(client.id))
+            .await
+            .unwrap()
+            .len(),
+        2
+    );
+}
@@ -0,0 +1,176 @@
+mod common;
+use std::time::Instant;
+
+use sqlx::SqlitePool;
+use zap_lib::commands::{self, PaginationMode, TaskSort, TimeEntryQuery};
+
+/// 生成合成数据：tasks 个任务，每个任务 entries_per_task 条已结束的计时记录
+async fn seed(pool: &SqlitePool, tasks: i64, entries_per_task: i64) {
+    sqlx::query(
+        "WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n WHERE i < ?)
+         INSERT INTO tasks (title, done, priority, position, created_at)
+         SELECT 'task ' || i, i % 3, i % 4, i, datetime('2025-01-01', '+' || (i / 10) || ' minutes')
+         FROM n",
+    )
+    .bind(tasks)
+    .execute(pool)
+    .await
+    .expect("Failed to seed tasks");
+
+    sqlx::query(
+        "WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n WHERE i < ?)
+         INSERT INTO time_entries (task_id, started_at, ended_at, duration_seconds)
+         SELECT t.id, datetime('2025-01-01', '+' || (t.id * 7 + n.i) || ' minutes'),
+                datetime('2025-01-01', '+' || (t.id * 7 + n.i + 1) || ' minutes'), (t.id * n.i) % 3600
+         FROM tasks t, n",
+    )
+    .bind(entries_per_task)
+    .execute(pool)
+    .await
+    .expect("Failed to seed time entries");
+}
+
+fn task_ids<T: serde::Serialize>(data: &[T]) -> Vec<u64> {
+    serde_json::to_value(data)
+        .unwrap()
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["task_id"].as_u64().unwrap())
+        .collect()
+}
+
+/// 沿游标翻完所有页，返回任务 id 顺序
+async fn walk_cursor(pool: &SqlitePool, sort: TaskSort, page_size: u32) -> Vec<u64> {
+    let mut ids = Vec::new();
+    let mut cursor: Option<String> = None;
+    loop {
+        let mut builder = commands::TaskQueryBuilder::default();
+        builder
+            .sort(sort)
+            .include_finished(true)
+            .pagination(PaginationMode::Cursor)
+            .page_size(page_size);
+        if let Some(ref c) = cursor {
+            builder.cursor(c.as_str());
+        }
+        let page = commands::list_tasks_impl(pool, builder.build().unwrap())
+            .await
+            .expect("Failed to list tasks");
+        assert!(page.cursor_total.is_none());
+        ids.extend(task_ids(&page.data));
+        match page.next_cursor {
+            Some(next) => cursor = Some(next),
+            None => return ids,
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_cursor_pagination_matches_offset() {
+    let pool = common::setup_memory_db()
+        .await
+        .expect("Failed to setup memory database");
+    seed(&pool, 57, 2).await;
+
+    for sort in [
+        TaskSort::Default,
+        TaskSort::Priority,
+        TaskSort::Created,
+        TaskSort::TotalTime,
+        TaskSort::EstimateRemaining,
+        TaskSort::Manual,
+    ] {
+        let query = commands::TaskQueryBuilder::default()
+            .sort(sort)
+            .include_finished(true)
+            .page_size(1000u32)
+            .build()
+            .unwrap();
+        let all = commands::list_tasks_impl(&pool, query)
+            .await
+            .expect("Failed to list tasks");
+        assert_eq!(all.total, 57);
+
+        assert_eq!(walk_cursor(&pool, sort, 10).await, task_ids(&all.data));
+    }
+
+    let query = commands::TaskQueryBuilder::default()
+        .pagination(PaginationMode::Cursor)
+        .sort(TaskSort::Manual)
+        .cursor("not a cursor")
+        .build()
+        .unwrap();
+    assert!(commands::list_tasks_impl(&pool, query).await.is_err());
+
+    let mut seen = 0;
+    let mut cursor = None;
+    loop {
+        let page = commands::list_time_entries_impl(
+            &pool,
+            TimeEntryQuery {
+                page_size: Some(25),
+                cursor,
+                with_total: true,
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("Failed to list time entries");
+        assert_eq!(page.cursor_total, Some(114));
+        seen += page.data.len();
+        match page.next_cursor {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+    assert_eq!(seen, 114);
+}
+
+/// 大数据量下页码分页与游标分页的耗时对比：cargo test --test pagination -- --ignored --nocapture
+#[tokio::test]
+#[ignore]
+async fn bench_large_task_listing() {
+    let pool = common::setup_memory_db()
+        .await
+        .expect("Failed to setup memory database");
+    let started = Instant::now();
+    seed(&pool, 20_000, 10).await;
+    println!(
+        "seeded 20000 tasks / 200000 entries in {:?}",
+        started.elapsed()
+    );
+
+    let pages = 50u32;
+    let started = Instant::now();
+    for page_index in 1..=pages {
+        let query = commands::TaskQueryBuilder::default()
+            .sort(TaskSort::Created)
+            .include_finished(true)
+            .page_index(page_index)
+            .build()
+            .unwrap();
+        commands::list_tasks_impl(&pool, query)
+            .await
+            .expect("Failed to list tasks");
+    }
+    println!("offset: {pages} pages in {:?}", started.elapsed());
+
+    let started = Instant::now();
+    let mut cursor: Option<String> = None;
+    for _ in 0..pages {
+        let mut builder = commands::TaskQueryBuilder::default();
+        builder
+            .sort(TaskSort::Created)
+            .include_finished(true)
+            .pagination(PaginationMode::Cursor);
+        if let Some(ref c) = cursor {
+            builder.cursor(c.as_str());
+        }
+        let page = commands::list_tasks_impl(&pool, builder.build().unwrap())
+            .await
+            .expect("Failed to list tasks");
+        cursor = page.next_cursor;
+    }
+    println!("cursor: {pages} pages in {:?}", started.elapsed());
+}
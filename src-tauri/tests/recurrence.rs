@@ -0,0 +1,134 @@
+mod common;
+use chrono::{Duration, NaiveDate};
+use sqlx::SqlitePool;
+use zap_lib::commands::{self, CreateRecurringTaskRequest, RecurrenceRule};
+
+fn date(s: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+}
+
+#[test]
+fn test_recurrence_rule_occurrences() {
+    // 2025-01-01 是周三
+    let start = date("2025-01-01");
+
+    let weekdays: RecurrenceRule = "FREQ=WEEKLY;BYDAY=MO,TU,WE,TH,FR".parse().unwrap();
+    assert_eq!(
+        weekdays.next_on_or_after(start, date("2025-01-04")),
+        Some(date("2025-01-06"))
+    );
+
+    let biweekly: RecurrenceRule = "RRULE:FREQ=WEEKLY;INTERVAL=2;BYDAY=MO".parse().unwrap();
+    assert_eq!(
+        biweekly.next_on_or_after(start, start),
+        Some(date("2025-01-13"))
+    );
+
+    let month_end: RecurrenceRule = "FREQ=MONTHLY;BYMONTHDAY=-1".parse().unwrap();
+    assert_eq!(
+        month_end.next_on_or_after(start, date("2025-02-01")),
+        Some(date("2025-02-28"))
+    );
+
+    let on_31st: RecurrenceRule = "FREQ=MONTHLY;BYMONTHDAY=31".parse().unwrap();
+    assert_eq!(
+        on_31st.next_on_or_after(start, date("2025-02-01")),
+        Some(date("2025-03-31"))
+    );
+
+    let until: RecurrenceRule = "FREQ=DAILY;INTERVAL=3;UNTIL=20250105".parse().unwrap();
+    assert_eq!(
+        until.next_on_or_after(start, date("2025-01-02")),
+        Some(date("2025-01-04"))
+    );
+    assert_eq!(until.next_on_or_after(start, date("2025-01-05")), None);
+
+    // INTERVAL 过大时拒绝，接近日期上限时返回 None 而不是溢出
+    assert!(
+        "FREQ=DAILY;INTERVAL=40000"
+            .parse::<RecurrenceRule>()
+            .is_err()
+    );
+    let sparse: RecurrenceRule = "FREQ=MONTHLY;INTERVAL=1000;BYMONTHDAY=31".parse().unwrap();
+    assert_eq!(
+        sparse.next_on_or_after(start, date("2025-02-01")),
+        Some(date("2108-05-31"))
+    );
+    let near_max = NaiveDate::MAX - Duration::days(10);
+    let every_1000_days: RecurrenceRule = "FREQ=DAILY;INTERVAL=1000".parse().unwrap();
+    assert_eq!(
+        every_1000_days.next_on_or_after(near_max, near_max + Duration::days(1)),
+        None
+    );
+
+    assert!("FREQ=YEARLY".parse::<RecurrenceRule>().is_err());
+    assert!("FREQ=DAILY;BYDAY=MO".parse::<RecurrenceRule>().is_err());
+    assert!("FREQ=WEEKLY;COUNT=3".parse::<RecurrenceRule>().is_err());
+}
+
+#[tokio::test]
+async fn test_recurring_task_materialization() {
+    let pool: SqlitePool = common::setup_memory_db()
+        .await
+        .expect("Failed to setup test database");
+
+    let today = chrono::Local::now().date_naive();
+    let series = commands::create_recurring_task_impl(
+        &pool,
+        CreateRecurringTaskRequest {
+            title: format!("站会 {}", chrono::Utc::now().timestamp_micros()),
+            rrule: "FREQ=DAILY".to_string(),
+            starts_on: Some((today - Duration::days(3)).format("%Y-%m-%d").to_string()),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Failed to create recurring task");
+
+    // 错过的日期只补生成今天的实例
+    let tomorrow = (today + Duration::days(1)).format("%Y-%m-%d").to_string();
+    assert_eq!(series.next_date.as_deref(), Some(tomorrow.as_str()));
+
+    let instances = |series_id| {
+        commands::TaskQueryBuilder::default()
+            .recurring_task_id(series_id)
+            .build()
+            .unwrap()
+    };
+    let tasks = commands::list_tasks_impl(&pool, instances(series.id))
+        .await
+        .expect("Failed to list instances");
+    assert_eq!(tasks.total, 1);
+
+    let task_id: u32 =
+        sqlx::query_scalar("SELECT id FROM tasks WHERE recurring_task_id = ? AND done = 0")
+            .bind(series.id)
+            .fetch_one(&pool)
+            .await
+            .expect("Failed to query instance id");
+
+    let created = commands::materialize_recurring_tasks_impl(&pool, today)
+        .await
+        .expect("Failed to materialize");
+    assert!(!created.contains(&task_id));
+
+    // 完成实例后立即生成下一个
+    commands::finish_task_impl(&pool, task_id)
+        .await
+        .expect("Failed to finish instance");
+    let stats = commands::get_recurring_task_stats_impl(&pool, series.id)
+        .await
+        .expect("Failed to get recurring task stats");
+    assert_eq!(stats.instance_count, 2);
+    assert_eq!(stats.finished_count, 1);
+
+    commands::delete_recurring_task_impl(&pool, series.id)
+        .await
+        .expect("Failed to delete recurring task");
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM tasks WHERE id = ?")
+        .bind(task_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(count, 1);
+}
@@ -0,0 +1,173 @@
+mod common;
+use sqlx::SqlitePool;
+use zap_lib::commands::{self, StatsDateQuery};
+
+async fn rollup_seconds(pool: &SqlitePool, task_id: u32) -> i64 {
+    sqlx::query_scalar("SELECT COALESCE(SUM(seconds), 0) FROM task_day_totals WHERE task_id = ?")
+        .bind(task_id)
+        .fetch_one(pool)
+        .await
+        .expect("Failed to query rollup")
+}
+
+#[tokio::test]
+async fn test_rollup_follows_entry_changes() {
+    let pool = common::setup_memory_db()
+        .await
+        .expect("Failed to setup test database");
+
+    let task_id = common::create_task(&pool, "汇总", |_| {}).await;
+    let day = chrono::Utc::now().date_naive();
+    let today = common::insert_entry(&pool, task_id, &format!("{day} 01:00:00"), 300).await;
+    let three_days_ago = day - chrono::Duration::days(3);
+    common::insert_entry(&pool, task_id, &format!("{three_days_ago} 00:00:00"), 1200).await;
+    assert_eq!(rollup_seconds(&pool, task_id).await, 1500);
+
+    // 编辑时长、调整可计费、删除记录都会同步
+    sqlx::query("UPDATE time_entries SET duration_seconds = 600 WHERE id = ?")
+        .bind(today)
+        .execute(&pool)
+        .await
+        .expect("Failed to update entry");
+    commands::set_time_entry_billable_impl(&pool, today as u32, Some(false))
+        .await
+        .expect("Failed to set billable");
+    assert_eq!(rollup_seconds(&pool, task_id).await, 1800);
+
+    sqlx::query("DELETE FROM time_entries WHERE id = ?")
+        .bind(today)
+        .execute(&pool)
+        .await
+        .expect("Failed to delete entry");
+    assert_eq!(rollup_seconds(&pool, task_id).await, 1200);
+
+    // 开始与停止计时：进行中的记录不计入，结束后计入
+    commands::start_task_impl(&pool, task_id)
+        .await
+        .expect("Failed to start task");
+    assert_eq!(rollup_seconds(&pool, task_id).await, 1200);
+    sqlx::query(
+        "UPDATE time_entries SET started_at = datetime('now', '-60 seconds')
+         WHERE task_id = ? AND ended_at IS NULL",
+    )
+    .bind(task_id)
+    .execute(&pool)
+    .await
+    .expect("Failed to backdate time entry");
+    commands::stop_task_impl(&pool, task_id)
+        .await
+        .expect("Failed to stop task");
+    assert!(rollup_seconds(&pool, task_id).await >= 1260);
+
+    let report = commands::check_task_day_totals_impl(&pool)
+        .await
+        .expect("Failed to check rollup");
+    assert!(report.mismatches.is_empty());
+    assert_eq!(report.checked_groups, 2);
+
+    // 列表与统计读取的时长与原始记录一致
+    let raw_total: i64 =
+        sqlx::query_scalar("SELECT SUM(duration_seconds) FROM time_entries WHERE task_id = ?")
+            .bind(task_id)
+            .fetch_one(&pool)
+            .await
+            .expect("Failed to sum entries");
+    let query = commands::TaskQueryBuilder::default()
+        .page_index(1u32)
+        .page_size(10u32)
+        .build()
+        .unwrap();
+    let tasks = commands::list_tasks_impl(&pool, query)
+        .await
+        .expect("Failed to list tasks");
+    let task = serde_json::to_value(&tasks.data[0]).unwrap();
+    assert_eq!(task["total_duration_seconds"], raw_total);
+
+    let today = chrono::Local::now().date_naive();
+    let range = StatsDateQuery {
+        start_date: Some((today - chrono::Duration::days(6)).to_string()),
+        end_date: Some(today.to_string()),
+        ..Default::default()
+    };
+    let week = commands::get_week_stats_impl(&pool, range)
+        .await
+        .expect("Failed to get week stats");
+    let week = serde_json::to_value(&week).unwrap();
+    assert_eq!(week["total_seconds"], raw_total);
+    assert_eq!(week["sessions_count"], 2);
+}
+
+#[tokio::test]
+async fn test_rollup_check_and_rebuild() {
+    let pool = common::setup_memory_db()
+        .await
+        .expect("Failed to setup test database");
+
+    let task_id = common::create_task(&pool, "重建", |_| {}).await;
+    let day = chrono::Utc::now().date_naive();
+    common::insert_entry(&pool, task_id, &format!("{day} 01:00:00"), 900).await;
+    common::insert_entry(&pool, task_id, &format!("{day} 02:00:00"), 600).await;
+
+    // 人为破坏汇总数据后，检查能发现差异，重建后恢复一致
+    sqlx::query("UPDATE task_day_totals SET seconds = 1, entry_count = 5")
+        .execute(&pool)
+        .await
+        .expect("Failed to corrupt rollup");
+    let report = commands::check_task_day_totals_impl(&pool)
+        .await
+        .expect("Failed to check rollup");
+    assert_eq!(report.mismatches.len(), 1);
+    let mismatch = &report.mismatches[0];
+    assert_eq!(mismatch.task_id, task_id);
+    assert_eq!(mismatch.expected_seconds, 1500);
+    assert_eq!(mismatch.actual_seconds, 1);
+    assert_eq!(mismatch.expected_entries, 2);
+
+    let groups = commands::rebuild_task_day_totals_impl(&pool)
+        .await
+        .expect("Failed to rebuild rollup");
+    assert_eq!(groups, 1);
+    let report = commands::check_task_day_totals_impl(&pool)
+        .await
+        .expect("Failed to check rollup");
+    assert!(report.mismatches.is_empty());
+    assert_eq!(rollup_seconds(&pool, task_id).await, 1500);
+}
+
+#[tokio::test]
+async fn test_rollup_keys_on_local_day() {
+    let pool = common::setup_memory_db()
+        .await
+        .expect("Failed to setup test database");
+
+    let task_id = common::create_task(&pool, "本地日期", |_| {}).await;
+    let today = chrono::Local::now().date_naive();
+    let midnight = common::local_to_utc(&format!("{today} 00:00:00"));
+    common::insert_entry(&pool, task_id, &midnight, 60).await;
+    let yesterday_late =
+        common::local_to_utc(&format!("{} 23:59:00", today - chrono::Duration::days(1)));
+    common::insert_entry(&pool, task_id, &yesterday_late, 30).await;
+
+    // 汇总日期与今日时长都按本地零点划分
+    let days: Vec<(String, i64)> =
+        sqlx::query_as("SELECT day, seconds FROM task_day_totals WHERE task_id = ? ORDER BY day")
+            .bind(task_id)
+            .fetch_all(&pool)
+            .await
+            .expect("Failed to query rollup");
+    assert_eq!(
+        days,
+        vec![
+            ((today - chrono::Duration::days(1)).to_string(), 30),
+            (today.to_string(), 60),
+        ]
+    );
+
+    let query = commands::TaskQueryBuilder::default().build().unwrap();
+    let tasks = commands::list_tasks_impl(&pool, query)
+        .await
+        .expect("Failed to list tasks");
+    let task = serde_json::to_value(&tasks.data[0]).unwrap();
+    assert_eq!(task["today_duration_seconds"], 60);
+    assert_eq!(task["total_duration_seconds"], 90);
+}
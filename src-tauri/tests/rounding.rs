@@ -0,0 +1,49 @@
+mod common;
+use sqlx::SqlitePool;
+use zap_lib::commands::{self, RoundingMode, RoundingPolicy, RoundingScope};
+
+fn policy(mode: RoundingMode, unit_minutes: u32) -> RoundingPolicy {
+    RoundingPolicy {
+        mode,
+        unit_minutes,
+        scope: RoundingScope::Entry,
+    }
+}
+
+#[test]
+fn test_round_modes() {
+    assert_eq!(policy(RoundingMode::None, 15).round(401), 401);
+    assert_eq!(policy(RoundingMode::Up, 15).round(1), 900);
+    assert_eq!(policy(RoundingMode::Up, 15).round(900), 900);
+    assert_eq!(policy(RoundingMode::Down, 6).round(719), 360);
+    assert_eq!(policy(RoundingMode::Nearest, 5).round(149), 0);
+    assert_eq!(policy(RoundingMode::Nearest, 5).round(150), 300);
+    assert_eq!(policy(RoundingMode::Up, 30).round(0), 0);
+}
+
+#[tokio::test]
+async fn test_rounding_policy_roundtrip() {
+    let pool: SqlitePool = common::setup_memory_db()
+        .await
+        .expect("Failed to setup test database");
+
+    let invalid = policy(RoundingMode::Up, 7);
+    assert!(
+        commands::set_rounding_policy_impl(&pool, invalid)
+            .await
+            .is_err()
+    );
+
+    let saved = RoundingPolicy {
+        mode: RoundingMode::Nearest,
+        unit_minutes: 15,
+        scope: RoundingScope::DayTask,
+    };
+    commands::set_rounding_policy_impl(&pool, saved)
+        .await
+        .expect("Failed to save rounding policy");
+    let loaded = commands::get_rounding_policy_impl(&pool)
+        .await
+        .expect("Failed to load rounding policy");
+    assert_eq!(loaded, saved);
+}
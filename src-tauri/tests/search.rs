@@ -0,0 +1,138 @@
+mod common;
+use sqlx::SqlitePool;
+use zap_lib::commands::{self, SearchHitKind, SearchRequest};
+
+/// 生成只含字母的唯一词，避免与其他测试数据冲突
+fn unique_word() -> String {
+    let mut n = chrono::Utc::now().timestamp_micros() as u64;
+    let mut word = String::from("zq");
+    while n > 0 {
+        word.push((b'a' + (n % 26) as u8) as char);
+        n /= 26;
+    }
+    word
+}
+
+async fn search(pool: &SqlitePool, query: &str) -> Vec<zap_lib::commands::SearchHit> {
+    commands::search_impl(
+        pool,
+        SearchRequest {
+            query: query.to_string(),
+            limit: None,
+        },
+    )
+    .await
+    .expect("Failed to search")
+}
+
+#[tokio::test]
+async fn test_full_text_search() {
+    let pool: SqlitePool = common::setup_memory_db()
+        .await
+        .expect("Failed to setup test database");
+
+    let word = unique_word();
+    let req = commands::CreateTaskRequestBuilder::default()
+        .title(format!("Review {word}"))
+        .notes(format!("alpha {word} release notes"))
+        .build()
+        .unwrap();
+    commands::add_task_impl(&pool, req)
+        .await
+        .expect("Failed to add task");
+    let task_id: u32 = sqlx::query_scalar("SELECT MAX(id) FROM tasks WHERE title = ?")
+        .bind(format!("Review {word}"))
+        .fetch_one(&pool)
+        .await
+        .expect("Failed to query task id");
+
+    sqlx::query(
+        "INSERT INTO time_entries (task_id, started_at, note) VALUES (?, datetime('now'), ?)",
+    )
+    .bind(task_id)
+    .bind(format!("paired on {word} migration"))
+    .execute(&pool)
+    .await
+    .expect("Failed to insert time entry");
+
+    let hits = search(&pool, &word).await;
+    assert_eq!(hits.len(), 2);
+    assert!(hits.iter().all(|h| h.task_id == task_id));
+    assert!(hits.iter().any(|h| h.kind == SearchHitKind::Session));
+    assert!(
+        hits.iter()
+            .any(|h| h.snippet.contains(&format!("«{word}»")))
+    );
+
+    assert_eq!(search(&pool, &format!("{}*", &word[..8])).await.len(), 2);
+    assert_eq!(search(&pool, &format!("\"alpha {word}\"")).await.len(), 1);
+    assert!(search(&pool, &format!("\"{word} alpha\"")).await.is_empty());
+    assert!(search(&pool, "\" - : (").await.is_empty());
+
+    let req = commands::UpdateTaskRequestBuilder::default()
+        .title("Renamed")
+        .notes(None::<String>)
+        .build()
+        .unwrap();
+    commands::update_task_impl(&pool, task_id, req)
+        .await
+        .expect("Failed to update task");
+    let hits = search(&pool, &word).await;
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].kind, SearchHitKind::Session);
+
+    sqlx::query("DELETE FROM time_entries WHERE task_id = ?")
+        .bind(task_id)
+        .execute(&pool)
+        .await
+        .expect("Failed to delete time entries");
+    assert!(search(&pool, &word).await.is_empty());
+}
+
+#[tokio::test]
+async fn test_chinese_substring_search() {
+    let pool: SqlitePool = common::setup_memory_db()
+        .await
+        .expect("Failed to setup test database");
+
+    let req = commands::CreateTaskRequestBuilder::default()
+        .title("测试任务")
+        .notes("整理回归用例")
+        .build()
+        .unwrap();
+    commands::add_task_impl(&pool, req)
+        .await
+        .expect("Failed to add task");
+    let task_id: u32 = sqlx::query_scalar("SELECT MAX(id) FROM tasks")
+        .fetch_one(&pool)
+        .await
+        .expect("Failed to query task id");
+    sqlx::query(
+        "INSERT INTO time_entries (task_id, started_at, note) VALUES (?, datetime('now'), ?)",
+    )
+    .bind(task_id)
+    .bind("和产品一起评审需求")
+    .execute(&pool)
+    .await
+    .expect("Failed to insert time entry");
+
+    // 两个字的词走 LIKE，三个字以上走 trigram 索引
+    let hits = search(&pool, "测试").await;
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].kind, SearchHitKind::Task);
+    assert_eq!(hits[0].snippet, "«测试»任务");
+    assert_eq!(search(&pool, "试任务").await.len(), 1);
+    assert_eq!(search(&pool, "回归用例").await.len(), 1);
+    assert_eq!(search(&pool, "测试 回归").await.len(), 1);
+    assert!(search(&pool, "测试 发布").await.is_empty());
+
+    let hits = search(&pool, "评审").await;
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].kind, SearchHitKind::Session);
+    assert_eq!(hits[0].snippet, "和产品一起«评审»需求");
+    assert_eq!(search(&pool, "评审需求 产品").await.len(), 1);
+
+    // LIKE 的通配符按字面匹配
+    assert!(search(&pool, "%").await.is_empty());
+    assert!(search(&pool, "测_").await.is_empty());
+}
@@ -0,0 +1,686 @@
+mod common;
+use sqlx::SqlitePool;
+use zap_lib::commands::{self, StatsDateQuery};
+
+#[tokio::test]
+async fn test_estimate_accuracy_and_overrun() {
+    let pool: SqlitePool = common::setup_memory_db()
+        .await
+        .expect("Failed to setup test database");
+
+    let suffix = chrono::Utc::now().timestamp_micros();
+    let category_id = sqlx::query("INSERT INTO categories (name, color) VALUES (?, '#123456')")
+        .bind(format!("预估分类 {suffix}"))
+        .execute(&pool)
+        .await
+        .expect("Failed to insert category")
+        .last_insert_rowid();
+
+    let finished = common::create_task(&pool, &format!("已完成 {suffix}"), |b| {
+        b.category_id(category_id as u32).estimate_seconds(600);
+    })
+    .await;
+    sqlx::query(
+        "INSERT INTO time_entries (task_id, started_at, ended_at, duration_seconds)
+         VALUES (?, datetime('now', '-1 hour'), datetime('now', '-45 minutes'), 900)",
+    )
+    .bind(finished)
+    .execute(&pool)
+    .await
+    .expect("Failed to insert time entry");
+    commands::finish_task_impl(&pool, finished)
+        .await
+        .expect("Failed to finish task");
+
+    let report = commands::get_estimate_accuracy_impl(&pool, StatsDateQuery::default())
+        .await
+        .expect("Failed to get estimate accuracy");
+    let stat = report
+        .categories
+        .iter()
+        .find(|c| c.category_id == Some(category_id as u32))
+        .expect("Missing category in report");
+    assert_eq!(stat.task_count, 1);
+    assert_eq!(stat.actual_seconds, 900);
+    assert_eq!(stat.ratio, 1.5);
+
+    // 运行中的任务超过预估后只提醒一次
+    let running = common::create_task(&pool, &format!("运行中 {suffix}"), |b| {
+        b.category_id(category_id as u32).estimate_seconds(60);
+    })
+    .await;
+    commands::start_task_impl(&pool, running)
+        .await
+        .expect("Failed to start task");
+    sqlx::query(
+        "UPDATE time_entries SET started_at = datetime('now', '-120 seconds')
+         WHERE task_id = ? AND ended_at IS NULL",
+    )
+    .bind(running)
+    .execute(&pool)
+    .await
+    .expect("Failed to backdate time entry");
+
+    let overruns = commands::take_estimate_overruns_impl(&pool)
+        .await
+        .expect("Failed to check overruns");
+    assert!(overruns.iter().any(|o| o.task_id == running));
+    let overruns = commands::take_estimate_overruns_impl(&pool)
+        .await
+        .expect("Failed to check overruns");
+    assert!(!overruns.iter().any(|o| o.task_id == running));
+
+    let query = commands::TaskQueryBuilder::default()
+        .task_name(format!("运行中 {suffix}"))
+        .build()
+        .unwrap();
+    let tasks = commands::list_tasks_impl(&pool, query)
+        .await
+        .expect("Failed to list tasks");
+    let task = serde_json::to_value(&tasks.data[0]).unwrap();
+    assert_eq!(task["estimate_seconds"], 60);
+    assert!(task["remaining_seconds"].as_i64().unwrap() <= -60);
+    assert!(task["percent_consumed"].as_f64().unwrap() >= 200.0);
+
+    commands::stop_task_impl(&pool, running)
+        .await
+        .expect("Failed to stop task");
+}
+
+#[tokio::test]
+async fn test_stats_engine_breakdowns_add_up() {
+    let pool = common::setup_memory_db()
+        .await
+        .expect("Failed to setup test database");
+
+    let category_id =
+        sqlx::query("INSERT INTO categories (name, color) VALUES ('开发', '#123456')")
+            .execute(&pool)
+            .await
+            .expect("Failed to insert category")
+            .last_insert_rowid();
+    let closed = common::create_task(&pool, "已结束", |b| {
+        b.category_id(category_id as u32).estimate_seconds(0);
+    })
+    .await;
+    let running = common::create_task(&pool, "进行中", |b| {
+        b.category_id(category_id as u32).estimate_seconds(0);
+    })
+    .await;
+
+    let today = chrono::Local::now().date_naive();
+    let week_start = today - chrono::Duration::days(13);
+    sqlx::query(
+        "INSERT INTO time_entries (task_id, started_at, ended_at, duration_seconds)
+         VALUES (?, datetime(? || ' 09:00:00', 'utc'), datetime(? || ' 10:00:00', 'utc'), 3600),
+                (?, datetime('now', 'localtime', 'start of day', 'utc'),
+                    datetime('now', 'localtime', 'start of day', 'utc', '+600 seconds'), 600)",
+    )
+    .bind(closed)
+    .bind(week_start.to_string())
+    .bind(week_start.to_string())
+    .bind(closed)
+    .execute(&pool)
+    .await
+    .expect("Failed to insert time entries");
+
+    // 进行中的记录按当前时刻计入总计和各分组
+    commands::start_task_impl(&pool, running)
+        .await
+        .expect("Failed to start task");
+    sqlx::query(
+        "UPDATE time_entries SET started_at = datetime('now', '-300 seconds')
+         WHERE task_id = ? AND ended_at IS NULL",
+    )
+    .bind(running)
+    .execute(&pool)
+    .await
+    .expect("Failed to backdate time entry");
+
+    let range = StatsDateQuery {
+        start_date: Some(week_start.to_string()),
+        end_date: Some(today.to_string()),
+        ..Default::default()
+    };
+    let report = commands::get_stats_impl(
+        &pool,
+        range,
+        commands::StatsGranularity::Week,
+        &[
+            commands::StatsGroupBy::Category,
+            commands::StatsGroupBy::Task,
+        ],
+    )
+    .await
+    .expect("Failed to get stats");
+
+    assert!(report.total_seconds >= 4500);
+    assert_eq!(report.days_count, 14);
+    assert_eq!(report.sessions_count, 3);
+    let categories = report.group(commands::StatsGroupBy::Category);
+    assert_eq!(categories.len(), 1);
+    assert_eq!(categories[0].seconds, report.total_seconds);
+    assert_eq!(categories[0].percentage, 100.0);
+    let tasks = report.group(commands::StatsGroupBy::Task);
+    assert_eq!(
+        tasks.iter().map(|t| t.seconds).sum::<i64>(),
+        report.total_seconds
+    );
+
+    // 按周分段：首尾两段裁剪到统计范围内，各段之和等于总计
+    assert!(report.timeline.len() >= 2);
+    assert_eq!(report.timeline[0].start_date, week_start.to_string());
+    assert_eq!(report.timeline.last().unwrap().end_date, today.to_string());
+    assert_eq!(
+        report.timeline.iter().map(|b| b.seconds).sum::<i64>(),
+        report.total_seconds
+    );
+
+    let today_stats = commands::get_today_stats_impl(&pool, StatsDateQuery::default())
+        .await
+        .expect("Failed to get today stats");
+    assert_eq!(
+        today_stats
+            .categories
+            .iter()
+            .map(|c| c.seconds)
+            .sum::<i64>(),
+        today_stats.total_seconds
+    );
+
+    let reversed = StatsDateQuery {
+        start_date: Some(today.to_string()),
+        end_date: Some(week_start.to_string()),
+        ..Default::default()
+    };
+    assert!(
+        commands::get_stats_impl(&pool, reversed, commands::StatsGranularity::Day, &[])
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn test_sessions_across_midnight_are_clipped() {
+    use chrono::{Local, NaiveDate, TimeZone, Utc};
+
+    let pool = common::setup_memory_db()
+        .await
+        .expect("Failed to setup test database");
+    let task_id = common::create_task(&pool, "通宵", |_| {}).await;
+
+    // 本地时间 2025-03-10（周一）23:00 至次日 01:00
+    let to_utc = |date: &str, h| {
+        let local = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .unwrap()
+            .and_hms_opt(h, 0, 0)
+            .unwrap();
+        Local
+            .from_local_datetime(&local)
+            .earliest()
+            .unwrap()
+            .with_timezone(&Utc)
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string()
+    };
+    sqlx::query(
+        "INSERT INTO time_entries (task_id, started_at, ended_at, duration_seconds)
+         VALUES (?, ?, ?, 7200)",
+    )
+    .bind(task_id)
+    .bind(to_utc("2025-03-10", 23))
+    .bind(to_utc("2025-03-11", 1))
+    .execute(&pool)
+    .await
+    .expect("Failed to insert time entry");
+
+    let range = |start: &str, end: &str| StatsDateQuery {
+        start_date: Some(start.to_string()),
+        end_date: Some(end.to_string()),
+        ..Default::default()
+    };
+    for day in ["2025-03-10", "2025-03-11"] {
+        let stats = commands::get_today_stats_impl(&pool, range(day, day))
+            .await
+            .expect("Failed to get day stats");
+        assert_eq!(stats.total_seconds, 3600);
+        assert_eq!(stats.sessions_count, 1);
+        assert_eq!(stats.detailed_report[0].seconds, 3600);
+    }
+
+    let both = commands::get_stats_impl(
+        &pool,
+        range("2025-03-10", "2025-03-11"),
+        commands::StatsGranularity::Day,
+        &[commands::StatsGroupBy::Task],
+    )
+    .await
+    .expect("Failed to get stats");
+    assert_eq!(both.total_seconds, 7200);
+    assert_eq!(both.sessions_count, 1);
+    assert_eq!(both.active_days, 2);
+    assert_eq!(
+        both.timeline.iter().map(|b| b.seconds).collect::<Vec<_>>(),
+        vec![3600, 3600]
+    );
+
+    // 两段落在同一周内时按一次会话计
+    let week = commands::get_stats_impl(
+        &pool,
+        range("2025-03-10", "2025-03-11"),
+        commands::StatsGranularity::Week,
+        &[],
+    )
+    .await
+    .expect("Failed to get stats");
+    assert_eq!(week.timeline.len(), 1);
+    assert_eq!(week.timeline[0].seconds, 7200);
+    assert_eq!(week.timeline[0].sessions_count, 1);
+}
+
+#[tokio::test]
+async fn test_focused_time_from_sessions() {
+    let pool = common::setup_memory_db()
+        .await
+        .expect("Failed to setup test database");
+
+    let category_id =
+        sqlx::query("INSERT INTO categories (name, color) VALUES ('专注', '#123456')")
+            .execute(&pool)
+            .await
+            .expect("Failed to insert category")
+            .last_insert_rowid();
+    let task_id = common::create_task(&pool, "深度工作", |b| {
+        b.category_id(category_id as u32).estimate_seconds(0);
+    })
+    .await;
+
+    // 30 分钟普通会话、10 分钟普通会话、5 分钟番茄钟
+    let mut entry_ids = Vec::new();
+    for (offset, seconds) in [("+1 hours", 1800), ("+2 hours", 600), ("+3 hours", 300)] {
+        let id = sqlx::query(
+            "INSERT INTO time_entries (task_id, started_at, ended_at, duration_seconds)
+             VALUES (?, datetime('now', 'localtime', 'start of day', ?, 'utc'),
+                     datetime('now', 'localtime', 'start of day', ?, 'utc', '+' || ? || ' seconds'), ?)",
+        )
+        .bind(task_id)
+        .bind(offset)
+        .bind(offset)
+        .bind(seconds)
+        .bind(seconds)
+        .execute(&pool)
+        .await
+        .expect("Failed to insert time entry")
+        .last_insert_rowid();
+        entry_ids.push(id as u32);
+    }
+    commands::set_session_kind_impl(&pool, entry_ids[2], commands::SessionKind::Pomodoro)
+        .await
+        .expect("Failed to set session kind");
+
+    // 默认阈值 25 分钟：30 分钟会话和番茄钟计为专注，与任务当前状态无关
+    let today = commands::get_today_stats_impl(&pool, StatsDateQuery::default())
+        .await
+        .expect("Failed to get today stats");
+    assert_eq!(today.total_seconds, 2700);
+    assert_eq!(today.focused_seconds, 2100);
+
+    commands::set_focus_policy_impl(
+        &pool,
+        commands::FocusPolicy {
+            min_session_minutes: 45,
+        },
+    )
+    .await
+    .expect("Failed to set focus policy");
+    let range = StatsDateQuery {
+        start_date: Some(chrono::Local::now().format("%Y-%m-%d").to_string()),
+        ..Default::default()
+    };
+    let month = commands::get_month_stats_impl(&pool, range)
+        .await
+        .expect("Failed to get month stats");
+    assert_eq!(month.focused_seconds, 300);
+
+    // 向下取整时专注时长同样取整，不会超过总时长
+    commands::set_focus_policy_impl(
+        &pool,
+        commands::FocusPolicy {
+            min_session_minutes: 25,
+        },
+    )
+    .await
+    .expect("Failed to set focus policy");
+    commands::set_rounding_policy_impl(
+        &pool,
+        commands::RoundingPolicy {
+            mode: commands::RoundingMode::Down,
+            unit_minutes: 30,
+            scope: commands::RoundingScope::Entry,
+        },
+    )
+    .await
+    .expect("Failed to save rounding policy");
+    let today = commands::get_today_stats_impl(&pool, StatsDateQuery::default())
+        .await
+        .expect("Failed to get today stats");
+    assert_eq!(today.total_seconds, 1800);
+    assert_eq!(today.focused_seconds, 1800);
+
+    assert!(
+        commands::set_focus_policy_impl(
+            &pool,
+            commands::FocusPolicy {
+                min_session_minutes: 0,
+            },
+        )
+        .await
+        .is_err()
+    );
+    assert!(
+        commands::set_session_kind_impl(&pool, 9999, commands::SessionKind::Focus)
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn test_hourly_distribution_splits_sessions() {
+    use chrono::{Datelike, Local, TimeZone, Utc};
+
+    let pool = common::setup_memory_db()
+        .await
+        .expect("Failed to setup test database");
+
+    let category_id =
+        sqlx::query("INSERT INTO categories (name, color) VALUES ('写作', '#123456')")
+            .execute(&pool)
+            .await
+            .expect("Failed to insert category")
+            .last_insert_rowid();
+    let task_id = common::create_task(&pool, "打卡图", |b| {
+        b.category_id(category_id as u32).estimate_seconds(0);
+    })
+    .await;
+
+    // 本地时间昨天 09:30 - 11:15，跨两个整点
+    let day = Local::now().date_naive() - chrono::Duration::days(1);
+    let to_utc = |h, m| {
+        Local
+            .from_local_datetime(&day.and_hms_opt(h, m, 0).unwrap())
+            .earliest()
+            .unwrap()
+            .with_timezone(&Utc)
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string()
+    };
+    sqlx::query(
+        "INSERT INTO time_entries (task_id, started_at, ended_at, duration_seconds)
+         VALUES (?, ?, ?, 6300)",
+    )
+    .bind(task_id)
+    .bind(to_utc(9, 30))
+    .bind(to_utc(11, 15))
+    .execute(&pool)
+    .await
+    .expect("Failed to insert time entry");
+
+    let range = StatsDateQuery {
+        start_date: Some(day.to_string()),
+        end_date: Some(day.to_string()),
+        ..Default::default()
+    };
+    let stats = commands::get_hourly_stats_impl(&pool, range)
+        .await
+        .expect("Failed to get hourly stats");
+
+    let weekday = day.weekday().num_days_from_sunday() as usize;
+    let overall = &stats.overall;
+    assert_eq!(overall.total_seconds, 6300);
+    assert_eq!(overall.matrix[weekday][9], 1800);
+    assert_eq!(overall.matrix[weekday][10], 3600);
+    assert_eq!(overall.matrix[weekday][11], 900);
+    assert_eq!(overall.weekday_totals[weekday], 6300);
+
+    let peak = overall.peak.as_ref().expect("Missing peak");
+    assert_eq!((peak.weekday as usize, peak.hour), (weekday, 10));
+    assert_eq!(peak.busiest_hour, 10);
+
+    assert_eq!(stats.categories.len(), 1);
+    assert_eq!(stats.categories[0].name, "写作");
+    assert_eq!(stats.categories[0].distribution.total_seconds, 6300);
+
+    // 范围外没有数据
+    let empty = commands::get_hourly_stats_impl(
+        &pool,
+        StatsDateQuery {
+            start_date: Some((day - chrono::Duration::days(7)).to_string()),
+            end_date: Some((day - chrono::Duration::days(2)).to_string()),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Failed to get hourly stats");
+    assert_eq!(empty.overall.total_seconds, 0);
+    assert!(empty.overall.peak.is_none());
+}
+
+#[tokio::test]
+async fn test_year_and_range_stats() {
+    let pool = common::setup_memory_db()
+        .await
+        .expect("Failed to setup test database");
+
+    let category_id =
+        sqlx::query("INSERT INTO categories (name, color) VALUES ('年度', '#123456')")
+            .execute(&pool)
+            .await
+            .expect("Failed to insert category")
+            .last_insert_rowid();
+    let task_id = common::create_task(&pool, "全年", |b| {
+        b.category_id(category_id as u32).estimate_seconds(0);
+    })
+    .await;
+
+    // 本地时间 08:00 开始
+    for (day, seconds) in [
+        ("2025-01-05", 600),
+        ("2025-01-06", 1200),
+        ("2025-02-10", 1800),
+        ("2025-03-01", 36000),
+    ] {
+        sqlx::query(
+            "INSERT INTO time_entries (task_id, started_at, ended_at, duration_seconds)
+             VALUES (?, datetime(? || ' 08:00:00', 'utc'),
+                     datetime(? || ' 08:00:00', 'utc', '+' || ? || ' seconds'), ?)",
+        )
+        .bind(task_id)
+        .bind(day)
+        .bind(day)
+        .bind(seconds)
+        .bind(seconds)
+        .execute(&pool)
+        .await
+        .expect("Failed to insert time entry");
+    }
+
+    let year = commands::get_year_stats_impl(
+        &pool,
+        StatsDateQuery {
+            start_date: Some("2025".to_string()),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Failed to get year stats");
+    assert_eq!(year.days.len(), 365);
+    assert_eq!(year.total_seconds, 39600);
+    assert_eq!(year.active_days, 4);
+
+    // 分档按四分位数，特别长的一天不会把其余日期压到最低档
+    let level_of = |date: &str| year.days.iter().find(|d| d.date == date).unwrap().level;
+    assert_eq!(level_of("2025-01-04"), 0);
+    assert_eq!(level_of("2025-01-05"), 1);
+    assert_eq!(level_of("2025-01-06"), 2);
+    assert_eq!(level_of("2025-02-10"), 3);
+    assert_eq!(level_of("2025-03-01"), 4);
+
+    assert_eq!(year.months.len(), 12);
+    assert_eq!(year.months[0].month, "2025-01");
+    assert_eq!(year.months[0].seconds, 1800);
+    assert_eq!(year.months[0].active_days, 2);
+    assert_eq!(year.months[2].seconds, 36000);
+
+    // 范围统计按长度自动选择粒度
+    let range = |start: &str, end: &str| StatsDateQuery {
+        start_date: Some(start.to_string()),
+        end_date: Some(end.to_string()),
+        ..Default::default()
+    };
+    let days = commands::get_range_stats_impl(&pool, range("2025-01-01", "2025-01-20"))
+        .await
+        .expect("Failed to get range stats");
+    assert_eq!(days.timeline.len(), 20);
+
+    let weeks = commands::get_range_stats_impl(&pool, range("2025-01-06", "2025-03-02"))
+        .await
+        .expect("Failed to get range stats");
+    assert_eq!(weeks.timeline.len(), 8);
+    assert_eq!(weeks.total_seconds, 39000);
+
+    let months = commands::get_range_stats_impl(&pool, range("2024-12-15", "2025-12-31"))
+        .await
+        .expect("Failed to get range stats");
+    assert_eq!(months.timeline.len(), 13);
+    assert_eq!(months.timeline[0].start_date, "2024-12-15");
+    assert_eq!(months.timeline[1].seconds, 1800);
+}
+
+#[tokio::test]
+async fn test_period_over_period_comparison() {
+    let pool = common::setup_memory_db()
+        .await
+        .expect("Failed to setup test database");
+
+    let mut categories = Vec::new();
+    for name in ["学习", "会议"] {
+        let id = sqlx::query("INSERT INTO categories (name, color) VALUES (?, '#123456')")
+            .bind(name)
+            .execute(&pool)
+            .await
+            .expect("Failed to insert category")
+            .last_insert_rowid();
+        categories.push(id);
+    }
+    let study = common::create_task(&pool, "读书", |b| {
+        b.category_id(categories[0] as u32).estimate_seconds(0);
+    })
+    .await;
+    let meeting = common::create_task(&pool, "周会", |b| {
+        b.category_id(categories[1] as u32).estimate_seconds(0);
+    })
+    .await;
+
+    // 二月：读书 1h、周会 1h；三月：读书 1.5h
+    for (task_id, day, seconds) in [
+        (study, "2025-02-03", 3600),
+        (meeting, "2025-02-04", 3600),
+        (study, "2025-03-03", 5400),
+    ] {
+        sqlx::query(
+            "INSERT INTO time_entries (task_id, started_at, ended_at, duration_seconds)
+             VALUES (?, datetime(? || ' 08:00:00', 'utc'),
+                     datetime(? || ' 08:00:00', 'utc', '+' || ? || ' seconds'), ?)",
+        )
+        .bind(task_id)
+        .bind(day)
+        .bind(day)
+        .bind(seconds)
+        .bind(seconds)
+        .execute(&pool)
+        .await
+        .expect("Failed to insert time entry");
+    }
+
+    let month = commands::get_month_stats_impl(
+        &pool,
+        StatsDateQuery {
+            start_date: Some("2025-03".to_string()),
+            compare: true,
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Failed to get month stats");
+    let comparison = month.comparison.expect("Missing comparison");
+    assert_eq!(comparison.start_date, "2025-02-01");
+    assert_eq!(comparison.end_date, "2025-02-28");
+    assert_eq!(comparison.total.current_seconds, 5400);
+    assert_eq!(comparison.total.previous_seconds, 7200);
+    assert_eq!(comparison.total.delta_seconds, -1800);
+    assert_eq!(comparison.total.percent_change, Some(-25.0));
+
+    let delta_of = |name: &str| {
+        comparison
+            .categories
+            .iter()
+            .find(|c| c.name == name)
+            .expect("Missing category delta")
+    };
+    assert_eq!(delta_of("学习").percent_change, Some(50.0));
+    assert_eq!(delta_of("会议").current_seconds, 0);
+    assert_eq!(delta_of("会议").percent_change, Some(-100.0));
+    assert_eq!(comparison.top_tasks.len(), 1);
+    assert_eq!(comparison.top_tasks[0].id, Some(study));
+
+    // 单日对比前一天，上一周期无数据时没有百分比；未请求对比时不计算
+    let day = commands::get_today_stats_impl(
+        &pool,
+        StatsDateQuery {
+            start_date: Some("2025-03-03".to_string()),
+            compare: true,
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Failed to get today stats");
+    let comparison = day.comparison.expect("Missing comparison");
+    assert_eq!(comparison.start_date, "2025-03-02");
+    assert_eq!(comparison.total.percent_change, None);
+
+    // 本地零点后不久的记录归入本地日期，对比窗口同样按本地零点划分
+    sqlx::query(
+        "INSERT INTO time_entries (task_id, started_at, ended_at, duration_seconds)
+         VALUES (?, datetime('2025-03-04 00:30:00', 'utc'), datetime('2025-03-04 01:00:00', 'utc'), 1800)",
+    )
+    .bind(meeting)
+    .execute(&pool)
+    .await
+    .expect("Failed to insert time entry");
+    let day = commands::get_today_stats_impl(
+        &pool,
+        StatsDateQuery {
+            start_date: Some("2025-03-04".to_string()),
+            compare: true,
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Failed to get today stats");
+    assert_eq!(day.total_seconds, 1800);
+    let comparison = day.comparison.expect("Missing comparison");
+    assert_eq!(comparison.start_date, "2025-03-03");
+    assert_eq!(comparison.total.previous_seconds, 5400);
+
+    let week = commands::get_week_stats_impl(
+        &pool,
+        StatsDateQuery {
+            start_date: Some("2025-03-01".to_string()),
+            end_date: Some("2025-03-07".to_string()),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Failed to get week stats");
+    assert!(week.comparison.is_none());
+}
@@ -0,0 +1,225 @@
+mod common;
+use sqlx::SqlitePool;
+use zap_lib::commands::{
+    self, GoalKind, GoalPeriod, GoalStatus, RoundingMode, RoundingPolicy, RoundingScope,
+    SaveGoalRequest, StreakKind, StreakQuery,
+    daily_focus::{UpsertDailyFocusRequest, toggle_daily_focus_impl, upsert_daily_focus_impl},
+};
+
+/// 在本地日期 date 的中午插入一条计时记录
+async fn insert_local_noon(pool: &SqlitePool, task_id: u32, date: &str, seconds: i64) {
+    let noon = common::local_to_utc(&format!("{date} 12:00:00"));
+    common::insert_entry(pool, task_id, &noon, seconds).await;
+}
+
+fn query(kind: StreakKind, date: &str) -> StreakQuery {
+    StreakQuery {
+        kind,
+        date: Some(date.to_string()),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_tracked_streaks_and_weekends() {
+    let pool = common::setup_memory_db()
+        .await
+        .expect("Failed to setup test database");
+    let task_id = common::create_task(&pool, "连续", |_| {}).await;
+
+    // 2026-03-02 为周一；周四中断，周末无记录
+    for date in [
+        "2026-03-02",
+        "2026-03-03",
+        "2026-03-04",
+        "2026-03-06",
+        "2026-03-09",
+        "2026-03-10",
+    ] {
+        insert_local_noon(&pool, task_id, date, 600).await;
+    }
+
+    // 今天（周三）尚未计时不中断当前连续记录
+    let streaks = commands::get_streaks_impl(&pool, query(StreakKind::Tracked, "2026-03-11"))
+        .await
+        .expect("Failed to get streaks");
+    assert_eq!(streaks.current, 2);
+    assert_eq!(streaks.longest, 3);
+    assert!(!streaks.today_done);
+    let history: Vec<_> = streaks
+        .history
+        .iter()
+        .map(|r| (r.start_date.as_str(), r.end_date.as_str(), r.days))
+        .collect();
+    assert_eq!(
+        history,
+        vec![
+            ("2026-03-09", "2026-03-10", 2),
+            ("2026-03-06", "2026-03-06", 1),
+            ("2026-03-02", "2026-03-04", 3),
+        ]
+    );
+
+    let mut skip = query(StreakKind::Tracked, "2026-03-11");
+    skip.skip_weekends = true;
+    let streaks = commands::get_streaks_impl(&pool, skip).await.unwrap();
+    assert_eq!(streaks.current, 3);
+    assert_eq!(streaks.history.len(), 2);
+    assert_eq!(streaks.history[0].start_date, "2026-03-06");
+
+    // 昨天没有记录则当前连续记录归零
+    let streaks = commands::get_streaks_impl(&pool, query(StreakKind::Tracked, "2026-03-12"))
+        .await
+        .unwrap();
+    assert_eq!(streaks.current, 0);
+    assert_eq!(streaks.longest, 3);
+}
+
+#[tokio::test]
+async fn test_focus_and_daily_goal_streaks() {
+    let pool = common::setup_memory_db()
+        .await
+        .expect("Failed to setup test database");
+    let task_id = common::create_task(&pool, "目标", |_| {}).await;
+
+    for (date, done) in [
+        ("2026-03-09", true),
+        ("2026-03-10", true),
+        ("2026-03-11", false),
+    ] {
+        let focus = upsert_daily_focus_impl(
+            &pool,
+            UpsertDailyFocusRequest {
+                date: date.to_string(),
+                content: format!("重点 {date}"),
+            },
+        )
+        .await
+        .expect("Failed to upsert daily focus");
+        if done {
+            toggle_daily_focus_impl(&pool, focus.id)
+                .await
+                .expect("Failed to toggle daily focus");
+        }
+    }
+
+    let streaks = commands::get_streaks_impl(&pool, query(StreakKind::FocusDone, "2026-03-11"))
+        .await
+        .expect("Failed to get focus streaks");
+    assert_eq!(streaks.current, 2);
+    assert!(!streaks.today_done);
+
+    insert_local_noon(&pool, task_id, "2026-03-08", 4000).await;
+    insert_local_noon(&pool, task_id, "2026-03-09", 3000).await;
+    insert_local_noon(&pool, task_id, "2026-03-09", 1200).await;
+    insert_local_noon(&pool, task_id, "2026-03-10", 1800).await;
+
+    // 缺少 goal_id 或目标不是每日目标时报错
+    assert!(
+        commands::get_streaks_impl(&pool, query(StreakKind::DailyGoal, "2026-03-10"))
+            .await
+            .is_err()
+    );
+    let weekly = commands::create_goal_impl(
+        &pool,
+        SaveGoalRequest {
+            kind: GoalKind::Target,
+            period: GoalPeriod::Week,
+            target_seconds: 3600,
+            category_id: None,
+            tag_id: None,
+            task_id: Some(task_id),
+            is_active: None,
+        },
+    )
+    .await
+    .unwrap();
+    let mut goal_query = query(StreakKind::DailyGoal, "2026-03-10");
+    goal_query.goal_id = Some(weekly.id);
+    assert!(commands::get_streaks_impl(&pool, goal_query).await.is_err());
+
+    let daily = commands::create_goal_impl(
+        &pool,
+        SaveGoalRequest {
+            kind: GoalKind::Target,
+            period: GoalPeriod::Day,
+            target_seconds: 3600,
+            category_id: None,
+            tag_id: None,
+            task_id: Some(task_id),
+            is_active: None,
+        },
+    )
+    .await
+    .unwrap();
+    let mut goal_query = query(StreakKind::DailyGoal, "2026-03-10");
+    goal_query.goal_id = Some(daily.id);
+    let streaks = commands::get_streaks_impl(&pool, goal_query).await.unwrap();
+    assert_eq!(streaks.current, 2);
+    assert_eq!(streaks.longest, 2);
+    assert!(!streaks.today_done);
+}
+
+#[tokio::test]
+async fn test_daily_goal_streak_matches_goal_progress() {
+    let pool = common::setup_memory_db()
+        .await
+        .expect("Failed to setup test database");
+    let task_id = common::create_task(&pool, "口径", |_| {}).await;
+    commands::set_rounding_policy_impl(
+        &pool,
+        RoundingPolicy {
+            mode: RoundingMode::Up,
+            unit_minutes: 15,
+            scope: RoundingScope::Entry,
+        },
+    )
+    .await
+    .expect("Failed to save rounding policy");
+    let goal = commands::create_goal_impl(
+        &pool,
+        SaveGoalRequest {
+            kind: GoalKind::Target,
+            period: GoalPeriod::Day,
+            target_seconds: 3600,
+            category_id: None,
+            tag_id: None,
+            task_id: Some(task_id),
+            is_active: None,
+        },
+    )
+    .await
+    .unwrap();
+
+    // 跨越本地午夜的两小时记录两天各计一小时；3000 秒取整后为一小时
+    let overnight = common::local_to_utc("2026-03-09 23:00:00");
+    common::insert_entry(&pool, task_id, &overnight, 7200).await;
+    insert_local_noon(&pool, task_id, "2026-03-11", 3000).await;
+    insert_local_noon(&pool, task_id, "2026-03-12", 1800).await;
+
+    for date in [
+        "2026-03-08",
+        "2026-03-09",
+        "2026-03-10",
+        "2026-03-11",
+        "2026-03-12",
+    ] {
+        let progress = commands::get_goal_progress_impl(&pool, Some(date.to_string()))
+            .await
+            .unwrap();
+        let mut goal_query = query(StreakKind::DailyGoal, date);
+        goal_query.goal_id = Some(goal.id);
+        let streaks = commands::get_streaks_impl(&pool, goal_query).await.unwrap();
+        assert_eq!(
+            streaks.today_done,
+            progress[0].status == GoalStatus::Met,
+            "{date}"
+        );
+    }
+
+    let mut goal_query = query(StreakKind::DailyGoal, "2026-03-11");
+    goal_query.goal_id = Some(goal.id);
+    let streaks = commands::get_streaks_impl(&pool, goal_query).await.unwrap();
+    assert_eq!(streaks.current, 3);
+    assert!(streaks.today_done);
+}
@@ -0,0 +1,120 @@
+mod common;
+use sqlx::SqlitePool;
+use zap_lib::commands::{self, SaveTagRequest};
+
+async fn create_tag(pool: &SqlitePool, name: String) -> u32 {
+    commands::create_tag_impl(pool, SaveTagRequest { name, color: None })
+        .await
+        .expect("Failed to create tag")
+        .id
+}
+
+#[tokio::test]
+async fn test_filter_tasks_by_tags_and_tag_stats() {
+    let pool: SqlitePool = common::setup_memory_db()
+        .await
+        .expect("Failed to setup test database");
+
+    let urgent = create_tag(&pool, "紧急".to_string()).await;
+    let backend = create_tag(&pool, "后端".to_string()).await;
+
+    let both = common::create_task(&pool, "两个标签", |_| {}).await;
+    let only_urgent = common::create_task(&pool, "仅紧急", |_| {}).await;
+
+    commands::set_task_tags_impl(&pool, both, vec![urgent, backend, urgent])
+        .await
+        .expect("Failed to set task tags");
+    let tags = commands::set_task_tags_impl(&pool, only_urgent, vec![urgent])
+        .await
+        .expect("Failed to set task tags");
+    assert_eq!(tags.len(), 1);
+    assert!(
+        commands::set_task_tags_impl(&pool, both, vec![u32::MAX])
+            .await
+            .is_err()
+    );
+
+    let query = commands::TaskQueryBuilder::default()
+        .tags_any(vec![urgent, backend])
+        .build()
+        .unwrap();
+    let tasks = commands::list_tasks_impl(&pool, query)
+        .await
+        .expect("Failed to list tasks by any tag");
+    assert_eq!(tasks.total, 2);
+
+    let query = commands::TaskQueryBuilder::default()
+        .tags_all(vec![urgent, backend])
+        .build()
+        .unwrap();
+    let tasks = commands::list_tasks_impl(&pool, query)
+        .await
+        .expect("Failed to list tasks by all tags");
+    assert_eq!(tasks.total, 1);
+
+    for (task_id, seconds) in [(both, 600), (only_urgent, 300)] {
+        sqlx::query(
+            "INSERT INTO time_entries (task_id, started_at, ended_at, duration_seconds)
+             VALUES (?, '2025-03-10 09:00:00', datetime('2025-03-10 09:00:00', ? || ' seconds'), ?)",
+        )
+        .bind(task_id)
+        .bind(seconds)
+        .bind(seconds)
+        .execute(&pool)
+        .await
+        .expect("Failed to insert time entry");
+    }
+
+    let stats = commands::get_tag_stats_impl(
+        &pool,
+        commands::StatsDateQuery {
+            start_date: Some("2025-03-10".to_string()),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Failed to get tag stats");
+    let seconds_of = |tag_id| {
+        stats
+            .tags
+            .iter()
+            .find(|t| t.tag_id == Some(tag_id))
+            .map(|t| (t.seconds, t.task_count))
+    };
+    assert_eq!(seconds_of(urgent), Some((900, 2)));
+    assert_eq!(seconds_of(backend), Some((600, 1)));
+}
+
+#[tokio::test]
+async fn test_duplicate_tag_name() {
+    let pool = common::setup_memory_db()
+        .await
+        .expect("Failed to setup test database");
+
+    create_tag(&pool, "Bug".to_string()).await;
+    let review = create_tag(&pool, "review".to_string()).await;
+
+    // 名称不区分大小写，重复时返回明确的错误而不是数据库约束错误
+    let err = commands::create_tag_impl(
+        &pool,
+        SaveTagRequest {
+            name: " bug ".to_string(),
+            color: None,
+        },
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.to_string(), "Tag already exists: bug");
+
+    let err = commands::update_tag_impl(
+        &pool,
+        review,
+        SaveTagRequest {
+            name: "BUG".to_string(),
+            color: None,
+        },
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.to_string(), "Tag already exists: BUG");
+}
@@ -79,3 +79,259 @@ async fn test_toggle_task_done() {
         .await
         .expect("Failed finish task");
 }
+
+#[tokio::test]
+async fn test_due_date_filters() {
+    let pool: SqlitePool = common::setup_memory_db()
+        .await
+        .expect("Failed to setup test database");
+
+    let title = "截止任务";
+    let yesterday = (chrono::Local::now().date_naive() - chrono::Duration::days(1))
+        .format("%Y-%m-%d")
+        .to_string();
+
+    let req = commands::CreateTaskRequestBuilder::default()
+        .title(title)
+        .due_date(yesterday)
+        .build()
+        .unwrap();
+    commands::add_task_impl(&pool, req)
+        .await
+        .expect("Failed to add task");
+
+    let overdue = |due| {
+        commands::TaskQueryBuilder::default()
+            .task_name(title)
+            .due(due)
+            .build()
+            .unwrap()
+    };
+    let tasks = commands::list_tasks_impl(&pool, overdue(commands::DueFilter::Overdue))
+        .await
+        .expect("Failed to list overdue tasks");
+    assert_eq!(tasks.total, 1);
+    let tasks = commands::list_tasks_impl(&pool, overdue(commands::DueFilter::Today))
+        .await
+        .expect("Failed to list tasks due today");
+    assert_eq!(tasks.total, 0);
+
+    let count = commands::count_overdue_tasks_impl(&pool)
+        .await
+        .expect("Failed to count overdue tasks");
+    assert_eq!(count, 1);
+
+    let req = commands::CreateTaskRequestBuilder::default()
+        .title("错误日期")
+        .due_date("2024/01/01")
+        .build()
+        .unwrap();
+    assert!(commands::add_task_impl(&pool, req).await.is_err());
+}
+
+#[tokio::test]
+async fn test_priority_and_manual_sort() {
+    let pool: SqlitePool = common::setup_memory_db()
+        .await
+        .expect("Failed to setup test database");
+
+    let prefix = format!("排序任务 {}", chrono::Utc::now().timestamp_micros());
+    for (suffix, priority) in [
+        ("a", commands::TaskPriority::P3),
+        ("b", commands::TaskPriority::P0),
+        ("c", commands::TaskPriority::P1),
+    ] {
+        let req = commands::CreateTaskRequestBuilder::default()
+            .title(format!("{prefix} {suffix}"))
+            .priority(priority)
+            .build()
+            .unwrap();
+        commands::add_task_impl(&pool, req)
+            .await
+            .expect("Failed to add task");
+    }
+
+    let ids: Vec<u32> = sqlx::query_scalar("SELECT id FROM tasks WHERE title LIKE ? ORDER BY id")
+        .bind(format!("{prefix}%"))
+        .fetch_all(&pool)
+        .await
+        .expect("Failed to query task ids");
+
+    let sorted_ids = |sort| {
+        let pool = pool.clone();
+        let prefix = prefix.clone();
+        async move {
+            let query = commands::TaskQueryBuilder::default()
+                .task_name(prefix)
+                .sort(sort)
+                .build()
+                .unwrap();
+            let tasks = commands::list_tasks_impl(&pool, query)
+                .await
+                .expect("Failed to list tasks");
+            let json = serde_json::to_value(&tasks.data).unwrap();
+            json.as_array()
+                .unwrap()
+                .iter()
+                .map(|t| t["task_id"].as_u64().unwrap() as u32)
+                .collect::<Vec<_>>()
+        }
+    };
+
+    assert_eq!(
+        sorted_ids(commands::TaskSort::Priority).await,
+        vec![ids[1], ids[2], ids[0]]
+    );
+    assert_eq!(sorted_ids(commands::TaskSort::Manual).await, ids);
+
+    // 拖拽排序不改变“最近更新”的顺序
+    for (task_id, updated_at) in ids.iter().zip(["2024-01-03", "2024-01-02", "2024-01-01"]) {
+        sqlx::query("UPDATE tasks SET updated_at = ? WHERE id = ?")
+            .bind(updated_at)
+            .bind(task_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+    }
+    let updated_before = sorted_ids(commands::TaskSort::Updated).await;
+    assert_eq!(updated_before, ids);
+    commands::reorder_tasks_impl(&pool, vec![ids[2], ids[0], ids[1]])
+        .await
+        .expect("Failed to reorder tasks");
+    assert_eq!(
+        sorted_ids(commands::TaskSort::Manual).await,
+        vec![ids[2], ids[0], ids[1]]
+    );
+    assert_eq!(
+        sorted_ids(commands::TaskSort::Updated).await,
+        updated_before
+    );
+    assert!(
+        commands::reorder_tasks_impl(&pool, vec![ids[0], ids[0]])
+            .await
+            .is_err()
+    );
+
+    for sort in [
+        commands::TaskSort::Updated,
+        commands::TaskSort::TotalTime,
+        commands::TaskSort::TodayTime,
+        commands::TaskSort::EstimateRemaining,
+    ] {
+        assert_eq!(sorted_ids(sort).await.len(), 3);
+    }
+}
+
+#[tokio::test]
+async fn test_rich_task_filters() {
+    let pool: SqlitePool = common::setup_memory_db()
+        .await
+        .expect("Failed to setup test database");
+
+    let prefix = format!("筛选任务 {}", chrono::Utc::now().timestamp_micros());
+    let today = chrono::Local::now().date_naive().to_string();
+    let yesterday = (chrono::Local::now().date_naive() - chrono::Duration::days(1)).to_string();
+
+    let category_id = sqlx::query("INSERT INTO categories (name, color) VALUES (?, '#654321')")
+        .bind(&prefix)
+        .execute(&pool)
+        .await
+        .expect("Failed to insert category")
+        .last_insert_rowid() as u32;
+
+    let req = commands::CreateTaskRequestBuilder::default()
+        .title(format!("{prefix} 分类"))
+        .category_id(category_id)
+        .estimate_seconds(600)
+        .build()
+        .unwrap();
+    commands::add_task_impl(&pool, req)
+        .await
+        .expect("Failed to add task");
+    let req = commands::CreateTaskRequestBuilder::default()
+        .title(format!("{prefix} 未分类"))
+        .build()
+        .unwrap();
+    commands::add_task_impl(&pool, req)
+        .await
+        .expect("Failed to add task");
+
+    let ids: Vec<u32> = sqlx::query_scalar("SELECT id FROM tasks WHERE title LIKE ? ORDER BY id")
+        .bind(format!("{prefix}%"))
+        .fetch_all(&pool)
+        .await
+        .expect("Failed to query task ids");
+    // 本地零点刚过的记录：东八区等时区的 UTC 日期仍是昨天，但应算作今天
+    let early = common::local_to_utc(&format!("{today} 00:00:30"));
+    common::insert_entry(&pool, ids[0], &early, 60).await;
+    commands::finish_task_impl(&pool, ids[1])
+        .await
+        .expect("Failed to finish task");
+
+    let count = |builder: &mut commands::TaskQueryBuilder| {
+        let query = builder.task_name(prefix.as_str()).build().unwrap();
+        let pool = pool.clone();
+        async move {
+            commands::list_tasks_impl(&pool, query)
+                .await
+                .expect("Failed to list tasks")
+                .total
+        }
+    };
+    let query = commands::TaskQueryBuilder::default;
+
+    assert_eq!(count(&mut query()).await, 1);
+    assert_eq!(count(query().include_finished(true)).await, 2);
+    assert_eq!(count(query().category_ids(vec![category_id])).await, 1);
+    assert_eq!(
+        count(query().uncategorized(true).include_finished(true)).await,
+        1
+    );
+    assert_eq!(
+        count(
+            query()
+                .category_ids(vec![category_id])
+                .uncategorized(true)
+                .include_finished(true)
+        )
+        .await,
+        2
+    );
+    assert_eq!(
+        count(query().created_from(today.as_str()).include_finished(true)).await,
+        2
+    );
+    assert_eq!(
+        count(
+            query()
+                .created_to(yesterday.as_str())
+                .include_finished(true)
+        )
+        .await,
+        0
+    );
+    assert_eq!(
+        count(
+            query()
+                .completed_from(today.as_str())
+                .completed_to(today.as_str())
+                .include_finished(true)
+        )
+        .await,
+        1
+    );
+    assert_eq!(
+        count(query().tracked_from(today.as_str()).include_finished(true)).await,
+        1
+    );
+    assert_eq!(count(query().has_estimate(true)).await, 1);
+    assert_eq!(
+        count(query().has_estimate(false).include_finished(true)).await,
+        1
+    );
+    assert_eq!(count(query().has_time_today(true)).await, 1);
+    assert_eq!(
+        count(query().has_time_today(false).include_finished(true)).await,
+        1
+    );
+}
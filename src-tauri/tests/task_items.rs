@@ -0,0 +1,68 @@
+mod common;
+use sqlx::SqlitePool;
+use zap_lib::commands::{self, AddTaskItemRequest, OpenItemsPolicy, UpdateTaskItemRequest};
+
+#[tokio::test]
+async fn test_task_items_crud_and_finish_policy() {
+    let pool: SqlitePool = common::setup_memory_db()
+        .await
+        .expect("Failed to setup test database");
+
+    let task_id = common::create_task(&pool, "清单任务", |_| {}).await;
+
+    let mut ids = Vec::new();
+    for step in ["需求评审", "编码", "自测"] {
+        let item = commands::add_task_item_impl(
+            &pool,
+            AddTaskItemRequest {
+                task_id,
+                title: step.to_string(),
+            },
+        )
+        .await
+        .expect("Failed to add task item");
+        ids.push(item.id);
+    }
+
+    ids.reverse();
+    let items = commands::reorder_task_items_impl(&pool, task_id, ids.clone())
+        .await
+        .expect("Failed to reorder task items");
+    assert_eq!(items.iter().map(|i| i.id).collect::<Vec<_>>(), ids);
+    assert!(
+        commands::reorder_task_items_impl(&pool, task_id, ids[1..].to_vec())
+            .await
+            .is_err()
+    );
+
+    commands::update_task_item_impl(
+        &pool,
+        ids[0],
+        UpdateTaskItemRequest {
+            is_done: Some(true),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Failed to update task item");
+
+    commands::set_open_items_policy_impl(&pool, OpenItemsPolicy::Require)
+        .await
+        .expect("Failed to set policy");
+    assert!(commands::finish_task_impl(&pool, task_id).await.is_err());
+
+    commands::set_open_items_policy_impl(&pool, OpenItemsPolicy::Complete)
+        .await
+        .expect("Failed to set policy");
+    commands::finish_task_impl(&pool, task_id)
+        .await
+        .expect("Failed to finish task");
+    let items = commands::list_task_items_impl(&pool, task_id)
+        .await
+        .expect("Failed to list task items");
+    assert!(items.iter().all(|i| i.is_done));
+
+    commands::set_open_items_policy_impl(&pool, OpenItemsPolicy::Ignore)
+        .await
+        .expect("Failed to reset policy");
+}